@@ -0,0 +1,155 @@
+use proptest::{array, prelude::*};
+
+use crate::{
+  byte::ByteSize,
+  field::FieldSpec,
+  instruction::{Instruction, Operation},
+  value::MixValue,
+};
+
+/// Words and registers whose bytes all hold values of the byte size
+pub fn value<const BYTES: usize>(byte_size: ByteSize) -> impl Strategy<Value = MixValue<BYTES>> {
+  (array::uniform(0..byte_size.get() as u8), any::<bool>())
+    .prop_map(|(bytes, sign)| MixValue::from_bytes(bytes, sign))
+}
+
+/// Field specifications (L:R) with L not after R
+pub fn field() -> impl Strategy<Value = FieldSpec> {
+  (0..=5u32)
+    .prop_flat_map(|right| (0..=right, Just(right)))
+    .prop_map(|(left, right)| FieldSpec::new(left, right))
+}
+
+/// Operations of the machine, including the attachments
+pub fn operation() -> impl Strategy<Value = Operation> {
+  (0..64u32, 0..10u32).prop_filter_map("Operation does not exist", |(code, field)| {
+    Operation::decode(code, field).ok()
+  })
+}
+
+/// Instructions that decode, with an address that fits two binary bytes
+pub fn instruction() -> impl Strategy<Value = Instruction> {
+  (
+    any::<bool>(),
+    0..4096u32,
+    0..=6u32,
+    operation(),
+    field(),
+    0..64u32,
+  )
+    .prop_map(|(sign, address, index, operation, field, modifier)| {
+      let modifier = if operation.has_field_spec() {
+        field.encode()
+      } else {
+        modifier
+      };
+
+      Instruction::new(sign, address, index, modifier, operation)
+    })
+}
+
+/// The bytes hold values of the byte size given, binary by default
+impl<const BYTES: usize> Arbitrary for MixValue<BYTES> {
+  type Parameters = ByteSize;
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(byte_size: Self::Parameters) -> Self::Strategy {
+    value(byte_size).boxed()
+  }
+}
+
+impl Arbitrary for FieldSpec {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+    field().boxed()
+  }
+}
+
+impl Arbitrary for Instruction {
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+    instruction().boxed()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    assembler::Assembly, register::Register, sourcemap::SourceMap, symbols::SymbolTable,
+    word::Word, Data, FieldRead, FieldWrite,
+  };
+
+  proptest! {
+    #[test]
+    fn test_word_round_trip(word in any::<Word>()) {
+      prop_assert_eq!(Word::from(word.read()), word);
+    }
+
+    #[test]
+    fn test_register_round_trip(register in any::<Register>()) {
+      prop_assert_eq!(Register::from(Word::from(register)), register);
+    }
+
+    #[test]
+    fn test_field_round_trip(word in any::<Word>(), field in any::<FieldSpec>()) {
+      let mut copy = Word::default();
+
+      copy.write_with_modifier(word.read_with_modifier(field).unwrap(), field).unwrap();
+
+      prop_assert_eq!(copy.read_with_modifier(field), word.read_with_modifier(field));
+    }
+
+    #[test]
+    fn test_field_spec_round_trip(field in any::<FieldSpec>()) {
+      prop_assert_eq!(FieldSpec::try_from(field.encode()), Ok(field));
+    }
+
+    #[test]
+    fn test_instruction_round_trip(instruction in any::<Instruction>()) {
+      prop_assert_eq!(Instruction::try_from(u32::from(instruction)), Ok(instruction));
+      prop_assert_eq!(Instruction::try_from(Word::from(instruction)), Ok(instruction));
+    }
+
+    #[test]
+    fn test_disassembly_round_trip(
+      words in proptest::collection::vec(any::<Word>(), 1..8),
+      decimal in any_with::<Word>(ByteSize::DECIMAL),
+    ) {
+      let assembly = |words: Vec<Word>| Assembly {
+        words: words.into_iter().enumerate().collect(),
+        start: Some(0),
+        symbols: SymbolTable::default(),
+        source_map: SourceMap::default(),
+      };
+
+      prop_assert_eq!(assembly(words).check_round_trip(ByteSize::BINARY), Ok(()));
+      prop_assert_eq!(assembly(vec![decimal]).check_round_trip(ByteSize::DECIMAL), Ok(()));
+    }
+
+    #[test]
+    fn test_add_commutes(
+      left in any_with::<Word>(ByteSize::DECIMAL),
+      right in any_with::<Word>(ByteSize::DECIMAL),
+    ) {
+      let sum = left.checked_add(&right, ByteSize::DECIMAL);
+      let other = right.checked_add(&left, ByteSize::DECIMAL);
+
+      prop_assert_eq!(sum.overflow, other.overflow);
+      prop_assert!(sum.result.mix_eq(&other.result));
+    }
+
+    #[test]
+    fn test_sub_undoes_add(left in any::<Word>(), right in any::<Word>()) {
+      let sum = left.checked_add(&right, ByteSize::BINARY);
+      let difference = sum.result.checked_sub(&right, ByteSize::BINARY);
+
+      prop_assume!(!sum.overflow);
+      prop_assert!(difference.result.mix_eq(&left));
+    }
+  }
+}
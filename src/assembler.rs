@@ -0,0 +1,1956 @@
+use std::{
+  cell::RefCell,
+  collections::{BTreeMap, BTreeSet, HashMap},
+  fs, iter,
+  path::{Path, PathBuf},
+  rc::Rc,
+  str::FromStr,
+};
+
+use crate::{
+  byte::ByteSize,
+  charcode,
+  computer::{Computer, MEMORY},
+  error::{AssemblyError, AssemblyErrorKind},
+  field::FieldSpec,
+  instruction::{Instruction, Operation},
+  object::{Object, ObjectWord, Part, Relocation, Value},
+  program::Program,
+  sourcemap::SourceMap,
+  symbols::SymbolTable,
+  word::Word,
+};
+
+/// Longest name of a symbol
+const SYMBOL_LENGTH: usize = 10;
+
+/// Words assembled from a MIXAL source, by the location they go to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assembly {
+  /// Locations and the words that go there, in the order of the source
+  pub words: Vec<(usize, Word)>,
+  /// Location the program starts at, given by END
+  pub start: Option<usize>,
+  /// Symbols the source defines
+  pub symbols: SymbolTable,
+  /// Lines the words come from
+  pub source_map: SourceMap,
+}
+
+impl Assembly {
+  /// Places the words into the memory of the machine
+  pub fn load(&self, computer: &mut Computer) {
+    for &(location, word) in &self.words {
+      computer.memory[location] = word;
+    }
+  }
+
+  /// Makes a program of the words, a segment for each run of locations one
+  /// after another, that starts where END says or else at its first word
+  ///
+  /// The program keeps the source map, so that a fault can be traced to its
+  /// line.
+  pub fn to_program(&self) -> Program {
+    let mut program = Program::new();
+
+    for &(location, word) in &self.words {
+      match program.segments.last_mut() {
+        Some((origin, words)) if *origin + words.len() == location => words.push(word),
+        _ => program.add_segment(location, vec![word]),
+      }
+    }
+
+    program.entry = self
+      .start
+      .or_else(|| self.words.first().map(|&(location, _)| location))
+      .unwrap_or(0);
+    program.source_map = self.source_map.clone();
+
+    program
+  }
+}
+
+/// Assembler of MIXAL, the assembly language of MIX
+///
+/// A line holds a location field, an operation and an address field apart
+/// by blanks, a line that starts with a blank has no location field and one
+/// that starts with `*` is a comment. The text after the address field is a
+/// comment as well.
+///
+/// Besides the operations of the machine a line may hold one of the
+/// pseudo-operations:
+///
+/// - `EQU` defines the symbol of the location field as the value of its
+///   address field, which takes only symbols defined above
+/// - `ORIG` sets the location of the next line
+/// - `CON` assembles the W-value of its address field as a word
+/// - `ALF` assembles the five characters of its address field as a word,
+///   quoted if they start or end with a blank
+/// - `END` ends the source, the W-value of its address field is the
+///   location the program starts at
+/// - `INCLUDE`, or `INSERT`, reads the lines of the file its address field
+///   names in place of its own line
+///
+/// A W-value is a list of expressions apart by commas, each with an optional
+/// field, `E1(F1),E2(F2),...`. It is the word that +0 becomes once the value
+/// of each expression is stored into its field in turn the way STA stores,
+/// the field is (0:5) if none is given.
+///
+/// The address of an instruction may be a literal constant, `=W=`, which
+/// stands for the location of a word holding the value of `W`. The words of
+/// the literal constants follow the last line before END, the same constant
+/// is kept once.
+///
+/// The local symbols `0H` to `9H` may be defined by any number of lines, in
+/// an address field `dB` stands for the last `dH` above the line and `dF`
+/// for the first one below it.
+///
+/// The source is read twice: the first pass gives each line its location
+/// and defines the symbols, the second assembles the words, so a symbol may
+/// be used before the line that defines it.
+///
+/// The file of INCLUDE is looked for by the file that includes it, or in
+/// the current directory for a source given as text, and then in each of
+/// the include paths in turn. A file cannot include itself, nor a file that
+/// includes it. An END in an included file ends the whole source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assembler {
+  /// Number of values a byte of the machine the words are for holds
+  pub byte_size: ByteSize,
+  /// Directories the files of INCLUDE are looked for in
+  pub include_paths: Vec<PathBuf>,
+}
+
+impl Assembler {
+  /// Creates the assembler for a binary machine
+  pub fn new() -> Self {
+    Self {
+      byte_size: ByteSize::BINARY,
+      include_paths: Vec::new(),
+    }
+  }
+
+  /// Creates the assembler for a decimal machine
+  pub fn decimal() -> Self {
+    Self {
+      byte_size: ByteSize::DECIMAL,
+      include_paths: Vec::new(),
+    }
+  }
+
+  /// Assembles the source
+  pub fn assemble(&self, source: &str) -> Result<Assembly, AssemblyError> {
+    self.assemble_source(None, source)
+  }
+
+  /// Assembles the source read from the file, which the source map names
+  pub fn assemble_named(
+    &self,
+    file: impl AsRef<Path>,
+    source: &str,
+  ) -> Result<Assembly, AssemblyError> {
+    self.assemble_source(Some(file.as_ref()), source)
+  }
+
+  fn assemble_source(&self, file: Option<&Path>, source: &str) -> Result<Assembly, AssemblyError> {
+    let lines = self.read(file, source)?;
+    let pass = self.passes(&lines, 0, Symbols::default())?;
+    let mut source_map = SourceMap::default();
+
+    for &(location, _, statement) in &pass.words {
+      let statement = &pass.statements[statement];
+
+      source_map.insert(location, statement.file, statement.line);
+    }
+
+    Ok(Assembly {
+      words: pass
+        .words
+        .iter()
+        .map(|&(location, word, _)| (location, word))
+        .collect(),
+      start: pass.start,
+      symbols: pass.symbols.table(),
+      source_map,
+    })
+  }
+
+  /// Assembles the source as a relocatable module, with its locations from
+  /// 0 and the symbols it does not define as external
+  ///
+  /// The address of an instruction and the W-value of CON or END may add a
+  /// single location of the module or a single external symbol, or
+  /// subtract one, and the linker adjusts them. The words after an ORIG to
+  /// a number stay at their locations, while `ORIG *+N` keeps them moving
+  /// with the module. A symbol that takes the value of an external one is
+  /// not given to other modules.
+  pub fn assemble_object(&self, source: &str) -> Result<Object, AssemblyError> {
+    let lines = self.read(None, source)?;
+    let base = self.passes(&lines, 0, Symbols::external(HashMap::new()))?;
+    let externals = base.symbols.externals();
+    let mut words = base
+      .words
+      .iter()
+      .map(|&(location, word, _)| ObjectWord {
+        location,
+        relocatable: false,
+        word,
+        relocations: Vec::new(),
+      })
+      .collect::<Vec<_>>();
+    let mut symbols = base
+      .symbols
+      .values
+      .iter()
+      .map(|(name, &value)| {
+        let value = Value {
+          value,
+          relocatable: false,
+        };
+
+        (name.clone(), Some(value))
+      })
+      .collect::<BTreeMap<_, _>>();
+    let mut start = base.start.map(|start| Value {
+      value: start as i64,
+      relocatable: false,
+    });
+
+    // The passes again with the module moved by one and by two, or with an
+    // external symbol of 1 and of 2, show what each value depends on
+    for symbol in [None].into_iter().chain(externals.iter().map(Some)) {
+      let vary = |offset: i64| match symbol {
+        None => self.passes(&lines, offset, Symbols::external(HashMap::new())),
+        Some(name) => self.passes(
+          &lines,
+          0,
+          Symbols::external(HashMap::from([(name.clone(), offset)])),
+        ),
+      };
+      let passes = [vary(1)?, vary(2)?];
+
+      for (index, object_word) in words.iter_mut().enumerate() {
+        let statement = &base.statements[base.words[index].2];
+        let not_relocatable =
+          || statement.operand_error(AssemblyErrorKind::NotRelocatable(statement.operand.into()));
+        let [(first, _, _), (second, _, _)] = passes.each_ref().map(|pass| pass.words[index]);
+
+        match (
+          first - object_word.location,
+          second - object_word.location,
+          symbol,
+        ) {
+          (0, 0, _) => {}
+          (1, 2, None) => object_word.relocatable = true,
+          _ => return Err(not_relocatable()),
+        }
+
+        let part = match statement.directive() {
+          Some(_) => Part::Word,
+          None => Part::Address,
+        };
+        let [first, second] = passes.each_ref().map(|pass| pass.words[index].1);
+        let difference = |word: &Word| part.difference(word, &object_word.word, self.byte_size);
+
+        match (difference(&first), difference(&second)) {
+          (Some(0), Some(0)) => {}
+          (Some(1), Some(2)) => object_word.relocations.push(Relocation {
+            part,
+            symbol: symbol.cloned(),
+          }),
+          _ => return Err(not_relocatable()),
+        }
+      }
+
+      for (name, value) in symbols.iter_mut() {
+        let differences = passes
+          .each_ref()
+          .map(|pass| pass.symbols.values[name] - base.symbols.values[name]);
+
+        match (differences, symbol, value.as_mut()) {
+          ([0, 0], _, _) => {}
+          ([1, 2], None, Some(value)) => value.relocatable = true,
+          _ => *value = None,
+        }
+      }
+
+      if let (Some(value), Some(end)) = (start.as_mut(), base.end) {
+        let differences = passes
+          .each_ref()
+          .map(|pass| pass.start.unwrap_or(0) as i64 - value.value);
+
+        match (differences, symbol) {
+          ([0, 0], _) => {}
+          ([1, 2], None) => value.relocatable = true,
+          _ => {
+            let statement = &base.statements[end];
+
+            return Err(
+              statement.operand_error(AssemblyErrorKind::NotRelocatable(statement.operand.into())),
+            );
+          }
+        }
+      }
+    }
+
+    Ok(Object {
+      byte_size: self.byte_size,
+      words,
+      symbols: symbols
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect(),
+      start,
+    })
+  }
+
+  /// Reads the lines of the source, with the lines of the files INCLUDE
+  /// names in place of its own
+  pub(crate) fn read(&self, file: Option<&Path>, source: &str) -> Result<Vec<Line>, AssemblyError> {
+    let mut including = file
+      .and_then(|file| file.canonicalize().ok())
+      .into_iter()
+      .collect();
+    let mut lines = Vec::new();
+
+    self.include(file, source, &mut including, &mut lines)?;
+
+    Ok(lines)
+  }
+
+  /// Adds the lines of the source of the file to the lines read so far,
+  /// given the files that include it
+  fn include(
+    &self,
+    file: Option<&Path>,
+    source: &str,
+    including: &mut Vec<PathBuf>,
+    lines: &mut Vec<Line>,
+  ) -> Result<(), AssemblyError> {
+    let shared = file.map(Rc::<Path>::from);
+
+    for (index, text) in source.lines().enumerate() {
+      let statement = match Statement::parse(index + 1, text) {
+        Ok(Some(statement)) if is_include(statement.operation) => statement,
+        _ => {
+          lines.push(Line {
+            file: shared.clone(),
+            number: index + 1,
+            text: text.to_string(),
+          });
+          continue;
+        }
+      };
+      let name = statement.operand;
+      let error = |kind| statement.operand_error(kind).in_file(file);
+
+      if let Some(label) = statement.label {
+        let kind = AssemblyErrorKind::InvalidSyntax(label.to_string());
+
+        return Err(statement.label_error(kind).in_file(file));
+      }
+
+      let path = self
+        .resolve(file, name)
+        .ok_or_else(|| error(AssemblyErrorKind::CannotInclude(name.to_string())))?;
+      let canonical = path
+        .canonicalize()
+        .map_err(|_| error(AssemblyErrorKind::CannotInclude(name.to_string())))?;
+
+      if including.contains(&canonical) {
+        return Err(error(AssemblyErrorKind::CyclicInclude(name.to_string())));
+      }
+
+      let included = fs::read_to_string(&path)
+        .map_err(|_| error(AssemblyErrorKind::CannotInclude(name.to_string())))?;
+
+      including.push(canonical);
+      self.include(Some(&path), &included, including, lines)?;
+      including.pop();
+    }
+
+    Ok(())
+  }
+
+  /// Finds the file INCLUDE names, by the file that includes it and then in
+  /// the include paths
+  fn resolve(&self, file: Option<&Path>, name: &str) -> Option<PathBuf> {
+    let directory = file.and_then(Path::parent).unwrap_or(Path::new(""));
+
+    iter::once(directory)
+      .chain(self.include_paths.iter().map(PathBuf::as_path))
+      .map(|directory| directory.join(name))
+      .find(|path| path.is_file())
+  }
+
+  /// Assembles the lines in two passes, with the location counter starting
+  /// at the origin and the symbols the source defines added to the given
+  /// ones
+  pub(crate) fn passes<'a>(
+    &self,
+    lines: &'a [Line],
+    origin: i64,
+    symbols: Symbols,
+  ) -> Result<Pass<'a>, AssemblyError> {
+    let mut statements = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+      let file = line.file.as_deref();
+      let statement =
+        Statement::parse(line.number, &line.text).map_err(|error| error.in_file(file))?;
+
+      if let Some(statement) = statement {
+        statements.push(Statement {
+          file,
+          index,
+          ..statement
+        });
+      }
+    }
+
+    let symbols = self.define(&mut statements, origin, symbols)?;
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut end = None;
+
+    for (index, statement) in statements.iter().enumerate() {
+      match statement.directive() {
+        Some(Directive::Equ | Directive::Orig) => {}
+        Some(Directive::End) => {
+          start = self.start(statement, &symbols)?;
+          end = Some(index);
+        }
+        _ => {
+          let (location, word) = self.assemble_statement(statement, &symbols)?;
+
+          words.push((location, word, index));
+        }
+      }
+    }
+
+    Ok(Pass {
+      statements,
+      words,
+      start,
+      end,
+      symbols,
+    })
+  }
+
+  /// Gives each statement its location from the origin and defines the
+  /// symbols of the location fields, the first pass
+  ///
+  /// The statements after END are dropped and a CON for each literal
+  /// constant is put before it.
+  fn define<'a>(
+    &self,
+    statements: &mut Vec<Statement<'a>>,
+    origin: i64,
+    mut symbols: Symbols,
+  ) -> Result<Symbols, AssemblyError> {
+    let mut pool: Vec<Statement<'a>> = Vec::new();
+    let mut location = origin;
+    let mut end = None;
+
+    for (position, statement) in statements.iter_mut().enumerate() {
+      let directive = statement.directive();
+
+      if directive == Some(Directive::End) {
+        end = Some(position);
+        break;
+      }
+
+      let value = match directive {
+        Some(Directive::Equ) => self.value(statement, &symbols)?,
+        _ => location,
+      };
+
+      statement.location = location;
+
+      if let Some(label) = statement.label {
+        symbols
+          .define(label, value, statement.index)
+          .map_err(|kind| statement.label_error(kind))?;
+      }
+
+      match directive {
+        Some(Directive::Equ) => {}
+        Some(Directive::Orig) => location = self.value(statement, &symbols)?,
+        Some(Directive::Con | Directive::Alf | Directive::End) => location += 1,
+        None => {
+          statement.operation()?;
+          location += 1;
+
+          let literal = split_literal(statement.operand)
+            .filter(|&literal| !pool.iter().any(|constant| constant.operand == literal));
+
+          if let Some(literal) = literal {
+            pool.push(Statement {
+              file: statement.file,
+              line: statement.line,
+              index: statement.index,
+              label: None,
+              operation: "CON",
+              operand: literal,
+              location: 0,
+              operation_column: statement.operand_column,
+              operand_column: statement.operand_column + 1,
+            });
+          }
+        }
+      }
+    }
+
+    for constant in &mut pool {
+      constant.location = location;
+      symbols
+        .literals
+        .insert(constant.operand.to_string(), location);
+      location += 1;
+    }
+
+    let end = end.unwrap_or(statements.len());
+
+    statements.truncate(end + 1);
+
+    // The symbol of END is the location after the literal constants
+    if let Some(statement) = statements.get_mut(end) {
+      statement.location = location;
+
+      if let Some(label) = statement.label {
+        symbols
+          .define(label, location, statement.index)
+          .map_err(|kind| statement.label_error(kind))?;
+      }
+    }
+
+    statements.splice(end..end, pool);
+
+    Ok(symbols)
+  }
+
+  /// Evaluates the address field of the statement as a single expression
+  fn value(&self, statement: &Statement, symbols: &Symbols) -> Result<i64, AssemblyError> {
+    Parser::new(statement, symbols, self.byte_size).value()
+  }
+
+  /// Finds the location the program starts at from the address field of
+  /// END, none if it is empty
+  fn start(
+    &self,
+    statement: &Statement,
+    symbols: &Symbols,
+  ) -> Result<Option<usize>, AssemblyError> {
+    if statement.operand.is_empty() {
+      return Ok(None);
+    }
+
+    let start = self.byte_size.value(&self.w_value(statement, symbols)?);
+
+    match usize::try_from(start) {
+      Ok(location) if location < MEMORY => Ok(Some(location)),
+      _ => Err(statement.operand_error(AssemblyErrorKind::InvalidLocation(start))),
+    }
+  }
+
+  /// Assembles the word of the statement, the second pass
+  fn assemble_statement(
+    &self,
+    statement: &Statement,
+    symbols: &Symbols,
+  ) -> Result<(usize, Word), AssemblyError> {
+    if !(0..MEMORY as i64).contains(&statement.location) {
+      return Err(
+        statement.operation_error(AssemblyErrorKind::InvalidLocation(statement.location)),
+      );
+    }
+
+    let word = match statement.directive() {
+      Some(Directive::Con) => self.w_value(statement, symbols)?,
+      Some(Directive::Alf) => characters(statement)?,
+      _ => self.instruction(statement, symbols)?.encode(self.byte_size),
+    };
+
+    Ok((statement.location as usize, word))
+  }
+
+  /// Reads the address field of the instruction of the statement as its
+  /// address, index and field, if one is given
+  pub(crate) fn address_field(
+    &self,
+    statement: &Statement,
+    symbols: &Symbols,
+  ) -> Result<(i64, i64, Option<i64>), AssemblyError> {
+    Parser::new(statement, symbols, self.byte_size).address_field()
+  }
+
+  /// Evaluates the address field of the statement as a W-value
+  fn w_value(&self, statement: &Statement, symbols: &Symbols) -> Result<Word, AssemblyError> {
+    Parser::new(statement, symbols, self.byte_size).w_value()
+  }
+
+  /// Assembles the instruction of an operation of the machine
+  fn instruction(
+    &self,
+    statement: &Statement,
+    symbols: &Symbols,
+  ) -> Result<Instruction, AssemblyError> {
+    let operation = statement.operation()?;
+    let (address, index, field) = self.address_field(statement, symbols)?;
+    let field = field.unwrap_or_else(|| operation.default_field() as i64);
+    // An address that starts with a minus and comes to zero is -0
+    let sign = address > 0 || (address == 0 && !statement.operand.starts_with('-'));
+
+    Ok(Instruction::new(
+      sign,
+      address.unsigned_abs() as u32,
+      index as u32,
+      field as u32,
+      operation,
+    ))
+  }
+}
+
+impl Default for Assembler {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Assembles the source for a binary machine
+pub fn assemble(source: &str) -> Result<Assembly, AssemblyError> {
+  Assembler::new().assemble(source)
+}
+
+/// Reads an instruction from a line of MIXAL without a location field, such
+/// as `LDA 2000,1(0:3)`
+///
+/// The address field takes no symbols and `*` stands for location 0, the
+/// parts must fit a binary machine.
+impl FromStr for Instruction {
+  type Err = AssemblyError;
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    let statement = Statement::from_fields(1, text, None, text)?;
+
+    Assembler::new().instruction(&statement, &Symbols::default())
+  }
+}
+
+/// Assembles the word of ALF: the five characters following the operation,
+/// or the ones between quotes, filled with blanks
+fn characters(statement: &Statement) -> Result<Word, AssemblyError> {
+  let invalid = || {
+    statement.operand_error(AssemblyErrorKind::InvalidSyntax(
+      statement.operand.to_string(),
+    ))
+  };
+  let text = match statement.operand.strip_prefix('"') {
+    Some(quoted) => quoted.split_once('"').ok_or_else(invalid)?.0,
+    None => {
+      let length = statement
+        .operand
+        .char_indices()
+        .nth(Word::BYTES)
+        .map_or(statement.operand.len(), |(position, _)| position);
+
+      &statement.operand[..length]
+    }
+  };
+
+  if text.chars().count() > Word::BYTES {
+    return Err(invalid());
+  }
+
+  charcode::str_to_words(&format!("{:<1$}", text, Word::BYTES))
+    .map(|words| words[0])
+    .map_err(|_| invalid())
+}
+
+/// Line of the source, which may be in an included file
+pub(crate) struct Line {
+  /// File the line is in, none for the source given as text
+  file: Option<Rc<Path>>,
+  /// Number of the line in its file, from 1
+  number: usize,
+  text: String,
+}
+
+/// Source assembled by both passes
+pub(crate) struct Pass<'a> {
+  pub(crate) statements: Vec<Statement<'a>>,
+  /// Words with their locations and the statements they come from
+  pub(crate) words: Vec<(usize, Word, usize)>,
+  pub(crate) start: Option<usize>,
+  /// Statement of END, if the source has one
+  end: Option<usize>,
+  pub(crate) symbols: Symbols,
+}
+
+/// Pseudo-operation of the assembler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Directive {
+  Equ,
+  Orig,
+  Con,
+  Alf,
+  End,
+}
+
+impl Directive {
+  fn from_name(name: &str) -> Option<Self> {
+    match name.to_ascii_uppercase().as_str() {
+      "EQU" => Some(Self::Equ),
+      "ORIG" => Some(Self::Orig),
+      "CON" => Some(Self::Con),
+      "ALF" => Some(Self::Alf),
+      "END" => Some(Self::End),
+      _ => None,
+    }
+  }
+}
+
+/// Line of the source split into its fields
+#[derive(Debug)]
+pub(crate) struct Statement<'a> {
+  /// File the line is in, none for the source given as text
+  pub(crate) file: Option<&'a Path>,
+  /// Number of the line in its file, from 1
+  pub(crate) line: usize,
+  /// Position of the line in the source with the included files in place,
+  /// which orders the definitions of local symbols
+  index: usize,
+  pub(crate) label: Option<&'a str>,
+  pub(crate) operation: &'a str,
+  /// Address field, for ALF the rest of the line
+  pub(crate) operand: &'a str,
+  /// Location the statement is assembled at
+  pub(crate) location: i64,
+  /// Columns the operation and the address field start at, from 1
+  pub(crate) operation_column: usize,
+  pub(crate) operand_column: usize,
+}
+
+impl<'a> Statement<'a> {
+  /// Splits the line into its fields, none if it is empty or a comment
+  fn parse(line: usize, text: &'a str) -> Result<Option<Self>, AssemblyError> {
+    if text.trim().is_empty() || text.starts_with('*') {
+      return Ok(None);
+    }
+
+    let (label, rest) = match text.starts_with(char::is_whitespace) {
+      true => (None, text),
+      false => {
+        let (label, rest) = split_field(text);
+
+        (Some(label), rest)
+      }
+    };
+
+    Self::from_fields(line, text, label, rest).map(Some)
+  }
+
+  /// Splits the rest of the line after the location field into the
+  /// operation and the address field
+  fn from_fields(
+    line: usize,
+    text: &'a str,
+    label: Option<&'a str>,
+    rest: &'a str,
+  ) -> Result<Self, AssemblyError> {
+    // Column of the field the rest of the line starts with
+    let column = |rest: &str| text.len() - rest.trim_start().len() + 1;
+    let operation_column = column(rest);
+    let (operation, rest) = split_field(rest);
+
+    if operation.is_empty() {
+      return Err(AssemblyError::new(
+        line,
+        operation_column,
+        "",
+        AssemblyErrorKind::InvalidSyntax(text.trim().to_string()),
+      ));
+    }
+
+    let is_label = |label: &str| is_symbol(label) && !matches!(local(label), Some((_, 'B' | 'F')));
+
+    if let Some(label) = label.filter(|&label| !is_label(label)) {
+      return Err(AssemblyError::new(
+        line,
+        1,
+        label,
+        AssemblyErrorKind::InvalidSyntax(label.to_string()),
+      ));
+    }
+
+    Ok(Self {
+      file: None,
+      line,
+      index: line,
+      label,
+      operation,
+      operand: match Directive::from_name(operation) {
+        Some(Directive::Alf) => rest.trim(),
+        _ => split_field(rest).0,
+      },
+      location: 0,
+      operation_column,
+      operand_column: column(rest),
+    })
+  }
+
+  pub(crate) fn directive(&self) -> Option<Directive> {
+    Directive::from_name(self.operation)
+  }
+
+  /// Makes the error found at the location field
+  fn label_error(&self, kind: AssemblyErrorKind) -> AssemblyError {
+    AssemblyError::new(self.line, 1, self.label.unwrap_or(""), kind).in_file(self.file)
+  }
+
+  /// Makes the error found at the operation
+  fn operation_error(&self, kind: AssemblyErrorKind) -> AssemblyError {
+    AssemblyError::new(self.line, self.operation_column, self.operation, kind).in_file(self.file)
+  }
+
+  /// Makes the error found at the address field as a whole
+  fn operand_error(&self, kind: AssemblyErrorKind) -> AssemblyError {
+    AssemblyError::new(self.line, self.operand_column, self.operand, kind).in_file(self.file)
+  }
+
+  /// Finds the operation of the statement by its name
+  pub(crate) fn operation(&self) -> Result<Operation, AssemblyError> {
+    Operation::from_mnemonic(&self.operation.to_ascii_uppercase()).ok_or_else(|| {
+      self.operation_error(AssemblyErrorKind::UnknownOperation(
+        self.operation.to_string(),
+      ))
+    })
+  }
+}
+
+/// Tells whether the operation is INCLUDE or its other name INSERT
+fn is_include(operation: &str) -> bool {
+  ["INCLUDE", "INSERT"]
+    .iter()
+    .any(|name| operation.eq_ignore_ascii_case(name))
+}
+
+/// Splits the first field off the text, skipping the blanks before it
+fn split_field(text: &str) -> (&str, &str) {
+  let text = text.trim_start();
+
+  text.split_at(text.find(char::is_whitespace).unwrap_or(text.len()))
+}
+
+/// Splits the text between the equal signs off an address field that starts
+/// with a literal constant, `=W=`
+fn split_literal(text: &str) -> Option<&str> {
+  text
+    .strip_prefix('=')?
+    .split_once('=')
+    .map(|(literal, _)| literal)
+}
+
+/// Checks if the text is the name of a symbol: up to ten letters and digits
+/// with at least one letter
+pub(crate) fn is_symbol(text: &str) -> bool {
+  text.len() <= SYMBOL_LENGTH
+    && text.chars().all(|char| char.is_ascii_alphanumeric())
+    && text.chars().any(|char| char.is_ascii_alphabetic())
+}
+
+/// Splits a local symbol, `dH`, `dB` or `dF` with a digit `d`, into its
+/// digit and letter
+pub(crate) fn local(text: &str) -> Option<(usize, char)> {
+  let mut chars = text.chars();
+  let digit = chars.next()?.to_digit(10)?;
+  let letter = chars.next().filter(|letter| "HBF".contains(*letter))?;
+
+  match chars.next() {
+    Some(_) => None,
+    None => Some((digit as usize, letter)),
+  }
+}
+
+/// Values of the symbols defined by the source
+#[derive(Debug, Default)]
+pub(crate) struct Symbols {
+  pub(crate) values: HashMap<String, i64>,
+  /// Locations of the literal constants, by the text between their equal
+  /// signs
+  literals: HashMap<String, i64>,
+  /// Positions of the lines that define each local symbol `dH` and their
+  /// values, by the digit
+  locals: [Vec<(usize, i64)>; 10],
+  /// Values of the symbols of other modules, the ones not given are 0, none
+  /// unless the source is a relocatable module
+  externals: Option<HashMap<String, i64>>,
+  /// Symbols used without a definition so far, which are external
+  used: RefCell<BTreeSet<String>>,
+}
+
+impl Symbols {
+  /// Creates the symbols of a relocatable module, those of other modules
+  /// taking the values given
+  fn external(values: HashMap<String, i64>) -> Self {
+    Self {
+      externals: Some(values),
+      ..Self::default()
+    }
+  }
+
+  /// Defines the symbol of the location field of the line, a local symbol
+  /// may be defined any number of times
+  ///
+  /// A symbol a relocatable module used as external before cannot be
+  /// defined after all.
+  fn define(&mut self, name: &str, value: i64, index: usize) -> Result<(), AssemblyErrorKind> {
+    if let Some((digit, 'H')) = local(name) {
+      self.locals[digit].push((index, value));
+      return Ok(());
+    }
+
+    if self.used.borrow().contains(name) {
+      return Err(AssemblyErrorKind::UndefinedSymbol(name.to_string()));
+    }
+
+    if self.values.insert(name.to_string(), value).is_some() {
+      return Err(AssemblyErrorKind::DuplicateSymbol(name.to_string()));
+    }
+
+    Ok(())
+  }
+
+  fn get(&self, name: &str) -> Result<i64, AssemblyErrorKind> {
+    if let Some(&value) = self.values.get(name) {
+      return Ok(value);
+    }
+
+    match &self.externals {
+      Some(externals) => {
+        self.used.borrow_mut().insert(name.to_string());
+        Ok(externals.get(name).copied().unwrap_or(0))
+      }
+      None => Err(AssemblyErrorKind::UndefinedSymbol(name.to_string())),
+    }
+  }
+
+  /// Names of the external symbols the source used
+  fn externals(&self) -> BTreeSet<String> {
+    self.used.borrow().clone()
+  }
+
+  /// Finds the value of `dB`, the last `dH` above the line, or of `dF`, the
+  /// first `dH` below it
+  fn local(&self, digit: usize, letter: char, index: usize) -> Result<i64, AssemblyErrorKind> {
+    let mut definitions = self.locals[digit].iter();
+    let definition = match letter {
+      'B' => definitions.rev().find(|&&(defined, _)| defined < index),
+      _ => definitions.find(|&&(defined, _)| defined > index),
+    };
+
+    definition
+      .map(|&(_, value)| value)
+      .ok_or_else(|| AssemblyErrorKind::UndefinedSymbol(format!("{}{}", digit, letter)))
+  }
+
+  /// Makes the table of the symbols other than the local ones
+  fn table(&self) -> SymbolTable {
+    SymbolTable {
+      symbols: self.values.clone().into_iter().collect(),
+      literals: self
+        .literals
+        .iter()
+        .map(|(literal, &location)| (literal.clone(), location as usize))
+        .collect(),
+    }
+  }
+
+  fn literal(&self, text: &str) -> Result<i64, AssemblyErrorKind> {
+    self
+      .literals
+      .get(text)
+      .copied()
+      .ok_or_else(|| AssemblyErrorKind::UndefinedSymbol(format!("={}=", text)))
+  }
+}
+
+/// Reader of the expressions of an address field
+///
+/// An expression is a number, a symbol or `*` for the location of the line,
+/// possibly signed, joined by the binary operations `+`, `-`, `*`, `/`, `//`
+/// and `:` which are taken strictly from left to right.
+struct Parser<'a> {
+  text: &'a str,
+  position: usize,
+  symbols: &'a Symbols,
+  location: i64,
+  /// File and line of the address field
+  file: Option<&'a Path>,
+  line: usize,
+  /// Position of the line of the address field, which local symbols are
+  /// found from
+  index: usize,
+  /// Column the address field starts at
+  column: usize,
+  byte_size: ByteSize,
+}
+
+impl<'a> Parser<'a> {
+  /// Creates the reader of the address field of the statement
+  fn new(statement: &Statement<'a>, symbols: &'a Symbols, byte_size: ByteSize) -> Self {
+    Self {
+      text: statement.operand,
+      position: 0,
+      symbols,
+      location: statement.location,
+      file: statement.file,
+      line: statement.line,
+      index: statement.index,
+      column: statement.operand_column,
+      byte_size,
+    }
+  }
+
+  fn rest(&self) -> &'a str {
+    &self.text[self.position..]
+  }
+
+  fn is_done(&self) -> bool {
+    self.position == self.text.len()
+  }
+
+  /// Skips the text if the rest starts with it
+  fn eat(&mut self, text: &str) -> bool {
+    let found = self.rest().starts_with(text);
+
+    if found {
+      self.position += text.len();
+    }
+
+    found
+  }
+
+  /// Makes the error found at the text from the start to the position
+  fn error(&self, start: usize, kind: AssemblyErrorKind) -> AssemblyError {
+    AssemblyError::new(
+      self.line,
+      self.column + start,
+      &self.text[start..self.position],
+      kind,
+    )
+    .in_file(self.file)
+  }
+
+  /// Makes the error of the rest of the text not reading
+  fn invalid(&self) -> AssemblyError {
+    AssemblyError::new(
+      self.line,
+      self.column + self.position,
+      self.rest(),
+      AssemblyErrorKind::InvalidSyntax(self.rest().to_string()),
+    )
+    .in_file(self.file)
+  }
+
+  /// Reads the address field of an instruction, `A,I(F)` with every part
+  /// optional, returns the field if it is given
+  fn address_field(&mut self) -> Result<(i64, i64, Option<i64>), AssemblyError> {
+    let address = if self.rest().starts_with('=') {
+      self.literal()?
+    } else if self.is_done() || self.rest().starts_with([',', '(']) {
+      0
+    } else {
+      let start = self.position;
+      let address = self.expression()?;
+
+      if address.unsigned_abs() >= self.byte_size.capacity(2) {
+        return Err(self.error(start, AssemblyErrorKind::OutOfRange(address)));
+      }
+
+      address
+    };
+    let index = match self.eat(",") {
+      true => self.byte()?,
+      false => 0,
+    };
+    let field = match self.eat("(") {
+      true => {
+        let field = self.byte()?;
+
+        if !self.eat(")") {
+          return Err(self.invalid());
+        }
+
+        Some(field)
+      }
+      false => None,
+    };
+
+    if !self.is_done() {
+      return Err(self.invalid());
+    }
+
+    Ok((address, index, field))
+  }
+
+  /// Reads an expression that is the value of a byte, the index or the
+  /// field of an instruction
+  fn byte(&mut self) -> Result<i64, AssemblyError> {
+    let start = self.position;
+    let value = self.expression()?;
+
+    if !(0..self.byte_size.get() as i64).contains(&value) {
+      return Err(self.error(start, AssemblyErrorKind::OutOfRange(value)));
+    }
+
+    Ok(value)
+  }
+
+  /// Reads a literal constant as the location of its word
+  fn literal(&mut self) -> Result<i64, AssemblyError> {
+    let start = self.position;
+    let literal = split_literal(self.rest()).ok_or_else(|| self.invalid())?;
+
+    self.position += literal.len() + 2;
+    self
+      .symbols
+      .literal(literal)
+      .map_err(|kind| self.error(start, kind))
+  }
+
+  /// Reads the address field of a pseudo-operation, a single expression
+  fn value(&mut self) -> Result<i64, AssemblyError> {
+    let value = self.expression()?;
+
+    if !self.is_done() {
+      return Err(self.invalid());
+    }
+
+    Ok(value)
+  }
+
+  /// Reads a W-value as its word, an expression that starts with a minus
+  /// and comes to zero is -0
+  fn w_value(&mut self) -> Result<Word, AssemblyError> {
+    let mut word = Word::default();
+
+    loop {
+      let start = self.position;
+      let negative = self.rest().starts_with('-');
+      let value = self.expression()?;
+
+      if value.unsigned_abs() >= self.byte_size.capacity(5) {
+        return Err(self.error(start, AssemblyErrorKind::OutOfRange(value)));
+      }
+
+      let field = match self.eat("(") {
+        true => {
+          let start = self.position;
+          let field = self.expression()?;
+          let spec = u32::try_from(field)
+            .ok()
+            .and_then(|field| FieldSpec::try_from(field).ok())
+            .ok_or_else(|| self.error(start, AssemblyErrorKind::OutOfRange(field)))?;
+
+          if !self.eat(")") {
+            return Err(self.invalid());
+          }
+
+          spec
+        }
+        false => FieldSpec::WHOLE,
+      };
+
+      word.store(
+        &self
+          .byte_size
+          .word(value.unsigned_abs(), value > 0 || (value == 0 && !negative)),
+        field,
+      );
+
+      if !self.eat(",") {
+        break;
+      }
+    }
+
+    if !self.is_done() {
+      return Err(self.invalid());
+    }
+
+    Ok(word)
+  }
+
+  /// Reads an expression
+  fn expression(&mut self) -> Result<i64, AssemblyError> {
+    let mut value = if self.eat("-") {
+      -self.atom()?
+    } else {
+      self.eat("+");
+      self.atom()?
+    };
+
+    loop {
+      let start = self.position;
+      // Worked out wider than the value, so that a result too large for it
+      // is an error and not an overflow
+      let left = value as i128;
+      let result = if self.eat("+") {
+        left + self.atom()? as i128
+      } else if self.eat("-") {
+        left - self.atom()? as i128
+      } else if self.eat("*") {
+        left * self.atom()? as i128
+      } else if self.eat("//") {
+        let divisor = self.nonzero()? as i128;
+
+        // The dividend takes rA and rX as DIV does
+        left * self.byte_size.capacity(5) as i128 / divisor
+      } else if self.eat("/") {
+        left / self.nonzero()? as i128
+      } else if self.eat(":") {
+        8 * left + self.atom()? as i128
+      } else {
+        return Ok(value);
+      };
+
+      value = i64::try_from(result).map_err(|_| {
+        let clamped = result.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+
+        self.error(start, AssemblyErrorKind::OutOfRange(clamped))
+      })?;
+    }
+  }
+
+  /// Reads an atom that is a divisor
+  fn nonzero(&mut self) -> Result<i64, AssemblyError> {
+    let start = self.position;
+
+    match self.atom()? {
+      0 => {
+        self.position = start;
+        Err(self.invalid())
+      }
+      divisor => Ok(divisor),
+    }
+  }
+
+  /// Reads a number, a symbol or `*`
+  fn atom(&mut self) -> Result<i64, AssemblyError> {
+    if self.eat("*") {
+      return Ok(self.location);
+    }
+
+    let length = self
+      .rest()
+      .find(|char: char| !char.is_ascii_alphanumeric())
+      .unwrap_or(self.rest().len());
+    let atom = &self.rest()[..length];
+
+    if atom.is_empty() || length > SYMBOL_LENGTH {
+      return Err(self.invalid());
+    }
+
+    let start = self.position;
+
+    self.position += length;
+
+    let value = match (atom.parse(), local(atom)) {
+      (Ok(number), _) => Ok(number),
+      (_, Some((digit, letter @ ('B' | 'F')))) => self.symbols.local(digit, letter, self.index),
+      _ => self.symbols.get(atom),
+    };
+    let value = value.map_err(|kind| self.error(start, kind))?;
+
+    Ok(value)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{computer::HaltReason, error::MixError, instruction::Target, register::Register};
+
+  fn instruction(word: &Word) -> Instruction {
+    Instruction::decode(word, ByteSize::BINARY).unwrap()
+  }
+
+  /// Line and kind of the error of the source
+  fn error_of(source: &str) -> (usize, AssemblyErrorKind) {
+    let error = assemble(source).unwrap_err();
+
+    (error.line, error.kind)
+  }
+
+  #[test]
+  fn test_forward_reference() {
+    let source = "\
+* Jumps over the first HLT to a label defined below
+START JMP LATER
+      HLT
+LATER ENTA 5
+      HLT
+";
+    let assembly = assemble(source).unwrap();
+
+    assert_eq!(assembly.words.len(), 4);
+    assert_eq!(
+      instruction(&assembly.words[0].1),
+      Instruction::new(true, 2, 0, 0, Operation::Jmp)
+    );
+
+    let mut computer = Computer::new();
+
+    assembly.load(&mut computer);
+
+    assert_eq!(computer.run(0), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(5, Some(true)));
+  }
+
+  #[rstest]
+  #[case(
+    " LDA 2000,1(0:3)",
+    Instruction::new(true, 2000, 1, 3, Operation::Ld(Target::A))
+  )]
+  #[case(" LDA -1", Instruction::new(false, 1, 0, 5, Operation::Ld(Target::A)))]
+  #[case(" STJ 100", Instruction::new(true, 100, 0, 2, Operation::Stj))]
+  #[case(" MOVE 100,2", Instruction::new(true, 100, 2, 1, Operation::Move))]
+  #[case(" OUT 100(18)", Instruction::new(true, 100, 0, 18, Operation::Out))]
+  #[case(" ENT1 *", Instruction::new(true, 0, 0, 2, Operation::Ent(Target::I1)))]
+  #[case(" jmp *+3", Instruction::new(true, 3, 0, 0, Operation::Jmp))]
+  #[case(" HLT", Instruction::new(true, 0, 0, 2, Operation::Hlt))]
+  #[case(
+    " INC1 -1+2*3",
+    Instruction::new(true, 3, 0, 0, Operation::Inc(Target::I1))
+  )]
+  #[case(
+    " LDA 1(1:3+2)",
+    Instruction::new(true, 1, 0, 13, Operation::Ld(Target::A))
+  )]
+  #[case(
+    " LDA 1(2:2)  comment",
+    Instruction::new(true, 1, 0, 18, Operation::Ld(Target::A))
+  )]
+  #[case(" LDX 7/2", Instruction::new(true, 3, 0, 5, Operation::Ld(Target::X)))]
+  #[case(
+    " LDX 1//524288",
+    Instruction::new(true, 2048, 0, 5, Operation::Ld(Target::X))
+  )]
+  fn test_instruction(#[case] source: &str, #[case] expected: Instruction) {
+    let assembly = assemble(source).unwrap();
+
+    assert_eq!(assembly.words, [(0, expected.encode(ByteSize::BINARY))]);
+  }
+
+  #[rstest]
+  #[case(
+    "LDA 2000,1(0:3)",
+    Instruction::new(true, 2000, 1, 3, Operation::Ld(Target::A))
+  )]
+  #[case("  JMP -1+3  ", Instruction::new(true, 2, 0, 0, Operation::Jmp))]
+  #[case("MOVE 0,2(10)", Instruction::new(true, 0, 2, 10, Operation::Move))]
+  #[case("HLT", Instruction::new(true, 0, 0, 2, Operation::Hlt))]
+  #[case("LDA -0,1", Instruction::new(false, 0, 1, 5, Operation::Ld(Target::A)))]
+  fn test_from_str(#[case] text: &str, #[case] expected: Instruction) {
+    assert_eq!(text.parse(), Ok(expected));
+    assert_eq!(expected.to_string().parse(), Ok(expected));
+  }
+
+  #[rstest]
+  #[case("", AssemblyErrorKind::InvalidSyntax("".to_string()))]
+  #[case("CON 1", AssemblyErrorKind::UnknownOperation("CON".to_string()))]
+  #[case("JMP LOOP", AssemblyErrorKind::UndefinedSymbol("LOOP".to_string()))]
+  #[case("LDA 1(64)", AssemblyErrorKind::OutOfRange(64))]
+  fn test_from_str_error(#[case] text: &str, #[case] kind: AssemblyErrorKind) {
+    assert_eq!(text.parse::<Instruction>().unwrap_err().kind, kind);
+  }
+
+  #[test]
+  fn test_symbols_of_locations() {
+    let source = "
+      NOP
+TABLE NOP
+      NOP
+      LDA TABLE+1,1
+      JMP TABLE-1
+X1    NOP   X1 is a symbol, not a register
+      LDX X1
+";
+    let assembly = assemble(source).unwrap();
+    let addresses: Vec<u32> = assembly
+      .words
+      .iter()
+      .map(|(_, word)| instruction(word).address)
+      .collect();
+
+    assert_eq!(addresses, [0, 0, 0, 2, 0, 5, 5]);
+  }
+
+  #[test]
+  fn test_decimal() {
+    let assembly = Assembler::decimal().assemble(" LDA 9999").unwrap();
+
+    assert_eq!(
+      Instruction::decode(&assembly.words[0].1, ByteSize::DECIMAL),
+      Ok(Instruction::new(true, 9999, 0, 5, Operation::Ld(Target::A)))
+    );
+    assert_eq!(
+      assemble(" LDA 9999").unwrap_err().kind,
+      AssemblyErrorKind::OutOfRange(9999)
+    );
+  }
+
+  #[rstest]
+  #[case(" JMP NOWHERE", 1, AssemblyErrorKind::UndefinedSymbol("NOWHERE".to_string()))]
+  #[case("A NOP\nA NOP", 2, AssemblyErrorKind::DuplicateSymbol("A".to_string()))]
+  #[case(" NOP\n LDAX 1", 2, AssemblyErrorKind::UnknownOperation("LDAX".to_string()))]
+  #[case(" LDA 1,64", 1, AssemblyErrorKind::OutOfRange(64))]
+  #[case(" LDA 1(64)", 1, AssemblyErrorKind::OutOfRange(64))]
+  #[case(" LDA 4096", 1, AssemblyErrorKind::OutOfRange(4096))]
+  #[case(" LDA 1(1:3", 1, AssemblyErrorKind::InvalidSyntax("".to_string()))]
+  #[case(" LDA 1+", 1, AssemblyErrorKind::InvalidSyntax("".to_string()))]
+  #[case(" LDA 1/0", 1, AssemblyErrorKind::InvalidSyntax("0".to_string()))]
+  #[case(" LDA 12345678901", 1, AssemblyErrorKind::InvalidSyntax("12345678901".to_string()))]
+  #[case("1 NOP", 1, AssemblyErrorKind::InvalidSyntax("1".to_string()))]
+  fn test_error(#[case] source: &str, #[case] line: usize, #[case] kind: AssemblyErrorKind) {
+    assert_eq!(error_of(source), (line, kind));
+  }
+
+  #[rstest]
+  #[case(" JMP NOWHERE", 6, "NOWHERE")]
+  #[case("A NOP\nA NOP", 1, "A")]
+  #[case("  LDAX 1", 3, "LDAX")]
+  #[case(" LDA 1,64", 8, "64")]
+  #[case(" LDA 1(2*32)", 8, "2*32")]
+  #[case(" LDA 1000+3096", 6, "1000+3096")]
+  #[case(" LDA 1(1:3", 11, "")]
+  #[case(" LDA 1/0 comment", 8, "0")]
+  #[case(" LDA =X=", 7, "X")]
+  #[case(" CON 1,2(6)", 10, "6")]
+  #[case(" CON 9999999999*9999999999*9999999999", 16, "*9999999999")]
+  #[case(" ALF \"OPEN", 6, "\"OPEN")]
+  #[case("1H NOP 1B", 8, "1B")]
+  #[case("2B NOP", 1, "2B")]
+  #[case("LABEL", 6, "")]
+  fn test_error_location(#[case] source: &str, #[case] column: usize, #[case] token: &str) {
+    let error = assemble(source).unwrap_err();
+
+    assert_eq!((error.column, error.token.as_str()), (column, token));
+  }
+
+  #[test]
+  fn test_diagnostic() {
+    let source = "START NOP\n      JMP  NOWHERE\n";
+    let error = assemble(source).unwrap_err();
+
+    assert_eq!(
+      error.to_string(),
+      "Line 2, column 12: Symbol NOWHERE is not defined"
+    );
+    assert_eq!(
+      error.display_with_source(source).to_string(),
+      "\
+error: Symbol NOWHERE is not defined
+  --> line 2, column 12
+  |
+2 |       JMP  NOWHERE
+  |            ^^^^^^^ define it in the location field of a line"
+    );
+  }
+
+  #[test]
+  fn test_out_of_memory() {
+    let source = " NOP\n".repeat(4001);
+
+    assert_eq!(
+      assemble(&source),
+      Err(AssemblyError::new(
+        4001,
+        2,
+        "NOP",
+        AssemblyErrorKind::InvalidLocation(4000)
+      ))
+    );
+  }
+
+  #[test]
+  fn test_pseudo_operations() {
+    let source = "
+TERM   EQU  19
+BUF    EQU  TERM*100+100
+FIRST  ORIG 3000
+START  OUT  MSG(TERM)
+       HLT
+MSG    ALF  HELLO
+       ALF  \" WORL\"
+       ALF  D
+       CON  -100
+       CON  BUF
+       END  START
+       this line is not read
+";
+    let assembly = assemble(source).unwrap();
+
+    assert_eq!(assembly.start, Some(3000));
+    assert_eq!(
+      assembly.words[0],
+      (
+        3000,
+        Instruction::new(true, 3002, 0, 19, Operation::Out).encode(ByteSize::BINARY)
+      )
+    );
+    assert_eq!(
+      charcode::words_to_string(&[
+        assembly.words[2].1,
+        assembly.words[3].1,
+        assembly.words[4].1
+      ]),
+      Some("HELLO WORLD    ".to_string())
+    );
+    assert_eq!(assembly.words[5], (3005, Word::new(100, Some(false))));
+    assert_eq!(assembly.words[6], (3006, Word::new(2000, Some(true))));
+
+    let mut computer = Computer::new();
+
+    assembly.load(&mut computer);
+
+    assert_eq!(computer.run(3000), Ok(HaltReason::Halted));
+  }
+
+  #[test]
+  fn test_to_program() {
+    let assembly = assemble(
+      "
+       ORIG 100
+X      CON  5
+       ORIG 3000
+START  LDA  X
+       HLT
+       END  START
+",
+    )
+    .unwrap();
+    let program = assembly.to_program();
+
+    assert!(program.instructions.is_empty());
+    assert_eq!(
+      program.segments,
+      vec![
+        (100, vec![Word::new(5, Some(true))]),
+        (3000, vec![assembly.words[1].1, assembly.words[2].1]),
+      ]
+    );
+    assert_eq!(program.entry, 3000);
+
+    let mut computer = Computer::new();
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(5, Some(true)));
+  }
+
+  #[test]
+  fn test_to_program_fault_line() {
+    let program = assemble(
+      "\
+START ENT1 100
+      LDA  3950,1
+      HLT
+      END  START
+",
+    )
+    .unwrap()
+    .to_program();
+    let mut computer = Computer::new();
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::InvalidAddress(4050))
+    );
+    assert_eq!(computer.location, 1);
+    assert_eq!(program.source_line(computer.location).unwrap().line, 2);
+    assert_eq!(program.source_line(-1), None);
+  }
+
+  #[rstest]
+  #[case(" CON 100", Word::new(100, Some(true)))]
+  #[case(" CON -1(0:5)", Word::new(1, Some(false)))]
+  #[case(" CON 1(1:1),2(2:2)", Word::from_bytes([1, 2, 0, 0, 0], true))]
+  #[case(" CON 100(0:2),3(4:5)", Word::from_bytes([1, 36, 0, 0, 3], true))]
+  #[case(" CON -1(0:0),65(1:2)", Word::from_bytes([1, 1, 0, 0, 0], false))]
+  #[case(" CON 1,2", Word::new(2, Some(true)))]
+  #[case(" CON -0", Word::new(0, Some(false)))]
+  #[case(" CON -1+1", Word::new(0, Some(false)))]
+  #[case(" CON 1-1", Word::new(0, Some(true)))]
+  #[case(" LDA =1(1:1)=\n", Word::from_bytes([1, 0, 0, 0, 0], true))]
+  fn test_w_value(#[case] source: &str, #[case] expected: Word) {
+    let assembly = assemble(source).unwrap();
+
+    assert_eq!(
+      assembly.words.last(),
+      Some(&(assembly.words.len() - 1, expected))
+    );
+  }
+
+  #[test]
+  fn test_end_w_value() {
+    let assembly = assemble(" NOP\n END 1(4:4),3(5:5)").unwrap();
+
+    assert_eq!(assembly.start, Some(67));
+  }
+
+  #[test]
+  fn test_orig_label() {
+    let assembly = assemble(" ORIG 10\nHERE ORIG *+5\n JMP HERE").unwrap();
+
+    assert_eq!(
+      assembly.words,
+      [(
+        15,
+        Instruction::new(true, 10, 0, 0, Operation::Jmp).encode(ByteSize::BINARY)
+      )]
+    );
+    assert_eq!(assembly.start, None);
+  }
+
+  #[test]
+  fn test_literals() {
+    let source = "
+       ORIG 100
+START  LDA  =5=
+       ADD  =5=
+       ADD  =-2=(1:5)
+       ENTX LAST
+       HLT
+LAST   END  START
+";
+    let assembly = assemble(source).unwrap();
+
+    assert_eq!(
+      &assembly.words[5..],
+      [
+        (105, Word::new(5, Some(true))),
+        (106, Word::new(2, Some(false)))
+      ]
+    );
+
+    let mut computer = Computer::new();
+
+    assembly.load(&mut computer);
+
+    assert_eq!(computer.run(100), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(12, Some(true)));
+    assert_eq!(computer.x, Word::new(107, Some(true)));
+  }
+
+  #[test]
+  fn test_symbol_table() {
+    let source = "
+TERM   EQU  19
+       ORIG 100
+START  LDA  =5=
+2H     OUT  =5=(TERM)
+LAST   END  START
+";
+    let symbols = assemble(source).unwrap().symbols;
+
+    assert_eq!(
+      symbols.symbols,
+      [("LAST", 103), ("START", 100), ("TERM", 19)]
+        .map(|(name, value)| (name.to_string(), value))
+        .into()
+    );
+    assert_eq!(symbols.literals, [("5".to_string(), 102)].into());
+    assert_eq!(symbols.name_of(102).as_deref(), Some("=5="));
+  }
+
+  #[test]
+  fn test_source_map() {
+    let source = "
+* Loads from past the end of memory
+       ORIG 100
+START  ENT1 2000
+       LDA  2000,1
+       HLT
+       END  START
+";
+    let assembly = Assembler::new()
+      .assemble_named("fault.mixal", source)
+      .unwrap();
+    let mut computer = Computer::new();
+
+    assembly.load(&mut computer);
+
+    assert_eq!(computer.run(100), Err(MixError::InvalidAddress(4000)));
+    assert_eq!(
+      assembly
+        .source_map
+        .get(computer.pc as usize - 1)
+        .unwrap()
+        .to_string(),
+      "fault.mixal:5"
+    );
+    assert_eq!(
+      assemble(source)
+        .unwrap()
+        .source_map
+        .get(102)
+        .unwrap()
+        .to_string(),
+      "line 6"
+    );
+  }
+
+  #[test]
+  fn test_literal_without_end() {
+    let assembly = assemble(" LDA =1=").unwrap();
+
+    assert_eq!(
+      assembly.words,
+      [
+        (
+          0,
+          Instruction::new(true, 1, 0, 5, Operation::Ld(Target::A)).encode(ByteSize::BINARY)
+        ),
+        (1, Word::new(1, Some(true)))
+      ]
+    );
+  }
+
+  #[test]
+  fn test_local_symbols() {
+    let source = "
+      ENT1 3
+2H    INCA 1
+      DEC1 1
+      J1P  2B
+      JMP  2F
+      HLT
+2H    ENTX 2B
+      HLT
+";
+    let mut computer = Computer::new();
+
+    assemble(source).unwrap().load(&mut computer);
+
+    assert_eq!(computer.run(0), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(3, Some(true)));
+    assert_eq!(computer.x, Word::new(1, Some(true)));
+  }
+
+  #[test]
+  fn test_local_symbol_of_own_line() {
+    let source = "
+3H    EQU  10
+3H    EQU  3B+1
+      ORIG 3B
+3H    JMP  3B
+      JMP  3H
+3H    NOP
+";
+    let assembly = assemble(source);
+
+    assert_eq!(
+      assembly,
+      Err(AssemblyError::new(
+        6,
+        12,
+        "3H",
+        AssemblyErrorKind::UndefinedSymbol("3H".to_string())
+      ))
+    );
+
+    let assembly = assemble(&source.replace("JMP  3H", "JMP  3F")).unwrap();
+    let addresses: Vec<_> = assembly
+      .words
+      .iter()
+      .map(|&(location, word)| (location, instruction(&word).address))
+      .collect();
+
+    assert_eq!(addresses, [(11, 11), (12, 13), (13, 0)]);
+  }
+
+  #[rstest]
+  #[case(" JMP 1B", 1, AssemblyErrorKind::UndefinedSymbol("1B".to_string()))]
+  #[case("1H NOP\n JMP 1F", 2, AssemblyErrorKind::UndefinedSymbol("1F".to_string()))]
+  #[case(" ORIG 1F\n1H NOP", 1, AssemblyErrorKind::UndefinedSymbol("1F".to_string()))]
+  #[case("1B NOP", 1, AssemblyErrorKind::InvalidSyntax("1B".to_string()))]
+  fn test_local_symbol_error(
+    #[case] source: &str,
+    #[case] line: usize,
+    #[case] kind: AssemblyErrorKind,
+  ) {
+    assert_eq!(error_of(source), (line, kind));
+  }
+
+  #[rstest]
+  #[case(" LDA =1", 1, AssemblyErrorKind::InvalidSyntax("=1".to_string()))]
+  #[case(" NOP\n LDA =X=", 2, AssemblyErrorKind::UndefinedSymbol("X".to_string()))]
+  #[case(" ORIG 3999\n LDA =1=", 2, AssemblyErrorKind::InvalidLocation(4000))]
+  #[case(
+    "X EQU Y\nY EQU 1",
+    1,
+    AssemblyErrorKind::UndefinedSymbol("Y".to_string())
+  )]
+  #[case(" ORIG LATER\nLATER NOP", 1, AssemblyErrorKind::UndefinedSymbol("LATER".to_string()))]
+  #[case(" ORIG 3999\n NOP\n NOP", 3, AssemblyErrorKind::InvalidLocation(4000))]
+  #[case(" CON 1073741824", 1, AssemblyErrorKind::OutOfRange(1073741824))]
+  #[case(" CON 1(6)", 1, AssemblyErrorKind::OutOfRange(6))]
+  #[case(" CON 1(-1)", 1, AssemblyErrorKind::OutOfRange(-1))]
+  #[case(
+    " CON 9999999999*9999999999*9999999999",
+    1,
+    AssemblyErrorKind::OutOfRange(i64::MAX)
+  )]
+  #[case(
+    " CON -9999999999*9999999999*9999999999",
+    1,
+    AssemblyErrorKind::OutOfRange(i64::MIN)
+  )]
+  #[case(
+    " CON 9999999999//3",
+    1,
+    AssemblyErrorKind::OutOfRange(3579139412975419392)
+  )]
+  #[case(
+    " CON 9999999999*200000000//3",
+    1,
+    AssemblyErrorKind::OutOfRange(i64::MAX)
+  )]
+  #[case(
+    " CON 9999999999*200000000:1",
+    1,
+    AssemblyErrorKind::OutOfRange(i64::MAX)
+  )]
+  #[case(" CON 1(1:1", 1, AssemblyErrorKind::InvalidSyntax("".to_string()))]
+  #[case(" CON 1)", 1, AssemblyErrorKind::InvalidSyntax(")".to_string()))]
+  #[case(" ALF \"TOO LONG\"", 1, AssemblyErrorKind::InvalidSyntax("\"TOO LONG\"".to_string()))]
+  #[case(" ALF \"OPEN", 1, AssemblyErrorKind::InvalidSyntax("\"OPEN".to_string()))]
+  #[case(" ALF hello", 1, AssemblyErrorKind::InvalidSyntax("hello".to_string()))]
+  #[case(" END 4000", 1, AssemblyErrorKind::InvalidLocation(4000))]
+  fn test_pseudo_operation_error(
+    #[case] source: &str,
+    #[case] line: usize,
+    #[case] kind: AssemblyErrorKind,
+  ) {
+    assert_eq!(error_of(source), (line, kind));
+  }
+
+  #[test]
+  fn test_index_register() {
+    let mut computer = Computer::new();
+    let assembly = assemble(
+      "
+      ENT1 3
+LOOP  DEC1 1
+      J1P  LOOP
+      HLT
+",
+    )
+    .unwrap();
+
+    assembly.load(&mut computer);
+
+    assert_eq!(computer.run(0), Ok(HaltReason::Halted));
+    assert_eq!(computer.ri[0], Register::new(0, Some(true)));
+  }
+
+  #[test]
+  fn test_include() {
+    let directory = tempfile::tempdir().unwrap();
+    let library = directory.path().join("library");
+    let main = directory.path().join("main.mixal");
+
+    std::fs::create_dir(&library).unwrap();
+    std::fs::write(library.join("double.mixal"), "       ADD  TEMP\n").unwrap();
+    std::fs::write(
+      library.join("subroutines.mixal"),
+      "* Doubles rA\nDOUBLE STJ  1F\n       STA  TEMP\n       INCLUDE double.mixal\n1H     JMP  *\n",
+    )
+    .unwrap();
+    std::fs::write(directory.path().join("values.mixal"), "TEMP   CON  0\n").unwrap();
+
+    let source = "
+3H     ENTA 21
+       JMP  DOUBLE
+       JMP  2F
+       INCLUDE subroutines.mixal
+       insert values.mixal
+2H     HLT
+       END  3B
+";
+    let assembler = Assembler {
+      include_paths: vec![library.clone()],
+      ..Assembler::new()
+    };
+    let assembly = assembler.assemble_named(&main, source).unwrap();
+    let mut computer = Computer::new();
+
+    assembly.load(&mut computer);
+
+    assert_eq!(assembly.start, Some(0));
+    assert_eq!(computer.run(0), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(42, Some(true)));
+    assert_eq!(
+      assembly.source_map.get(5).unwrap().file.as_deref(),
+      Some(library.join("double.mixal").as_path())
+    );
+    assert_eq!(assembly.source_map.get(6).unwrap().line, 5);
+    assert_eq!(assembly.source_map.get(8).unwrap().file, Some(main));
+  }
+
+  #[test]
+  fn test_include_cycle() {
+    let directory = tempfile::tempdir().unwrap();
+    let first = directory.path().join("first.mixal");
+    let second = directory.path().join("second.mixal");
+
+    std::fs::write(&first, " NOP\n INCLUDE second.mixal\n").unwrap();
+    std::fs::write(&second, " INCLUDE first.mixal\n").unwrap();
+
+    let error = Assembler::new()
+      .assemble_named(&first, " NOP\n INCLUDE second.mixal\n")
+      .unwrap_err();
+
+    assert_eq!(error.file, Some(second));
+    assert_eq!(
+      (error.line, error.kind),
+      (
+        1,
+        AssemblyErrorKind::CyclicInclude("first.mixal".to_string())
+      )
+    );
+  }
+
+  #[test]
+  fn test_include_error_location() {
+    let directory = tempfile::tempdir().unwrap();
+    let library = directory.path().join("library.mixal");
+
+    std::fs::write(&library, " NOP\n JMP  NOWHERE\n").unwrap();
+
+    let error = assemble(&format!(" INCLUDE {}", library.display())).unwrap_err();
+
+    assert_eq!(error.file.as_deref(), Some(library.as_path()));
+    assert_eq!(
+      error.to_string(),
+      format!(
+        "{}, Line 2, column 7: Symbol NOWHERE is not defined",
+        library.display()
+      )
+    );
+  }
+
+  #[rstest]
+  #[case(" INCLUDE missing.mixal", 1, AssemblyErrorKind::CannotInclude("missing.mixal".to_string()))]
+  #[case(" NOP\n INCLUDE", 2, AssemblyErrorKind::CannotInclude("".to_string()))]
+  #[case("LIBRARY INCLUDE missing.mixal", 1, AssemblyErrorKind::InvalidSyntax("LIBRARY".to_string()))]
+  fn test_include_error(
+    #[case] source: &str,
+    #[case] line: usize,
+    #[case] kind: AssemblyErrorKind,
+  ) {
+    assert_eq!(error_of(source), (line, kind));
+  }
+}
@@ -0,0 +1,139 @@
+use crate::{word::Word, Signed};
+
+/// Number of values a byte of the machine holds
+///
+/// MIX bytes hold anywhere from 64 to 100 values, and programs are expected
+/// to work whatever the size is. The bytes of a word are read as the digits
+/// of a number in the radix of the byte size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteSize(u32);
+
+impl ByteSize {
+  /// Bytes of six bits, as on a binary machine
+  pub const BINARY: Self = Self(64);
+
+  /// Bytes of two decimal digits, as on a decimal machine
+  pub const DECIMAL: Self = Self(100);
+
+  /// Creates the byte size, which must be 64 through 100
+  pub fn new(size: u32) -> Option<Self> {
+    (64..=100).contains(&size).then_some(Self(size))
+  }
+
+  /// Number of values a byte holds
+  pub fn get(self) -> u32 {
+    self.0
+  }
+
+  /// Number of values the bytes hold taken together, which is one more than
+  /// the largest magnitude they keep
+  pub fn capacity(self, bytes: u32) -> u64 {
+    (self.0 as u64).pow(bytes)
+  }
+
+  /// Reads bytes 1-5 of the word as a magnitude
+  pub fn magnitude(self, word: &Word) -> u64 {
+    word
+      .bytes()
+      .fold(0, |value, byte| value * self.0 as u64 + byte as u64)
+  }
+
+  /// Reads the word as a signed number
+  pub fn value(self, word: &Word) -> i64 {
+    let value = self.magnitude(word) as i64;
+
+    if word.read_sign() {
+      value
+    } else {
+      -value
+    }
+  }
+
+  /// Makes a word of the magnitude, only the remainder modulo the capacity
+  /// of the word is kept
+  pub fn word(self, magnitude: u64, sign: bool) -> Word {
+    Word::from_bytes(self.bytes(magnitude), sign)
+  }
+
+  /// Splits the magnitude into bytes, the highest bytes that do not fit are
+  /// dropped
+  pub fn bytes<const N: usize>(self, magnitude: u64) -> [u8; N] {
+    let mut bytes = [0; N];
+    let mut rest = magnitude;
+
+    for byte in bytes.iter_mut().rev() {
+      *byte = (rest % self.0 as u64) as u8;
+      rest /= self.0 as u64;
+    }
+
+    bytes
+  }
+}
+
+/// A binary machine is the one most programs are written for
+impl Default for ByteSize {
+  fn default() -> Self {
+    Self::BINARY
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::Data;
+
+  #[rstest]
+  #[case(63, None)]
+  #[case(64, Some(ByteSize::BINARY))]
+  #[case(80, Some(ByteSize(80)))]
+  #[case(100, Some(ByteSize::DECIMAL))]
+  #[case(101, None)]
+  fn test_new(#[case] size: u32, #[case] expected: Option<ByteSize>) {
+    assert_eq!(ByteSize::new(size), expected);
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, 2, 4096)]
+  #[case(ByteSize::BINARY, 5, 1 << 30)]
+  #[case(ByteSize::DECIMAL, 2, 10000)]
+  #[case(ByteSize::DECIMAL, 5, 10_000_000_000)]
+  fn test_capacity(#[case] size: ByteSize, #[case] bytes: u32, #[case] expected: u64) {
+    assert_eq!(size.capacity(bytes), expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(ByteSize::BINARY, 0, [0, 0, 0, 0, 0])]
+  #[case(ByteSize::BINARY, 4095, [0, 0, 0, 63, 63])]
+  #[case(ByteSize::BINARY, (1 << 30) - 1, [63, 63, 63, 63, 63])]
+  #[case(ByteSize::DECIMAL, 3999, [0, 0, 0, 39, 99])]
+  #[case(ByteSize::DECIMAL, 1_234_567_890, [12, 34, 56, 78, 90])]
+  #[case(ByteSize::DECIMAL, 9_999_999_999, [99, 99, 99, 99, 99])]
+  fn test_word(#[case] size: ByteSize, #[case] magnitude: u64, #[case] bytes: [u8; 5]) {
+    let word = size.word(magnitude, false);
+
+    assert_eq!(word, Word::from_bytes(bytes, false));
+    assert_eq!(size.magnitude(&word), magnitude);
+    assert_eq!(size.value(&word), -(magnitude as i64));
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, 1 << 30, [0, 0, 0, 0, 0])]
+  #[case(ByteSize::DECIMAL, 10_000_000_001, [0, 0, 0, 0, 1])]
+  fn test_word_keeps_remainder(
+    #[case] size: ByteSize,
+    #[case] magnitude: u64,
+    #[case] bytes: [u8; 5],
+  ) {
+    assert_eq!(size.word(magnitude, true), Word::from_bytes(bytes, true));
+  }
+
+  #[test]
+  fn test_binary_matches_packed_data() {
+    let word = Word::new(0b000001_000010_000011_000100_000101, Some(true));
+
+    assert_eq!(ByteSize::BINARY.magnitude(&word), word.read_data() as u64);
+  }
+}
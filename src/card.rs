@@ -0,0 +1,230 @@
+use std::{
+  collections::VecDeque,
+  io::{self, BufRead, Write},
+  path::Path,
+};
+
+use crate::{deck::Deck, device::Device, error::DeviceError, word::Word};
+
+/// Card reader taking its deck from text in the format of [`Deck`]
+///
+/// A card reads as 16 words of five characters. The reader is ready as long
+/// as an IN finds a card, so the usual wait for the last card with JBUS
+/// ends. The IN after the last card finds the hopper empty: it leaves the
+/// memory as it is and the reader stays busy from then on, so a program
+/// finds the end of the deck with JBUS or JRED, and a further IN fails.
+pub struct CardReader {
+  cards: VecDeque<Vec<Word>>,
+  /// Whether an IN found the deck run out
+  exhausted: bool,
+}
+
+impl CardReader {
+  /// Number of words in a card
+  pub const BLOCK_SIZE: usize = Deck::CARD_SIZE;
+
+  /// Number of characters in a card
+  pub const COLUMNS: usize = Deck::COLUMNS;
+
+  /// Reads the deck from the file
+  pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+    Ok(Self::from_deck(Deck::open(path)?))
+  }
+
+  /// Reads the deck from the text, a line that is too long or has a
+  /// character outside the MIX character code is refused
+  pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+    Ok(Self::from_deck(Deck::from_reader(reader)?))
+  }
+
+  /// Loads the deck into the reader
+  pub fn from_deck(deck: Deck) -> Self {
+    Self {
+      cards: deck.cards.into(),
+      exhausted: false,
+    }
+  }
+
+  /// Number of cards left in the deck
+  pub fn remaining(&self) -> usize {
+    self.cards.len()
+  }
+}
+
+impl Device for CardReader {
+  fn block_size(&self) -> usize {
+    Self::BLOCK_SIZE
+  }
+
+  /// The reader is not ready once an IN finds the deck run out
+  fn is_busy(&self) -> bool {
+    self.exhausted
+  }
+
+  /// Reads the next card, or no words the first time the deck is found run
+  /// out
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    match self.cards.pop_front() {
+      Some(card) => Ok(card),
+      None if !self.exhausted => {
+        self.exhausted = true;
+        Ok(Vec::new())
+      }
+      None => Err(DeviceError::Io(io::ErrorKind::UnexpectedEof)),
+    }
+  }
+}
+
+/// Card punch writing its cards as text in the format of [`Deck`], such as to
+/// a file that a card reader takes as its deck later
+///
+/// A card with a byte that is not a character cannot be punched and faults.
+pub struct CardPunch {
+  output: Box<dyn Write>,
+}
+
+impl CardPunch {
+  /// Number of words in a card
+  pub const BLOCK_SIZE: usize = Deck::CARD_SIZE;
+
+  /// Creates the punch writing the cards to the output
+  pub fn new(output: impl Write + 'static) -> Self {
+    Self {
+      output: Box::new(output),
+    }
+  }
+}
+
+impl Device for CardPunch {
+  fn block_size(&self) -> usize {
+    Self::BLOCK_SIZE
+  }
+
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    writeln!(self.output, "{}", Deck::line(block)?)?;
+    self.output.flush()?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    charcode,
+    computer::{Computer, HaltReason},
+    error::MixError,
+    instruction::{Instruction, Operation, Target},
+    program::Program,
+    register::Register,
+  };
+
+  #[test]
+  fn test_read() {
+    let mut reader = CardReader::from_reader("HELLO WORLD\n\n0123456789".as_bytes()).unwrap();
+
+    assert_eq!(reader.remaining(), 3);
+
+    let card = reader.read().unwrap();
+
+    assert_eq!(card.len(), 16);
+    assert_eq!(
+      charcode::words_to_string(&card[..3]).unwrap(),
+      "HELLO WORLD    "
+    );
+    assert!(card[3..].iter().all(|word| *word == Word::default()));
+
+    assert_eq!(reader.read(), Ok(vec![Word::default(); 16]));
+    assert_eq!(
+      charcode::words_to_string(&reader.read().unwrap()[..2]).unwrap(),
+      "0123456789"
+    );
+    assert!(!reader.is_busy());
+    assert_eq!(reader.read(), Ok(Vec::new()));
+    assert!(reader.is_busy());
+    assert_eq!(
+      reader.read(),
+      Err(DeviceError::Io(io::ErrorKind::UnexpectedEof))
+    );
+  }
+
+  #[test]
+  fn test_full_card() {
+    let line = "A".repeat(80);
+    let mut reader = CardReader::from_reader(line.as_bytes()).unwrap();
+
+    assert_eq!(reader.read(), Ok(vec![Word::from_bytes([1; 5], true); 16]));
+  }
+
+  #[rstest]
+  #[case("B".repeat(81))]
+  #[case("ABC\nlowercase".to_string())]
+  #[case("TAB\tTAB".to_string())]
+  fn test_invalid_deck(#[case] text: String) {
+    let error = CardReader::from_reader(text.as_bytes()).err().unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  }
+
+  #[test]
+  fn test_punch_to_reader() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let mut punch = CardPunch::new(file.reopen().unwrap());
+    let mut card = charcode::str_to_words("PUNCHED").unwrap();
+
+    card.resize(16, Word::default());
+    punch.write(&card).unwrap();
+    punch.write(&[Word::default(); 16]).unwrap();
+
+    assert_eq!(
+      punch.write(&[Word::from_bytes([1, 2, 3, 4, 99], true); 16]),
+      Err(DeviceError::Io(io::ErrorKind::InvalidData))
+    );
+
+    let mut reader = CardReader::open(file.path()).unwrap();
+
+    assert_eq!(reader.remaining(), 2);
+    assert_eq!(reader.read(), Ok(card));
+  }
+
+  #[test]
+  fn test_end_of_deck_on_machine() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(
+        16,
+        Box::new(CardReader::from_reader("FIRST\nSECOND".as_bytes()).unwrap()),
+      )
+      .unwrap();
+
+    computer.memory[1032] = Word::new(9, Some(true));
+
+    // Reads cards into 1000, 1016, ... until the reader is not ready, rI1
+    // keeps the offset of the next card. The IN that finds the deck run out
+    // stores nothing.
+    program.add(Instruction::new(true, 4, 0, 16, Operation::Jbus));
+    program.add(Instruction::new(true, 1000, 1, 16, Operation::In));
+    program.add(Instruction::new(true, 16, 0, 0, Operation::Inc(Target::I1)));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Jmp));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.ri[0], Register::new(48, Some(true)));
+    assert_eq!(
+      charcode::words_to_string(&computer.memory[1016..1018]).unwrap(),
+      "SECOND    "
+    );
+    assert_eq!(computer.memory[1032], Word::new(9, Some(true)));
+
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 1000, 0, 16, Operation::In));
+
+    assert_eq!(computer.execute(&program), Err(MixError::NotReady(16)));
+  }
+}
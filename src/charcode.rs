@@ -0,0 +1,219 @@
+use crate::{error::InvalidChar, word::Word};
+
+/// Characters of the MIX character code, by their codes 0-55
+///
+/// Codes 10, 20 and 21 are the Greek letters Δ, Σ and Π that Knuth puts
+/// between the Latin letters.
+pub const CHARS: [char; 56] = [
+  ' ', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'Δ', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q',
+  'R', 'Σ', 'Π', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', '1', '2', '3', '4', '5', '6', '7',
+  '8', '9', '.', ',', '(', ')', '+', '-', '*', '/', '=', '$', '<', '>', '@', ';', ':', '\'',
+];
+
+/// Code of the digit 0, the digits 0-9 take the codes 30-39
+pub const DIGIT_ZERO: u8 = 30;
+
+/// Returns the character of the code, codes above 55 have no character
+pub fn code_to_char(code: u8) -> Option<char> {
+  CHARS.get(code as usize).copied()
+}
+
+/// Returns the code of the character
+///
+/// Lowercase letters are not part of the code. The Greek letters may also be
+/// written as `~`, `[` and `#`, the ASCII stand-ins other MIX tools use for
+/// them.
+pub fn char_to_code(char: char) -> Option<u8> {
+  let char = match char {
+    '~' => 'Δ',
+    '[' => 'Σ',
+    '#' => 'Π',
+    _ => char,
+  };
+
+  CHARS.iter().position(|&c| c == char).map(|code| code as u8)
+}
+
+/// Writes the number as decimal digits in character code, the way CHAR
+/// fills the ten bytes of rA and rX, the highest digits that do not fit are
+/// dropped
+pub fn number_to_digits<const N: usize>(number: u64) -> [u8; N] {
+  let mut digits = [DIGIT_ZERO; N];
+  let mut rest = number;
+
+  for digit in digits.iter_mut().rev() {
+    *digit += (rest % 10) as u8;
+    rest /= 10;
+  }
+
+  digits
+}
+
+/// Reads the codes as decimal digits, the way NUM reads the ten bytes of rA
+/// and rX
+///
+/// Any code is taken modulo 10, so that a blank reads as 0 just as the
+/// digit 0 does.
+pub fn digits_to_number(codes: &[u8]) -> u64 {
+  codes
+    .iter()
+    .fold(0, |number, code| number * 10 + (code % 10) as u64)
+}
+
+/// Encodes the text five characters to a word, the last word is padded with
+/// blanks
+pub fn str_to_words(text: &str) -> Result<Vec<Word>, InvalidChar> {
+  let codes = text
+    .chars()
+    .map(|char| char_to_code(char).ok_or(InvalidChar(char)))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  Ok(
+    codes
+      .chunks(Word::BYTES)
+      .map(|chunk| {
+        let mut bytes = [0; Word::BYTES];
+
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        Word::from_bytes(bytes, true)
+      })
+      .collect(),
+  )
+}
+
+/// Encodes the line as a block of the columns, the way a typewriter or a
+/// terminal reads it, the columns past its end are blank
+///
+/// None if the line is longer than the block or has a character outside the
+/// code.
+pub fn line_to_words(line: &str, columns: usize) -> Option<Vec<Word>> {
+  if line.chars().count() > columns {
+    return None;
+  }
+
+  str_to_words(&format!("{:<1$}", line, columns)).ok()
+}
+
+/// Decodes the words five characters each, the way a printer line or a card
+/// reads, none if a byte is not a character
+pub fn words_to_string(words: &[Word]) -> Option<String> {
+  words
+    .iter()
+    .map(|word| word.to_chars())
+    .collect::<Option<Vec<_>>>()
+    .map(|chars| chars.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(0, Some(' '))]
+  #[case(1, Some('A'))]
+  #[case(10, Some('Δ'))]
+  #[case(21, Some('Π'))]
+  #[case(29, Some('Z'))]
+  #[case(30, Some('0'))]
+  #[case(39, Some('9'))]
+  #[case(55, Some('\''))]
+  #[case(56, None)]
+  #[case(63, None)]
+  fn test_code_to_char(#[case] code: u8, #[case] expected: Option<char>) {
+    assert_eq!(code_to_char(code), expected);
+  }
+
+  #[rstest]
+  #[case(' ', Some(0))]
+  #[case('A', Some(1))]
+  #[case('Δ', Some(10))]
+  #[case('~', Some(10))]
+  #[case('[', Some(20))]
+  #[case('#', Some(21))]
+  #[case('9', Some(39))]
+  #[case('\'', Some(55))]
+  #[case('a', None)]
+  #[case('!', None)]
+  fn test_char_to_code(#[case] char: char, #[case] expected: Option<u8>) {
+    assert_eq!(char_to_code(char), expected);
+  }
+
+  #[test]
+  fn test_codes_round_trip() {
+    for code in 0..CHARS.len() as u8 {
+      assert_eq!(code_to_char(code).and_then(char_to_code), Some(code));
+    }
+  }
+
+  #[rstest]
+  #[case(0, [30, 30, 30, 30, 30, 30, 30, 30, 30, 30])]
+  #[case(12_977_699, [30, 30, 31, 32, 39, 37, 37, 36, 39, 39])]
+  #[case(9_999_999_999, [39; 10])]
+  #[case(12_345_678_901, [32, 33, 34, 35, 36, 37, 38, 39, 30, 31])]
+  fn test_number_to_digits(#[case] number: u64, #[case] digits: [u8; 10]) {
+    assert_eq!(number_to_digits::<10>(number), digits);
+    assert_eq!(digits_to_number(&digits), number % 10_000_000_000);
+  }
+
+  #[rstest]
+  #[case(&[], 0)]
+  #[case(&[31, 32, 33], 123)]
+  #[case(&[0, 0, 31, 39, 30], 190)]
+  #[case(&[1, 12, 55], 125)]
+  fn test_digits_to_number(#[case] codes: &[u8], #[case] number: u64) {
+    assert_eq!(digits_to_number(codes), number);
+  }
+
+  #[rstest]
+  #[case("", vec![])]
+  #[case("AB", vec![[1, 2, 0, 0, 0]])]
+  #[case("HELLO WORLD", vec![[8, 5, 13, 13, 16], [0, 26, 16, 19, 13], [4, 0, 0, 0, 0]])]
+  fn test_str_to_words(#[case] text: &str, #[case] bytes: Vec<[u8; 5]>) {
+    let words: Vec<_> = bytes
+      .into_iter()
+      .map(|bytes| Word::from_bytes(bytes, true))
+      .collect();
+
+    assert_eq!(str_to_words(text), Ok(words.clone()));
+    assert_eq!(
+      words_to_string(&words),
+      Some(format!("{:<1$}", text, words.len() * 5))
+    );
+  }
+
+  #[test]
+  fn test_str_to_words_invalid() {
+    assert_eq!(str_to_words("MIXal"), Err(InvalidChar('a')));
+  }
+
+  #[rstest]
+  #[case("", 10, Some(vec![[0; 5], [0; 5]]))]
+  #[case("AB", 10, Some(vec![[1, 2, 0, 0, 0], [0; 5]]))]
+  #[case("HELLO", 5, Some(vec![[8, 5, 13, 13, 16]]))]
+  #[case("HELLO!", 10, None)]
+  #[case("HELLO WORLD", 10, None)]
+  fn test_line_to_words(
+    #[case] line: &str,
+    #[case] columns: usize,
+    #[case] bytes: Option<Vec<[u8; 5]>>,
+  ) {
+    let words = bytes.map(|bytes| {
+      bytes
+        .into_iter()
+        .map(|bytes| Word::from_bytes(bytes, true))
+        .collect()
+    });
+
+    assert_eq!(line_to_words(line, columns), words);
+  }
+
+  #[test]
+  fn test_words_to_string_invalid() {
+    assert_eq!(
+      words_to_string(&[Word::from_bytes([1, 2, 60, 0, 0], true)]),
+      None
+    );
+  }
+}
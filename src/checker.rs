@@ -0,0 +1,307 @@
+use crate::{
+  byte::ByteSize,
+  computer::{Compare, Computer, HaltReason},
+  error::MixError,
+  instruction::Target,
+  program::Program,
+  word::Word,
+};
+
+/// Result of executing a single instruction
+pub type Outcome = Result<Option<HaltReason>, MixError>;
+
+/// Part of the state in which the two machines disagree, the registers are
+/// given as signed numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difference {
+  /// One machine halted or faulted and the other did not, or differently
+  Outcome(Outcome, Outcome),
+  /// The machines went on to different locations
+  Location(i64, i64),
+  /// A register other than rJ holds different numbers
+  Register(Target, i64, i64),
+  /// The jump register holds different numbers
+  Jump(i64, i64),
+  /// The overflow toggles are set differently
+  Overflow(bool, bool),
+  /// The comparison indicators are set differently
+  Comparison(Compare, Compare),
+}
+
+/// The first point at which the program behaves differently under the two
+/// byte sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+  /// Number of instructions executed, including the one that diverged
+  pub step: u64,
+  /// Location of the instruction that diverged
+  pub location: i64,
+  /// What the machines disagree on
+  pub difference: Difference,
+}
+
+/// Runs a program on two machines that differ in byte size only, one
+/// instruction at a time, to check that the program does not depend on the
+/// size of a byte
+///
+/// The memory is not compared, as the words of the program itself differ
+/// between byte sizes. A program that relies on the byte size sooner or later
+/// shows it in a register, a toggle or the flow of control.
+pub struct Checker {
+  pub machines: [Computer; 2],
+}
+
+impl Checker {
+  /// Creates the machines with the byte sizes, the rest of their state can
+  /// be set up before the program runs
+  pub fn new(first: ByteSize, second: ByteSize) -> Self {
+    let machine = |byte_size| {
+      let mut machine = Computer::new();
+
+      machine.byte_size = byte_size;
+      machine
+    };
+
+    Self {
+      machines: [machine(first), machine(second)],
+    }
+  }
+
+  /// Loads the program into both machines and runs it from its entry point
+  pub fn execute(&mut self, program: &Program) -> Result<Result<HaltReason, MixError>, Divergence> {
+    for machine in self.machines.iter_mut() {
+      machine.load(program);
+    }
+
+    self.run(program.entry)
+  }
+
+  /// Runs both machines from the start address until they halt, fault or
+  /// disagree, returns how the program ended if they agree throughout
+  ///
+  /// The limit of the first machine bounds the number of instructions, as it
+  /// does for [`Computer::run`], so a program that never halts ends with
+  /// [`HaltReason::LimitExceeded`].
+  pub fn run(&mut self, start: usize) -> Result<Result<HaltReason, MixError>, Divergence> {
+    for machine in self.machines.iter_mut() {
+      machine.pc = start as i64;
+    }
+
+    let limit = self.machines[0].limit;
+    let mut step = 0;
+
+    loop {
+      if limit.is_some_and(|limit| step >= limit) {
+        return Ok(Ok(HaltReason::LimitExceeded));
+      }
+
+      let location = self.machines[0].pc;
+      let [first, second] = self.machines.each_mut().map(|machine| machine.step());
+
+      step += 1;
+
+      let difference = if first != second {
+        Some(Difference::Outcome(first, second))
+      } else {
+        self.difference()
+      };
+
+      if let Some(difference) = difference {
+        return Err(Divergence {
+          step,
+          location,
+          difference,
+        });
+      }
+
+      match first {
+        Ok(None) => continue,
+        Ok(Some(reason)) => return Ok(Ok(reason)),
+        Err(error) => return Ok(Err(error)),
+      }
+    }
+  }
+
+  /// Compares the state of the machines after an instruction
+  fn difference(&self) -> Option<Difference> {
+    let [first, second] = &self.machines;
+
+    if first.pc != second.pc {
+      return Some(Difference::Location(first.pc, second.pc));
+    }
+
+    let registers = |machine: &Computer| {
+      [
+        (Target::A, machine.a),
+        (Target::I1, Word::from(machine.ri[0])),
+        (Target::I2, Word::from(machine.ri[1])),
+        (Target::I3, Word::from(machine.ri[2])),
+        (Target::I4, Word::from(machine.ri[3])),
+        (Target::I5, Word::from(machine.ri[4])),
+        (Target::I6, Word::from(machine.ri[5])),
+        (Target::X, machine.x),
+      ]
+      .map(|(target, word)| (target, machine.byte_size.value(&word)))
+    };
+
+    for ((target, left), (_, right)) in registers(first).into_iter().zip(registers(second)) {
+      if left != right {
+        return Some(Difference::Register(target, left, right));
+      }
+    }
+
+    let jump = |machine: &Computer| machine.byte_size.value(&Word::from(machine.j));
+
+    if jump(first) != jump(second) {
+      return Some(Difference::Jump(jump(first), jump(second)));
+    }
+
+    if first.overflow() != second.overflow() {
+      return Some(Difference::Overflow(first.overflow(), second.overflow()));
+    }
+
+    if first.comparison != second.comparison {
+      return Some(Difference::Comparison(first.comparison, second.comparison));
+    }
+
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::instruction::{Condition, Instruction, Operation};
+
+  fn hlt() -> Instruction {
+    Instruction::new(true, 0, 0, 0, Operation::Hlt)
+  }
+
+  #[test]
+  fn test_independent_program() {
+    let mut checker = Checker::new(ByteSize::BINARY, ByteSize::DECIMAL);
+    let mut program = Program::new();
+
+    // Sums the numbers 1 through 100 in rA, counting down in rI1
+    program.add(Instruction::new(
+      true,
+      100,
+      0,
+      0,
+      Operation::Ent(Target::I1),
+    ));
+    program.add(Instruction::new(true, 0, 1, 0, Operation::Inc(Target::A)));
+    program.add(Instruction::new(true, 1, 0, 0, Operation::Dec(Target::I1)));
+    program.add(Instruction::new(
+      true,
+      1,
+      0,
+      0,
+      Operation::Jump(Target::I1, Condition::Positive),
+    ));
+    program.add(Instruction::new(true, 3000, 0, 5, Operation::St(Target::A)));
+    program.add(hlt());
+
+    assert_eq!(checker.execute(&program), Ok(Ok(HaltReason::Halted)));
+
+    for machine in &checker.machines {
+      assert_eq!(machine.byte_size.value(&machine.memory[3000]), 5050);
+    }
+  }
+
+  #[test]
+  fn test_limit() {
+    let mut checker = Checker::new(ByteSize::BINARY, ByteSize::DECIMAL);
+    let mut program = Program::new();
+
+    checker.machines[0].limit = Some(100);
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Jmp));
+
+    assert_eq!(checker.execute(&program), Ok(Ok(HaltReason::LimitExceeded)));
+    assert_eq!(checker.machines[1].time, 100);
+  }
+
+  #[rstest]
+  // Shifting by a byte multiplies by the byte size
+  #[case(
+    vec![
+      Instruction::new(true, 1, 0, 0, Operation::Ent(Target::A)),
+      Instruction::new(true, 1, 0, 0, Operation::Sla),
+    ],
+    2,
+    1,
+    Difference::Register(Target::A, 64, 100)
+  )]
+  // The product spills into rA at a different number
+  #[case(
+    vec![
+      Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)),
+      Instruction::new(true, 1000, 0, 5, Operation::Mul),
+    ],
+    2,
+    1,
+    Difference::Register(Target::A, 2, 0)
+  )]
+  // The sum overflows a binary word only
+  #[case(
+    vec![
+      Instruction::new(true, 1001, 0, 5, Operation::Ld(Target::A)),
+      Instruction::new(true, 1001, 0, 5, Operation::Add),
+    ],
+    2,
+    1,
+    Difference::Register(Target::A, 926_258_176, 2_000_000_000)
+  )]
+  fn test_dependent_program(
+    #[case] instructions: Vec<Instruction>,
+    #[case] step: u64,
+    #[case] location: i64,
+    #[case] difference: Difference,
+  ) {
+    let mut checker = Checker::new(ByteSize::BINARY, ByteSize::DECIMAL);
+    let mut program = Program::new();
+
+    for machine in checker.machines.iter_mut() {
+      machine.memory[1000] = machine.byte_size.word(50_000, true);
+      machine.memory[1001] = machine.byte_size.word(1_000_000_000, true);
+    }
+
+    for instruction in instructions {
+      program.add(instruction);
+    }
+    program.add(hlt());
+
+    assert_eq!(
+      checker.execute(&program),
+      Err(Divergence {
+        step,
+        location,
+        difference,
+      })
+    );
+  }
+
+  #[test]
+  fn test_outcome_difference() {
+    let mut checker = Checker::new(ByteSize::BINARY, ByteSize::DECIMAL);
+
+    // The same bytes of a JMP make a different address for each byte size
+    for machine in checker.machines.iter_mut() {
+      machine.memory[0] = Word::from_bytes([63, 0, 0, 0, 39], true);
+    }
+
+    assert_eq!(
+      checker.run(0),
+      Err(Divergence {
+        step: 1,
+        location: 0,
+        difference: Difference::Outcome(
+          Err(MixError::InvalidAddress(4032)),
+          Err(MixError::InvalidAddress(6300))
+        ),
+      })
+    );
+  }
+}
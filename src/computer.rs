@@ -1,8 +1,32 @@
-use std::fmt;
+use std::{cmp::Ordering, collections::VecDeque, fmt, io, ops::Range};
 
-use crate::{instruction::Command, program::Program, register::Register, word::Word, Data};
+use crate::{
+  byte::ByteSize,
+  card::CardReader,
+  charcode,
+  device::{self, Device, DeviceKind, MemoryUnit},
+  error::{DecodeError, MixError, Undefined, Warning},
+  field::FieldSpec,
+  float,
+  governor::Governor,
+  instruction::{Condition, Instruction, Operation, Target},
+  interrupt::{self, State},
+  mapping::UnitMap,
+  pair::WordPair,
+  printer::LinePrinter,
+  program::Program,
+  register::Register,
+  timing::Timing,
+  transcript::Entry,
+  transfer::{Completion, Transfer},
+  word::{Overflowing, Word},
+  Data, Signed,
+};
 
-#[derive(Debug)]
+/// Number of locations in the memory of the machine
+pub(crate) const MEMORY: usize = 4000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Compare {
   None,
   Less,
@@ -10,76 +34,3007 @@ pub enum Compare {
   Greater,
 }
 
+/// The ordering of two numbers, as the comparison indicator shows it
+impl From<Ordering> for Compare {
+  fn from(value: Ordering) -> Self {
+    match value {
+      Ordering::Less => Self::Less,
+      Ordering::Equal => Self::Equal,
+      Ordering::Greater => Self::Greater,
+    }
+  }
+}
+
+/// Reason why the machine stopped executing a program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HaltReason {
+  /// The program executed HLT
+  Halted,
+  /// The location counter ran past the last memory location
+  EndOfMemory,
+  /// The machine executed as many instructions as it was allowed to
+  LimitExceeded,
+}
+
+/// What the machine does with an effective address that is out of memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressPolicy {
+  /// The instruction faults with an invalid address
+  #[default]
+  Fault,
+  /// The address wraps around the memory, as it does on machines that keep
+  /// only as many digits of an address as their memory needs
+  Wrap,
+  /// The address wraps around the memory and a warning is recorded, since
+  /// the program relies on behavior MIX leaves undefined
+  Warn,
+}
+
 pub struct Computer {
-  pub overflow: bool,
+  /// Location of the next instruction, negative in the control memory
+  pub pc: i64,
+  /// Location of the instruction executed last, which is the one that
+  /// faulted once the machine faults
+  pub location: i64,
+  /// Units of time spent executing instructions
+  pub time: u64,
+  /// The time split between computing and waiting for the units
+  pub timing: Timing,
+  overflow: bool,
   pub comparison: Compare,
-  pub memory: [Word; 4000],
+  pub memory: [Word; MEMORY],
   pub a: Word,
   pub x: Word,
-  pub i1: Register,
-  pub i2: Register,
-  pub i3: Register,
-  pub i4: Register,
-  pub i5: Register,
-  pub i6: Register,
+  pub j: Register,
+  /// Index registers rI1-rI6, in order
+  pub ri: [Register; 6],
+  /// Units attached by their numbers, a unit that is not attached does not
+  /// exist for the program
+  pub devices: [Option<Box<dyn Device>>; device::UNITS],
+  /// Operations the units are busy with, by unit
+  pub(crate) transfers: [Option<Transfer>; device::UNITS],
+  /// Whether the interrupt facility is attached, it is off by default since
+  /// the units would interrupt programs that do not expect it
+  pub interrupts: bool,
+  pub state: State,
+  /// Control memory of the interrupt facility, location -1 comes first
+  pub control: [Word; interrupt::CONTROL_MEMORY],
+  pub(crate) pending: VecDeque<i64>,
+  /// Whether the floating point attachment is installed, without it the
+  /// floating point operations fault
+  pub floating_point: bool,
+  /// Whether the binary extension of GNU MDK is enabled, which adds the
+  /// bitwise operations, the bit shifts and the parity jumps
+  pub binary: bool,
+  /// Number of values a byte holds, the floating point attachment and the
+  /// binary extension work on a binary machine only
+  pub byte_size: ByteSize,
+  /// Largest number of instructions a run may execute, so that a program
+  /// that loops forever stops
+  pub limit: Option<u64>,
+  /// What happens to the effective addresses that are out of memory
+  pub address_policy: AddressPolicy,
+  /// Warnings recorded while executing programs, oldest first
+  pub warnings: Vec<Warning>,
+  /// Whether the machine faults the first time the program relies on
+  /// behavior MIX leaves undefined, instead of doing what typical hardware
+  /// does
+  pub strict: bool,
+  /// Operations of the units recorded as they are done, oldest first, none
+  /// unless the transcript is kept
+  pub transcript: Option<Vec<Entry>>,
+  /// Governor holding the machine back to a speed of the wall clock, the
+  /// machine runs as fast as it can without one
+  pub governor: Option<Governor>,
 }
 
 impl Computer {
   pub fn new() -> Self {
     Self {
+      pc: 0,
+      location: 0,
+      time: 0,
+      timing: Timing::default(),
       overflow: false,
       comparison: Compare::None,
-      memory: [Word::default(); 4000],
+      memory: [Word::default(); MEMORY],
       a: Word::default(),
       x: Word::default(),
-      i1: Register::default(),
-      i2: Register::default(),
-      i3: Register::default(),
-      i4: Register::default(),
-      i5: Register::default(),
-      i6: Register::default(),
+      j: Register::default(),
+      ri: [Register::default(); 6],
+      devices: std::array::from_fn(|unit| {
+        let kind = DeviceKind::of(unit as u32).expect("Unit exists");
+
+        Some(Box::new(MemoryUnit::new(kind)) as Box<dyn Device>)
+      }),
+      transfers: std::array::from_fn(|_| None),
+      interrupts: false,
+      state: State::Normal,
+      control: [Word::default(); interrupt::CONTROL_MEMORY],
+      pending: VecDeque::new(),
+      floating_point: false,
+      binary: false,
+      byte_size: ByteSize::BINARY,
+      limit: None,
+      address_policy: AddressPolicy::Fault,
+      warnings: Vec::new(),
+      strict: false,
+      transcript: None,
+      governor: None,
+    }
+  }
+
+  /// Creates a decimal machine, whose bytes hold two decimal digits
+  pub fn decimal() -> Self {
+    Self {
+      byte_size: ByteSize::DECIMAL,
+      ..Self::new()
+    }
+  }
+
+  /// Limits every run of the machine to the number of instructions
+  pub fn with_limit(mut self, max_instructions: u64) -> Self {
+    self.limit = Some(max_instructions);
+    self
+  }
+
+  /// Attaches the units as the mapping backs them instead of the canonical
+  /// units, fails if a file backing a unit cannot be opened
+  pub fn with_units(mut self, map: UnitMap) -> io::Result<Self> {
+    self.devices = map.open()?;
+    Ok(self)
+  }
+
+  /// Holds every run of the machine back to the speed of the governor
+  pub fn with_governor(mut self, governor: Governor) -> Self {
+    self.governor = Some(governor);
+    self
+  }
+
+  /// Keeps the transcript of the operations of the units
+  pub fn with_transcript(mut self) -> Self {
+    self.transcript = Some(Vec::new());
+    self
+  }
+
+  /// Maps the card reader to the standard input and the line printer to the
+  /// standard output, so that the machine runs as a filter of text
+  ///
+  /// The whole input is read as the deck at once, a line that is not a card
+  /// is refused.
+  pub fn with_stdio(mut self) -> io::Result<Self> {
+    self.devices[16] = Some(Box::new(CardReader::from_reader(io::stdin().lock())?));
+    self.devices[18] = Some(Box::new(LinePrinter::stdout()));
+    Ok(self)
+  }
+
+  /// Checks if the overflow toggle is on
+  pub fn overflow(&self) -> bool {
+    self.overflow
+  }
+
+  /// Turns the overflow toggle on, as an operation whose result does not fit
+  /// into the register does
+  pub fn set_overflow(&mut self) {
+    self.overflow = true;
+  }
+
+  /// Turns the overflow toggle off
+  pub fn clear_overflow(&mut self) {
+    self.overflow = false;
+  }
+
+  /// Loads the program into memory with its instructions from address 0
+  /// and returns the range of addresses the instructions occupy
+  pub fn load(&mut self, program: &Program) -> Range<usize> {
+    self.load_at(program, 0)
+  }
+
+  /// Loads the instructions of the program from the origin address and its
+  /// segments at their own origins, returns the range of addresses the
+  /// instructions occupy
+  pub fn load_at(&mut self, program: &Program, origin: usize) -> Range<usize> {
+    let words: Vec<Word> = program
+      .instructions
+      .iter()
+      .map(|instruction| instruction.encode(self.byte_size))
+      .collect();
+    let range = self.load_image(origin, &words);
+
+    for (origin, words) in &program.segments {
+      self.load_image(*origin, words);
+    }
+
+    range
+  }
+
+  /// Loads raw words into memory from the origin address and returns the
+  /// range of addresses they occupy
+  pub fn load_image(&mut self, origin: usize, words: &[Word]) -> Range<usize> {
+    let range = origin..origin + words.len();
+
+    assert!(
+      range.end <= self.memory.len(),
+      "Image does not fit in memory"
+    );
+
+    self.memory[range.clone()].copy_from_slice(words);
+
+    range
+  }
+
+  /// Loads the program and executes it from its entry point
+  pub fn execute(&mut self, program: &Program) -> Result<HaltReason, MixError> {
+    self.load(program);
+    self.run(program.entry)
+  }
+
+  /// Loads the program and executes it from its entry point, stops after
+  /// the number of instructions whatever the limit of the machine is
+  pub fn execute_with_limit(
+    &mut self,
+    program: &Program,
+    max_instructions: u64,
+  ) -> Result<HaltReason, MixError> {
+    self.load(program);
+    self.run_with_limit(program.entry, Some(max_instructions))
+  }
+
+  /// Executes instructions from the start address until the machine halts
+  /// or runs out of its limit
+  pub fn run(&mut self, start: usize) -> Result<HaltReason, MixError> {
+    self.run_with_limit(start, self.limit)
+  }
+
+  /// Presses the GO button: reads a card from the card reader into the
+  /// memory from address 0, waits for the reader to be done and executes
+  /// from address 0, so that a self-loading deck boots the machine
+  pub fn go(&mut self) -> Result<HaltReason, MixError> {
+    let size = self.device(16)?.block_size();
+
+    if size > self.memory.len() {
+      return Err(MixError::InvalidAddress(size as i64 - 1));
+    }
+
+    self.wait(16)?;
+
+    let block = self.device_mut(16)?.read().map_err(|error| error.on(16))?;
+
+    self.start(16, Completion::Store(0..size, block))?;
+    self.wait(16)?;
+    self.run(0)
+  }
+
+  fn run_with_limit(&mut self, start: usize, limit: Option<u64>) -> Result<HaltReason, MixError> {
+    self.pc = start as i64;
+
+    let mut executed = 0;
+
+    if let Some(governor) = &mut self.governor {
+      governor.start(self.time);
+    }
+
+    loop {
+      if limit.is_some_and(|limit| executed >= limit) {
+        return Ok(HaltReason::LimitExceeded);
+      }
+
+      let reason = self.step()?;
+
+      if let Some(governor) = &mut self.governor {
+        governor.pace(self.time);
+      }
+
+      if let Some(reason) = reason {
+        return Ok(reason);
+      }
+
+      executed += 1;
+    }
+  }
+
+  /// Executes the instruction at the location counter, returns the reason
+  /// if the machine halts
+  pub fn step(&mut self) -> Result<Option<HaltReason>, MixError> {
+    self.complete_transfers()?;
+    self.serve_interrupt();
+
+    if self.pc >= self.memory.len() as i64 {
+      return Ok(Some(HaltReason::EndOfMemory));
+    }
+
+    self.location = self.pc;
+
+    // The location counter is left at the word that cannot be decoded
+    let instruction = Instruction::decode(self.cell(self.pc), self.byte_size)?;
+    let mut time = instruction.operation.time(instruction.modifier);
+
+    self.pc += 1;
+    // The time is counted before execution, so that HLT is taken into account
+    self.time += time;
+    self.timing.compute += time;
+
+    let operation = instruction.operation;
+
+    let binary = self.byte_size == ByteSize::BINARY;
+
+    if (operation.is_floating_point() && !(self.floating_point && binary))
+      || (operation.is_binary() && !(self.binary && binary))
+    {
+      return Err(MixError::MissingAttachment(instruction.operation));
+    }
+
+    match instruction.operation {
+      Operation::Nop => (),
+      Operation::Add | Operation::Sub => {
+        let value = self.operand(&instruction)?;
+        let Overflowing { result, overflow } = match instruction.operation {
+          Operation::Add => self.a.checked_add(&value, self.byte_size),
+          _ => self.a.checked_sub(&value, self.byte_size),
+        };
+
+        self.a = result;
+
+        if overflow {
+          self.set_overflow();
+        }
+      }
+      Operation::Mul => {
+        let value = self.operand(&instruction)?;
+
+        self.set_rax(self.a.checked_mul(&value, self.byte_size).result);
+      }
+      Operation::Div => {
+        let value = self.operand(&instruction)?;
+        let Overflowing { result, overflow } = self.rax().checked_div(&value, self.byte_size);
+
+        // The quotient must fit in rA, otherwise the registers are left as is
+        if overflow {
+          if self.strict {
+            return Err(Undefined::DivisionOverflow.into());
+          }
+
+          self.set_overflow();
+        }
+
+        self.set_rax(result);
+      }
+      Operation::Num => self.num(),
+      Operation::Char => self.char(),
+      Operation::Hlt => {
+        self.finish_transfers()?;
+
+        return Ok(Some(HaltReason::Halted));
+      }
+      Operation::Int => {
+        if !self.interrupts {
+          return Err(MixError::MissingAttachment(instruction.operation));
+        }
+
+        match self.state {
+          State::Normal => self.enter_control(interrupt::PROGRAM_INTERRUPT),
+          State::Control => self.leave_control(),
+        }
+      }
+      Operation::Sla
+      | Operation::Sra
+      | Operation::Slax
+      | Operation::Srax
+      | Operation::Slc
+      | Operation::Src
+      | Operation::Slb
+      | Operation::Srb => {
+        let count = self.effective_address(&instruction)?;
+
+        if count < 0 {
+          return Err(MixError::InvalidShift(count));
+        }
+
+        self.shift(instruction.operation, count as u32);
+      }
+      Operation::Move => {
+        let source = self.address(&instruction)?;
+        let count = instruction.modifier as usize;
+
+        // Words are moved one at a time, so overlapping blocks are allowed
+        for offset in 0..count as i64 {
+          let destination = self.locate(self.value(&Word::from(self.ri[0])) + offset)?;
+          let location = self.locate(source + offset)?;
+          let word = *self.cell(location);
+
+          *self.cell_mut(destination) = word;
+        }
+
+        self.increase(Target::I1, count as i64)?;
+      }
+      Operation::Ld(target) => {
+        let value = self.operand(&instruction)?;
+
+        self.set_register(target, value)?;
+      }
+      Operation::Ldn(target) => {
+        let mut value = self.operand(&instruction)?;
+
+        value.write_sign(!value.read_sign());
+        self.set_register(target, value)?;
+      }
+      Operation::St(target) => {
+        let address = self.address(&instruction)?;
+
+        self.store(address, &self.register(target), instruction.field_spec()?);
+      }
+      Operation::Stj => {
+        let address = self.address(&instruction)?;
+
+        self.store(address, &Word::from(self.j), instruction.field_spec()?);
+      }
+      Operation::Stz => {
+        let address = self.address(&instruction)?;
+
+        self.store(
+          address,
+          &Word::new(0, Some(true)),
+          instruction.field_spec()?,
+        );
+      }
+      Operation::Jbus => {
+        if self.is_busy(instruction.modifier)? {
+          // A program spinning on JBUS waits for the unit
+          self.timing.compute -= time;
+          self.timing.wait[instruction.modifier as usize] += time;
+          self.jump(&instruction)?
+        }
+      }
+      Operation::Jred => {
+        if !self.is_busy(instruction.modifier)? {
+          self.jump(&instruction)?
+        }
+      }
+      Operation::Ioc => {
+        let operation = self.effective_address(&instruction)?;
+        let x = self.magnitude(&self.x);
+
+        time += self.wait_ready(instruction.modifier)?;
+        self
+          .device_mut(instruction.modifier)?
+          .control(operation, x)
+          .map_err(|error| error.on(instruction.modifier))?;
+        self.start(instruction.modifier, Completion::Control(operation))?;
+      }
+      Operation::In => {
+        let size = self.device(instruction.modifier)?.block_size();
+        let range = self.block(&instruction, size)?;
+        let x = self.magnitude(&self.x);
+
+        time += self.wait_ready(instruction.modifier)?;
+
+        let device = self.device_mut(instruction.modifier)?;
+        let block = device
+          .select(x)
+          .and_then(|_| device.read())
+          .map_err(|error| error.on(instruction.modifier))?;
+
+        self.start(instruction.modifier, Completion::Store(range, block))?;
+      }
+      Operation::Out => {
+        let size = self.device(instruction.modifier)?.block_size();
+        let range = self.block(&instruction, size)?;
+        let x = self.magnitude(&self.x);
+        let unit = instruction.modifier;
+
+        time += self.wait_ready(unit)?;
+        self
+          .device_mut(unit)?
+          .select(x)
+          .map_err(|error| error.on(unit))?;
+        self.start(unit, Completion::Write(range))?;
+      }
+      Operation::Jmp => self.jump(&instruction)?,
+      Operation::Jsj => self.pc = self.address(&instruction)?,
+      // The overflow toggle is turned off whether the jump occurs or not
+      Operation::Jov => {
+        if self.take_overflow() {
+          self.jump(&instruction)?
+        }
+      }
+      Operation::Jnov => {
+        if !self.take_overflow() {
+          self.jump(&instruction)?
+        }
+      }
+      Operation::Jl
+      | Operation::Je
+      | Operation::Jg
+      | Operation::Jge
+      | Operation::Jne
+      | Operation::Jle => {
+        if self.compared(instruction.operation) {
+          self.jump(&instruction)?
+        }
+      }
+      Operation::Jump(target, condition) => {
+        if self.satisfies(condition, &self.register(target)) {
+          self.jump(&instruction)?
+        }
+      }
+      Operation::Inc(target) => {
+        let value = self.effective_address(&instruction)?;
+
+        self.increase(target, value)?;
+      }
+      Operation::Dec(target) => {
+        let value = self.effective_address(&instruction)?;
+
+        self.increase(target, -value)?;
+      }
+      Operation::Ent(target) => {
+        let value = self.effective_address(&instruction)?;
+
+        // The sign of the instruction is loaded when the address is zero
+        self.set_register(target, self.signed(value, instruction.sign))?;
+      }
+      Operation::Enn(target) => {
+        let value = self.effective_address(&instruction)?;
+
+        self.set_register(target, self.signed(-value, !instruction.sign))?;
+      }
+      Operation::Cmp(target) => self.compare(&self.register(target), &instruction)?,
+      Operation::Fadd | Operation::Fsub | Operation::Fmul | Operation::Fdiv => {
+        let address = self.address(&instruction)?;
+        let value = *self.cell(address);
+        let (result, overflow) = match instruction.operation {
+          Operation::Fadd => float::add(&self.a, &value),
+          Operation::Fsub => float::subtract(&self.a, &value),
+          Operation::Fmul => float::multiply(&self.a, &value),
+          _ => float::divide(&self.a, &value),
+        };
+
+        self.a = result;
+
+        if overflow {
+          self.set_overflow();
+        }
+      }
+      Operation::And | Operation::Or | Operation::Xor => {
+        let address = self.address(&instruction)?;
+        let value = self.cell(address).read_data();
+        let data = self.a.read_data();
+
+        // The sign of rA is left untouched
+        self.a.write_data(match instruction.operation {
+          Operation::And => data & value,
+          Operation::Or => data | value,
+          _ => data ^ value,
+        });
+      }
+      Operation::Flot => self.a = float::from_integer(&self.a),
+      Operation::Fix => {
+        let (result, overflow) = float::to_integer(&self.a);
+
+        self.a = result;
+
+        if overflow {
+          self.set_overflow();
+        }
+      }
+      Operation::Fcmp => {
+        let address = self.address(&instruction)?;
+        let value = *self.cell(address);
+
+        self.comparison = float::compare(&self.a, &value, self.cell(float::EPSILON)).into();
+      }
+    }
+
+    if self.interrupts {
+      self.tick(time);
     }
+
+    Ok(None)
+  }
+
+  /// Converts the ten bytes of rA and rX in character code into a number in
+  /// rA, the sign of rA and the contents of rX are left untouched
+  fn num(&mut self) {
+    let value = charcode::digits_to_number(&self.rax().bytes());
+
+    // Only the remainder modulo the word size is kept on overflow
+    if value >= self.byte_size.capacity(5) {
+      self.set_overflow();
+    }
+
+    self.a = self.byte_size.word(value, self.a.read_sign());
+  }
+
+  /// Converts the number in rA into ten decimal digits in character code
+  /// filling rA and rX, the signs of the registers are left untouched
+  fn char(&mut self) {
+    let mut pair = self.rax();
+
+    pair.write_bytes(charcode::number_to_digits(self.magnitude(&self.a)));
+    self.set_rax(pair);
+  }
+
+  /// Returns rA and rX taken together as ten bytes
+  fn rax(&self) -> WordPair {
+    WordPair::new(self.a, self.x)
+  }
+
+  /// Replaces rA and rX with the words of the pair
+  fn set_rax(&mut self, pair: WordPair) {
+    self.a = pair.high;
+    self.x = pair.low;
+  }
+
+  /// Shifts the bytes of rA, or the ten bytes of rA and rX taken together,
+  /// leaving the signs of the registers untouched
+  fn shift(&mut self, operation: Operation, count: u32) {
+    let mut pair = self.rax();
+    let count = count as usize;
+
+    match operation {
+      Operation::Sla => self.a.shift_left_bytes(count),
+      Operation::Sra => self.a.shift_right_bytes(count),
+      Operation::Slax => pair.shift_left_bytes(count),
+      Operation::Srax => pair.shift_right_bytes(count),
+      Operation::Slc => pair.rotate_left_bytes(count),
+      Operation::Src => pair.rotate_right_bytes(count),
+      Operation::Slb => pair.shift_bits_left(count as u32),
+      Operation::Srb => pair.shift_bits_right(count as u32),
+      _ => unreachable!("Shift not implemented"),
+    }
+
+    if !matches!(operation, Operation::Sla | Operation::Sra) {
+      self.set_rax(pair);
+    }
+  }
+
+  /// Turns the overflow toggle off and returns whether it was on, which is
+  /// the only way the machine itself clears the toggle
+  fn take_overflow(&mut self) -> bool {
+    std::mem::take(&mut self.overflow)
+  }
+
+  /// Checks the comparison indicator against the condition of the jump, the
+  /// indicator that has never been set satisfies no condition
+  fn compared(&self, operation: Operation) -> bool {
+    matches!(
+      (operation, &self.comparison),
+      (Operation::Jl, Compare::Less)
+        | (Operation::Je, Compare::Equal)
+        | (Operation::Jg, Compare::Greater)
+        | (Operation::Jge, Compare::Greater | Compare::Equal)
+        | (Operation::Jne, Compare::Less | Compare::Greater)
+        | (Operation::Jle, Compare::Less | Compare::Equal)
+    )
   }
 
-  fn load(&mut self, program: &Program) {
-    for (index, instruction) in program.instructions.iter().enumerate() {
-      self.memory[index] = Word::from(instruction);
+  /// Checks the value of a register against the condition of the register
+  /// jump, both +0 and -0 count as zero
+  fn satisfies(&self, condition: Condition, value: &Word) -> bool {
+    let magnitude = self.magnitude(value);
+    let zero = magnitude == 0;
+    let negative = !zero && !value.read_sign();
+    let positive = !zero && value.read_sign();
+
+    match condition {
+      Condition::Negative => negative,
+      Condition::Zero => zero,
+      Condition::Positive => positive,
+      Condition::NonNegative => !negative,
+      Condition::NonZero => !zero,
+      Condition::NonPositive => !positive,
+      Condition::Even => magnitude.is_multiple_of(2),
+      Condition::Odd => !magnitude.is_multiple_of(2),
     }
   }
 
-  pub fn execute(&mut self, program: Program) {
-    self.load(&program);
+  /// Returns the contents of the register as a word
+  fn register(&self, target: Target) -> Word {
+    match target {
+      Target::A => self.a,
+      Target::X => self.x,
+      _ => Word::from(self.ri[target.offset() as usize - 1]),
+    }
+  }
 
-    for instruction in program.instructions.iter() {
-      match instruction.command {
-        Command::Noop => continue,
-        Command::Lda => {
-          self.a = Word::from(
-            self.memory[instruction.address as usize].read_with_modifier(instruction.modifier),
-          );
+  /// Replaces the contents of the register, an index register keeps only
+  /// the sign and the last two bytes of the word
+  ///
+  /// A strict machine faults instead of dropping bytes 1-3 that are not zero.
+  fn set_register(&mut self, target: Target, value: Word) -> Result<(), MixError> {
+    match target {
+      Target::A => self.a = value,
+      Target::X => self.x = value,
+      _ => {
+        if self.strict && (1..=3).any(|index| value.get_byte(index) != 0) {
+          return Err(Undefined::IndexOverflow(target).into());
         }
+
+        *self.index_mut(target.offset() as u8)? = Register::from(value)
       }
     }
+
+    Ok(())
   }
-}
 
-impl fmt::Display for Computer {
-  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    writeln!(f, "Memory:")?;
-    for (i, word) in self.memory.iter().enumerate().rev() {
-      write!(f, "{:04X}: ", i)?;
-      writeln!(f, "{}", word)?;
+  /// Adds the value to the register, the register keeps its sign when the
+  /// result is zero
+  ///
+  /// Only rA and rX report overflow, the index registers keep the last two
+  /// bytes unless the machine is strict.
+  fn increase(&mut self, target: Target, value: i64) -> Result<(), MixError> {
+    if !matches!(target, Target::A | Target::X) {
+      let register = self.index(target.offset() as u8)?;
+      let Overflowing { result, overflow } = register.checked_inc(value, self.byte_size);
+
+      if overflow && self.strict {
+        return Err(Undefined::IndexOverflow(target).into());
+      }
+
+      *self.index_mut(target.offset() as u8)? = result;
+
+      return Ok(());
     }
 
-    writeln!(f, "Overflow: {}", self.overflow)?;
-    writeln!(f, "Comparison: {:?}", self.comparison)?;
-    writeln!(f, "A: {}", self.a)?;
-    writeln!(f, "X: {}", self.x)?;
-    writeln!(f, "I1: {}", self.i1)?;
-    writeln!(f, "I2: {}", self.i2)?;
-    writeln!(f, "I3: {}", self.i3)?;
-    writeln!(f, "I4: {}", self.i4)?;
-    writeln!(f, "I5: {}", self.i5)?;
-    write!(f, "I6: {}", self.i6)
+    let capacity = self.byte_size.capacity(5) as i64;
+    let register = self.register(target);
+    let mut result = self.value(&register) + value;
+
+    if result.abs() >= capacity {
+      self.set_overflow();
+
+      result %= capacity;
+    }
+
+    self.set_register(target, self.signed(result, register.read_sign()))
+  }
+
+  /// Replaces the field of the memory cell with the same number of bytes
+  /// taken from the right of the value, the sign is replaced only if the
+  /// field includes it
+  fn store(&mut self, address: i64, value: &Word, field: FieldSpec) {
+    self.cell_mut(address).store(value, field);
+  }
+
+  /// Reads the field of the memory cell at the effective address of the
+  /// instruction
+  fn operand(&mut self, instruction: &Instruction) -> Result<Word, MixError> {
+    let address = self.address(instruction)?;
+
+    Ok(Self::field(self.cell(address), instruction.field_spec()?))
+  }
+
+  /// Shifts the field of the word to the right, the sign is taken into
+  /// account only if the field includes it
+  fn field(word: &Word, field: FieldSpec) -> Word {
+    let FieldSpec { left, right } = field;
+    let mut result = Word::from_bytes([0; 5], left > 0 || word.read_sign());
+
+    for index in left.max(1)..=right {
+      result.set_byte(
+        (5 - (right - index)) as usize,
+        word.get_byte(index as usize),
+      );
+    }
+
+    result
+  }
+
+  /// Reads bytes 1-5 of the word as a magnitude in the byte size of the
+  /// machine
+  fn magnitude(&self, word: &Word) -> u64 {
+    self.byte_size.magnitude(word)
+  }
+
+  /// Reads the word as a signed number
+  fn value(&self, word: &Word) -> i64 {
+    self.byte_size.value(word)
+  }
+
+  /// Makes a word of the signed number, a zero gets the sign given
+  fn signed(&self, value: i64, zero_sign: bool) -> Word {
+    let sign = if value == 0 { zero_sign } else { value > 0 };
+
+    self.byte_size.word(value.unsigned_abs(), sign)
+  }
+
+  /// Jumps to the address of the instruction, saving the address of the next
+  /// instruction in rJ
+  fn jump(&mut self, instruction: &Instruction) -> Result<(), MixError> {
+    let address = self.address(instruction)?;
+
+    self.j = Register::from(self.signed(self.pc, true));
+    self.pc = address;
+
+    Ok(())
+  }
+
+  /// Returns the I/O unit by its number
+  pub fn device(&self, unit: u32) -> Result<&dyn Device, MixError> {
+    self
+      .devices
+      .get(unit as usize)
+      .and_then(|device| device.as_deref())
+      .ok_or(MixError::InvalidUnit(unit))
+  }
+
+  /// Returns the I/O unit by its number for the operation to change it
+  pub fn device_mut(&mut self, unit: u32) -> Result<&mut dyn Device, MixError> {
+    match self.devices.get_mut(unit as usize) {
+      Some(Some(device)) => Ok(device.as_mut()),
+      _ => Err(MixError::InvalidUnit(unit)),
+    }
+  }
+
+  /// Attaches the device as the unit, so that the program reaches it with
+  /// IN, OUT and IOC, returns the device the unit had before
+  pub fn attach_device(
+    &mut self,
+    unit: u32,
+    device: Box<dyn Device>,
+  ) -> Result<Option<Box<dyn Device>>, MixError> {
+    let slot = self
+      .devices
+      .get_mut(unit as usize)
+      .ok_or(MixError::InvalidUnit(unit))?;
+
+    Ok(slot.replace(device))
+  }
+
+  /// Detaches the unit, so that it does not exist for the program, returns
+  /// the device the unit had
+  pub fn detach_device(&mut self, unit: u32) -> Result<Box<dyn Device>, MixError> {
+    self
+      .devices
+      .get_mut(unit as usize)
+      .and_then(Option::take)
+      .ok_or(MixError::InvalidUnit(unit))
+  }
+
+  /// Computes the memory locations of a block of words starting at the
+  /// effective address of the instruction
+  fn block(&mut self, instruction: &Instruction, size: usize) -> Result<Range<usize>, MixError> {
+    let start = self.address(instruction)?;
+    let end = start + size as i64;
+
+    // The units transfer blocks to and from the main memory only
+    if start < 0 {
+      return Err(MixError::InvalidAddress(start));
+    }
+
+    if end > self.memory.len() as i64 {
+      return Err(MixError::InvalidAddress(end - 1));
+    }
+
+    Ok(start as usize..end as usize)
+  }
+
+  /// Returns the index register rI1-rI6 by its number
+  pub fn index(&self, i: u8) -> Result<&Register, MixError> {
+    match i {
+      1..=6 => Ok(&self.ri[i as usize - 1]),
+      _ => Err(DecodeError::InvalidIndex(i as u32).into()),
+    }
+  }
+
+  /// Returns the index register rI1-rI6 by its number for the operation to
+  /// change it
+  pub fn index_mut(&mut self, i: u8) -> Result<&mut Register, MixError> {
+    match i {
+      1..=6 => Ok(&mut self.ri[i as usize - 1]),
+      _ => Err(DecodeError::InvalidIndex(i as u32).into()),
+    }
+  }
+
+  /// Computes the effective address M of the instruction, modified by the
+  /// index register
+  fn effective_address(&self, instruction: &Instruction) -> Result<i64, MixError> {
+    let mut address = instruction.address as i64;
+
+    if !instruction.sign {
+      address = -address;
+    }
+
+    if instruction.index != 0 {
+      let register = self.index(instruction.index as u8)?;
+
+      address += self.value(&Word::from(*register));
+    }
+
+    Ok(address)
+  }
+
+  /// Computes the effective address of the instruction as a memory location
+  fn address(&mut self, instruction: &Instruction) -> Result<i64, MixError> {
+    let address = self.effective_address(instruction)?;
+
+    self.locate(address)
+  }
+
+  /// Checks that the address refers to a memory location, the control memory
+  /// is reachable in the control state only
+  ///
+  /// An address out of memory is handled by the address policy, it wraps
+  /// around the main memory unless the policy is to fault.
+  fn locate(&mut self, address: i64) -> Result<i64, MixError> {
+    let control = match self.state {
+      State::Normal => 0,
+      State::Control => self.control.len() as i64,
+    };
+
+    if (-control..self.memory.len() as i64).contains(&address) {
+      return Ok(address);
+    }
+
+    let wrapped = address.rem_euclid(self.memory.len() as i64);
+
+    match self.address_policy {
+      AddressPolicy::Fault => Err(MixError::InvalidAddress(address)),
+      AddressPolicy::Wrap => Ok(wrapped),
+      AddressPolicy::Warn => {
+        self.warnings.push(Warning::AddressWrapped {
+          location: self.pc - 1,
+          address,
+        });
+
+        Ok(wrapped)
+      }
+    }
+  }
+
+  /// Returns the memory cell at the location, a negative location refers to
+  /// the control memory
+  pub(crate) fn cell(&self, location: i64) -> &Word {
+    match location {
+      0.. => &self.memory[location as usize],
+      _ => &self.control[(-location - 1) as usize],
+    }
+  }
+
+  /// Returns the memory cell at the location for the operation to change it
+  pub(crate) fn cell_mut(&mut self, location: i64) -> &mut Word {
+    match location {
+      0.. => &mut self.memory[location as usize],
+      _ => &mut self.control[(-location - 1) as usize],
+    }
+  }
+
+  /// Compares the field of the value with the same field of the memory cell
+  fn compare(&mut self, value: &Word, instruction: &Instruction) -> Result<(), MixError> {
+    let left = Self::field(value, instruction.field_spec()?);
+    let right = self.operand(instruction)?;
+
+    self.comparison = left.mix_cmp(&right);
+
+    Ok(())
+  }
+}
+
+impl Default for Computer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl fmt::Display for Computer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "Memory:")?;
+    for (i, cell) in self.memory.iter().enumerate().rev() {
+      write!(f, "{:04X}: ", i)?;
+      writeln!(f, "{}", cell)?;
+    }
+
+    writeln!(f, "PC: {}", self.pc)?;
+    writeln!(f, "Time: {}", self.time)?;
+    writeln!(f, "State: {:?}", self.state)?;
+    writeln!(f, "Overflow: {}", self.overflow)?;
+    writeln!(f, "Comparison: {:?}", self.comparison)?;
+    writeln!(f, "A: {}", self.a)?;
+    writeln!(f, "X: {}", self.x)?;
+    write!(f, "J: {}", self.j)?;
+
+    for (index, register) in self.ri.iter().enumerate() {
+      write!(f, "\nI{}: {}", index + 1, register)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+  use rstest_reuse::{self, *};
+
+  use super::*;
+  use crate::error::{DecodeError, DeviceError};
+
+  fn hlt() -> Instruction {
+    Instruction::new(true, 0, 0, 0, Operation::Hlt)
+  }
+
+  #[test]
+  fn test_load() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(Instruction::new(
+      true,
+      1001,
+      0,
+      5,
+      Operation::Cmp(Target::I1),
+    ));
+
+    assert_eq!(computer.load(&program), 0..2);
+    assert_eq!(computer.memory[0], Word::from(program.instructions[0]));
+    assert_eq!(computer.memory[1], Word::from(program.instructions[1]));
+  }
+
+  #[rstest]
+  #[case(0, 3, 0..3)]
+  #[case(1000, 2, 1000..1002)]
+  #[case(3999, 1, 3999..4000)]
+  #[case(100, 0, 100..100)]
+  fn test_load_image(#[case] origin: usize, #[case] length: usize, #[case] expected: Range<usize>) {
+    let mut computer = Computer::new();
+    let words: Vec<Word> = (1..=length as u32)
+      .map(|n| Word::new(n, Some(true)))
+      .collect();
+
+    assert_eq!(computer.load_image(origin, &words), expected);
+    assert_eq!(&computer.memory[expected], &words[..]);
+  }
+
+  #[test]
+  #[should_panic(expected = "Image does not fit in memory")]
+  fn test_load_image_out_of_memory() {
+    Computer::new().load_image(3999, &[Word::default(); 2]);
+  }
+
+  #[test]
+  fn test_load_at() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 2000, 0, 5, Operation::Ld(Target::A)));
+    program.add(hlt());
+    program.add_segment(2000, vec![Word::new(42, Some(false))]);
+
+    assert_eq!(computer.load_at(&program, 3000), 3000..3002);
+    assert_eq!(computer.memory[3000], Word::from(program.instructions[0]));
+    assert_eq!(computer.memory[0], Word::default());
+    assert_eq!(computer.memory[2000], Word::new(42, Some(false)));
+  }
+
+  #[test]
+  fn test_execute_from_entry() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 1, 0, 2, Operation::Ent(Target::A)));
+    program.add(Instruction::new(true, 2, 0, 2, Operation::Ent(Target::A)));
+    program.add(hlt());
+    program.entry = 1;
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(2, Some(true)));
+  }
+
+  #[test]
+  fn test_execute_keeps_program() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.memory[1000] = Word::new(42, Some(true));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    computer.a = Word::default();
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(program.instructions.len(), 2);
+    assert_eq!(computer.a, Word::new(42, Some(true)));
+  }
+
+  #[rstest]
+  #[case(Operation::Jmp, 1)]
+  #[case(Operation::Jsj, 0)]
+  fn test_jmp(#[case] operation: Operation, #[case] expected: u16) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.memory[1000] = Word::new(42, Some(true));
+
+    program.add(Instruction::new(true, 2, 0, 0, operation));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::default());
+    assert_eq!(computer.j.read_data(), expected);
+    assert_eq!(computer.pc, 4);
+  }
+
+  #[rstest]
+  #[case(Operation::Jov, true, 1)]
+  #[case(Operation::Jov, false, 0)]
+  #[case(Operation::Jnov, true, 0)]
+  #[case(Operation::Jnov, false, 1)]
+  fn test_jov_jnov(#[case] operation: Operation, #[case] overflow: bool, #[case] expected: u16) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    if overflow {
+      computer.set_overflow();
+    }
+
+    program.add(Instruction::new(true, 2, 0, 0, operation));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert!(!computer.overflow());
+    assert_eq!(computer.j.read_data(), expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Operation::Jl, Compare::Less, true)]
+  #[case(Operation::Jl, Compare::Equal, false)]
+  #[case(Operation::Jl, Compare::Greater, false)]
+  #[case(Operation::Je, Compare::Less, false)]
+  #[case(Operation::Je, Compare::Equal, true)]
+  #[case(Operation::Je, Compare::Greater, false)]
+  #[case(Operation::Jg, Compare::Less, false)]
+  #[case(Operation::Jg, Compare::Equal, false)]
+  #[case(Operation::Jg, Compare::Greater, true)]
+  #[case(Operation::Jge, Compare::Less, false)]
+  #[case(Operation::Jge, Compare::Equal, true)]
+  #[case(Operation::Jge, Compare::Greater, true)]
+  #[case(Operation::Jne, Compare::Less, true)]
+  #[case(Operation::Jne, Compare::Equal, false)]
+  #[case(Operation::Jne, Compare::Greater, true)]
+  #[case(Operation::Jle, Compare::Less, true)]
+  #[case(Operation::Jle, Compare::Equal, true)]
+  #[case(Operation::Jle, Compare::Greater, false)]
+  #[case(Operation::Jl, Compare::None, false)]
+  #[case(Operation::Jne, Compare::None, false)]
+  fn test_comparison_jumps(
+    #[case] operation: Operation,
+    #[case] comparison: Compare,
+    #[case] expected: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.comparison = comparison;
+    computer.memory[1000] = Word::new(42, Some(true));
+
+    program.add(Instruction::new(true, 2, 0, 0, operation));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a == Word::default(), expected);
+    assert_eq!(computer.j.read_data(), if expected { 1 } else { 0 });
+  }
+
+  #[template]
+  #[rstest]
+  #[case(Condition::Negative, 5, false, true)]
+  #[case(Condition::Negative, 5, true, false)]
+  #[case(Condition::Negative, 0, false, false)]
+  #[case(Condition::Zero, 0, true, true)]
+  #[case(Condition::Zero, 0, false, true)]
+  #[case(Condition::Zero, 5, true, false)]
+  #[case(Condition::Positive, 5, true, true)]
+  #[case(Condition::Positive, 5, false, false)]
+  #[case(Condition::Positive, 0, true, false)]
+  #[case(Condition::NonNegative, 5, true, true)]
+  #[case(Condition::NonNegative, 0, false, true)]
+  #[case(Condition::NonNegative, 5, false, false)]
+  #[case(Condition::NonZero, 5, false, true)]
+  #[case(Condition::NonZero, 5, true, true)]
+  #[case(Condition::NonZero, 0, true, false)]
+  #[case(Condition::NonZero, 0, false, false)]
+  #[case(Condition::NonPositive, 5, false, true)]
+  #[case(Condition::NonPositive, 0, false, true)]
+  #[case(Condition::NonPositive, 0, true, true)]
+  #[case(Condition::NonPositive, 5, true, false)]
+  fn register_jump_cases(
+    #[case] condition: Condition,
+    #[case] number: u32,
+    #[case] sign: bool,
+    #[case] expected: bool,
+  ) {
+  }
+
+  /// Runs a jump over a load of rA, so that the jump can be observed
+  fn run_jump(computer: &mut Computer, operation: Operation, modifier: u32) -> bool {
+    let mut program = Program::new();
+    let a = computer.a;
+
+    computer.memory[1000] = Word::new(42, Some(true));
+
+    program.add(Instruction::new(true, 2, 0, modifier, operation));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    computer.a == a
+  }
+
+  fn memory_unit(computer: &Computer, unit: u32) -> &MemoryUnit {
+    computer.device(unit).unwrap().downcast_ref().unwrap()
+  }
+
+  fn memory_unit_mut(computer: &mut Computer, unit: u32) -> &mut MemoryUnit {
+    computer.device_mut(unit).unwrap().downcast_mut().unwrap()
+  }
+
+  fn set_index_register(computer: &mut Computer, index: u32, value: Register) {
+    *computer.index_mut(index as u8).unwrap() = value;
+  }
+
+  #[apply(register_jump_cases)]
+  fn test_ja(condition: Condition, number: u32, sign: bool, expected: bool) {
+    let mut computer = Computer::new();
+
+    computer.a = Word::new(number, Some(sign));
+
+    let operation = Operation::Jump(Target::A, condition);
+
+    assert_eq!(run_jump(&mut computer, operation, 0), expected);
+    assert_eq!(computer.j.read_data(), if expected { 1 } else { 0 });
+  }
+
+  #[apply(register_jump_cases)]
+  fn test_jx(condition: Condition, number: u32, sign: bool, expected: bool) {
+    let mut computer = Computer::new();
+
+    computer.x = Word::new(number, Some(sign));
+
+    let operation = Operation::Jump(Target::X, condition);
+
+    assert_eq!(run_jump(&mut computer, operation, 0), expected);
+    assert_eq!(computer.j.read_data(), if expected { 1 } else { 0 });
+  }
+
+  #[apply(register_jump_cases)]
+  fn test_ji(
+    condition: Condition,
+    number: u32,
+    sign: bool,
+    expected: bool,
+    #[values(Target::I1, Target::I2, Target::I3, Target::I4, Target::I5, Target::I6)]
+    target: Target,
+  ) {
+    let mut computer = Computer::new();
+    let operation = Operation::Jump(target, condition);
+
+    set_index_register(
+      &mut computer,
+      target.offset(),
+      Register::new(number as u16, Some(sign)),
+    );
+
+    assert_eq!(run_jump(&mut computer, operation, 0), expected);
+    assert_eq!(computer.j.read_data(), if expected { 1 } else { 0 });
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Operation::Sla, 0, 0b000001_000010_000011_000100_000101, 0b000001_000010_000011_000100_000101)]
+  #[case(Operation::Sla, 1, 0b000001_000010_000011_000100_000101, 0b000010_000011_000100_000101_000000)]
+  #[case(Operation::Sla, 2, 0b000001_000010_000011_000100_000101, 0b000011_000100_000101_000000_000000)]
+  #[case(Operation::Sla, 4, 0b000001_000010_000011_000100_000101, 0b000101_000000_000000_000000_000000)]
+  #[case(Operation::Sla, 5, 0b000001_000010_000011_000100_000101, 0)]
+  #[case(Operation::Sla, 100, 0b000001_000010_000011_000100_000101, 0)]
+  #[case(Operation::Sra, 1, 0b000001_000010_000011_000100_000101, 0b000000_000001_000010_000011_000100)]
+  #[case(Operation::Sra, 3, 0b000001_000010_000011_000100_000101, 0b000000_000000_000000_000001_000010)]
+  #[case(Operation::Sra, 5, 0b000001_000010_000011_000100_000101, 0)]
+  fn test_sla_sra(
+    #[case] operation: Operation,
+    #[case] count: u32,
+    #[case] number: u32,
+    #[case] expected: u32,
+    #[values(true, false)] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(number, Some(sign));
+
+    program.add(Instruction::new(true, count, 0, 0, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(expected, Some(sign)));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Operation::Slax, 0, 0b000001_000010_000011_000100_000101, 0b000110_000111_001000_001001_001010)]
+  #[case(Operation::Slax, 1, 0b000010_000011_000100_000101_000110, 0b000111_001000_001001_001010_000000)]
+  #[case(Operation::Slax, 6, 0b000111_001000_001001_001010_000000, 0b000000_000000_000000_000000_000000)]
+  #[case(Operation::Slax, 10, 0, 0)]
+  #[case(Operation::Slax, 1000, 0, 0)]
+  #[case(Operation::Srax, 1, 0b000000_000001_000010_000011_000100, 0b000101_000110_000111_001000_001001)]
+  #[case(Operation::Srax, 7, 0, 0b000000_000000_000001_000010_000011)]
+  #[case(Operation::Srax, 10, 0, 0)]
+  #[case(Operation::Slc, 0, 0b000001_000010_000011_000100_000101, 0b000110_000111_001000_001001_001010)]
+  #[case(Operation::Slc, 1, 0b000010_000011_000100_000101_000110, 0b000111_001000_001001_001010_000001)]
+  #[case(Operation::Slc, 6, 0b000111_001000_001001_001010_000001, 0b000010_000011_000100_000101_000110)]
+  #[case(Operation::Slc, 10, 0b000001_000010_000011_000100_000101, 0b000110_000111_001000_001001_001010)]
+  #[case(Operation::Slc, 13, 0b000100_000101_000110_000111_001000, 0b001001_001010_000001_000010_000011)]
+  #[case(Operation::Src, 1, 0b001010_000001_000010_000011_000100, 0b000101_000110_000111_001000_001001)]
+  #[case(Operation::Src, 4, 0b000111_001000_001001_001010_000001, 0b000010_000011_000100_000101_000110)]
+  #[case(Operation::Src, 20, 0b000001_000010_000011_000100_000101, 0b000110_000111_001000_001001_001010)]
+  fn test_slax_srax_slc_src(
+    #[case] operation: Operation,
+    #[case] count: u32,
+    #[case] a: u32,
+    #[case] x: u32,
+    #[values(true, false)] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(0b000001_000010_000011_000100_000101, Some(sign));
+    computer.x = Word::new(0b000110_000111_001000_001001_001010, Some(!sign));
+
+    program.add(Instruction::new(true, count, 0, 0, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(a, Some(sign)));
+    assert_eq!(computer.x, Word::new(x, Some(!sign)));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Operation::Slb, 0, 0b000001_000010_000011_000100_000101, 0b000110_000111_001000_001001_001010)]
+  #[case(Operation::Slb, 1, 0b000010_000100_000110_001000_001010, 0b001100_001110_010000_010010_010100)]
+  #[case(Operation::Slb, 6, 0b000010_000011_000100_000101_000110, 0b000111_001000_001001_001010_000000)]
+  #[case(Operation::Slb, 59, 0, 0)]
+  #[case(Operation::Slb, 1000, 0, 0)]
+  #[case(Operation::Srb, 1, 0b000000_100001_000001_100010_000010, 0b100011_000011_100100_000100_100101)]
+  #[case(Operation::Srb, 33, 0, 0b000000_001000_010000_011000_100000)]
+  #[case(Operation::Srb, 60, 0, 0)]
+  fn test_slb_srb(
+    #[case] operation: Operation,
+    #[case] count: u32,
+    #[case] a: u32,
+    #[case] x: u32,
+    #[values(true, false)] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.binary = true;
+    computer.a = Word::new(0b000001_000010_000011_000100_000101, Some(sign));
+    computer.x = Word::new(0b000110_000111_001000_001001_001010, Some(!sign));
+
+    program.add(Instruction::new(true, count, 0, 0, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(a, Some(sign)));
+    assert_eq!(computer.x, Word::new(x, Some(!sign)));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Operation::And, 0b000000_000000_000000_000000_001000)]
+  #[case(Operation::Or, 0b111111_111111_000000_000000_111110)]
+  #[case(Operation::Xor, 0b111111_111111_000000_000000_110110)]
+  fn test_and_or_xor(
+    #[case] operation: Operation,
+    #[case] expected: u32,
+    #[values(true, false)] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.binary = true;
+    computer.a = Word::new(0b111111_000000_000000_000000_101010, Some(sign));
+    computer.memory[1000] = Word::new(0b000000_111111_000000_000000_011100, Some(!sign));
+
+    program.add(Instruction::new(true, 1000, 0, 0, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(expected, Some(sign)));
+  }
+
+  #[rstest]
+  #[case(Condition::Even, 4, true)]
+  #[case(Condition::Even, 0, true)]
+  #[case(Condition::Even, 7, false)]
+  #[case(Condition::Odd, 7, true)]
+  #[case(Condition::Odd, 4, false)]
+  fn test_jae_jao_jxe_jxo(
+    #[case] condition: Condition,
+    #[case] number: u32,
+    #[case] expected: bool,
+    #[values(Target::A, Target::X)] target: Target,
+    #[values(true, false)] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+
+    computer.binary = true;
+    computer
+      .set_register(target, Word::new(number, Some(sign)))
+      .unwrap();
+
+    assert_eq!(
+      run_jump(&mut computer, Operation::Jump(target, condition), 0),
+      expected
+    );
+  }
+
+  #[rstest]
+  #[case(Operation::And)]
+  #[case(Operation::Slb)]
+  #[case(Operation::Jump(Target::A, Condition::Odd))]
+  fn test_binary_without_extension(#[case] operation: Operation) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 1000, 0, 0, operation));
+    program.add(hlt());
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::MissingAttachment(operation))
+    );
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0b000000_000000_011111_100000_100111, 0b100101_111001_101111_011110_011110, 12977700, false)]
+  #[case(0b011110_011110_011110_011110_011110, 0b011110_011110_011110_011110_011110, 0, false)]
+  #[case(0b000000_001010_010100_101000_110010, 0b111100_011110_011110_011110_100111, 9, false)]
+  #[case(0, 0b100011_100111_100111_100111_100111, 59999, false)]
+  #[case(0b011111_011110_011110_011110_011110, 0b011110_011110_011110_011110_011110, 1000000000, false)]
+  #[case(0b100111_100111_100111_100111_100111, 0b100111_100111_100111_100111_100111, 336323583, true)]
+  fn test_num(
+    #[case] a: u32,
+    #[case] x: u32,
+    #[case] expected: u32,
+    #[case] overflow: bool,
+    #[values(true, false)] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(a, Some(sign));
+    computer.x = Word::new(x, Some(true));
+
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Num));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(expected, Some(sign)));
+    assert_eq!(computer.x, Word::new(x, Some(true)));
+    assert_eq!(computer.overflow(), overflow);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(12977699, 0b011110_011110_011111_100000_100111, 0b100101_100101_100100_100111_100111)]
+  #[case(0, 0b011110_011110_011110_011110_011110, 0b011110_011110_011110_011110_011110)]
+  #[case(9, 0b011110_011110_011110_011110_011110, 0b011110_011110_011110_011110_100111)]
+  #[case(1073741823, 0b011111_011110_100101_100001_100101, 0b100010_011111_100110_100000_100001)]
+  fn test_char(
+    #[case] number: u32,
+    #[case] a: u32,
+    #[case] x: u32,
+    #[values(true, false)] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(number, Some(sign));
+    computer.x = Word::new(0, Some(!sign));
+
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Char));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(a, Some(sign)));
+    assert_eq!(computer.x, Word::new(x, Some(!sign)));
+  }
+
+  #[test]
+  fn test_num_char_round_trip() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(12977699, Some(false));
+
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Char));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Num));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(12977699, Some(false)));
+    assert!(!computer.overflow());
+  }
+
+  #[test]
+  fn test_shift_indexed() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(0b000001_000010_000011_000100_000101, Some(true));
+    computer.ri[1] = Register::new(3, Some(false));
+
+    program.add(Instruction::new(true, 5, 2, 0, Operation::Sla));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(
+      computer.a,
+      Word::new(0b000011_000100_000101_000000_000000, Some(true))
+    );
+  }
+
+  #[rstest]
+  #[case(0, 1000, 100)]
+  #[case(16, 3984, 16)]
+  #[case(19, 2000, 14)]
+  #[case(20, 100, 14)]
+  fn test_in(#[case] unit: u32, #[case] address: u32, #[case] size: usize) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    let data: Vec<Word> = (1..=2 * size as u32)
+      .map(|n| Word::new(n, Some(n % 2 == 0)))
+      .collect();
+    memory_unit_mut(&mut computer, unit).data = data.clone();
+
+    let start = address as usize;
+    program.add(Instruction::new(true, address, 0, unit, Operation::In));
+    program.add(Instruction::new(true, address, 0, unit, Operation::In));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.memory[start..start + size], data[size..]);
+    assert_eq!(memory_unit(&computer, unit).position, 2 * size);
+  }
+
+  #[rstest]
+  #[case(8)]
+  #[case(15)]
+  fn test_disk_select(#[case] unit: u32) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+    let block = |number: u32| vec![Word::new(number, Some(true)); 100];
+
+    computer.memory[1000..1100].copy_from_slice(&block(1));
+    computer.memory[1100..1200].copy_from_slice(&block(2));
+
+    // Writes block 2 before block 0, then reads block 2 back
+    program.add(Instruction::new(true, 2, 0, 0, Operation::Ent(Target::X)));
+    program.add(Instruction::new(true, 1100, 0, unit, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Ent(Target::X)));
+    program.add(Instruction::new(true, 1000, 0, unit, Operation::Out));
+    program.add(Instruction::new(true, 2, 0, 0, Operation::Ent(Target::X)));
+    program.add(Instruction::new(true, 2000, 0, unit, Operation::In));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    let data = &memory_unit(&computer, unit).data;
+
+    assert_eq!(data[..100], block(1));
+    assert_eq!(data[100..200], vec![Word::default(); 100]);
+    assert_eq!(data[200..300], block(2));
+    assert_eq!(computer.memory[2000..2100], block(2));
+    assert_eq!(memory_unit(&computer, unit).position, 300);
+  }
+
+  #[test]
+  fn test_in_indexed() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.ri[2] = Register::new(10, Some(true));
+    memory_unit_mut(&mut computer, 16).data = vec![Word::new(7, Some(true)); 16];
+
+    program.add(Instruction::new(true, 1000, 3, 16, Operation::In));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.memory[1009], Word::default());
+    assert_eq!(computer.memory[1010..1026], [Word::new(7, Some(true)); 16]);
+    assert_eq!(computer.memory[1026], Word::default());
+  }
+
+  #[rstest]
+  #[case(1000, 17, MixError::UnsupportedOperation(17))]
+  #[case(1000, 18, MixError::UnsupportedOperation(18))]
+  #[case(1000, 21, MixError::InvalidUnit(21))]
+  #[case(3990, 16, MixError::InvalidAddress(4005))]
+  #[case(3901, 0, MixError::InvalidAddress(4000))]
+  fn test_in_error(#[case] address: u32, #[case] unit: u32, #[case] expected: MixError) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, address, 0, unit, Operation::In));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Err(expected));
+  }
+
+  #[rstest]
+  #[case(0, 1000, 100)]
+  #[case(17, 2000, 16)]
+  #[case(18, 100, 24)]
+  #[case(19, 3972, 14)]
+  #[case(20, 500, 14)]
+  fn test_out(#[case] unit: u32, #[case] address: u32, #[case] size: usize) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    let start = address as usize;
+    for (offset, word) in computer.memory[start..start + 2 * size]
+      .iter_mut()
+      .enumerate()
+    {
+      *word = Word::new(offset as u32, Some(offset % 2 == 0));
+    }
+
+    program.add(Instruction::new(true, address, 0, unit, Operation::Out));
+    program.add(Instruction::new(
+      true,
+      address + size as u32,
+      0,
+      unit,
+      Operation::Out,
+    ));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(
+      memory_unit(&computer, unit).data,
+      computer.memory[start..start + 2 * size]
+    );
+  }
+
+  #[rstest]
+  #[case(1000, 16, MixError::UnsupportedOperation(16))]
+  #[case(1000, 21, MixError::InvalidUnit(21))]
+  #[case(3990, 18, MixError::InvalidAddress(4013))]
+  fn test_out_error(#[case] address: u32, #[case] unit: u32, #[case] expected: MixError) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, address, 0, unit, Operation::Out));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Err(expected));
+    assert!((0..device::UNITS as u32).all(|unit| memory_unit(&computer, unit).data.is_empty()));
+  }
+
+  #[rstest]
+  #[case(3, 0, 0, 500, 0)]
+  #[case(3, 2, 0, 500, 700)]
+  #[case(3, -2, 0, 500, 300)]
+  #[case(3, -10, 0, 500, 0)]
+  #[case(9, 0, 7, 500, 700)]
+  #[case(18, 0, 0, 48, 1440)]
+  #[case(20, 0, 0, 28, 0)]
+  fn test_ioc(
+    #[case] unit: u32,
+    #[case] operation: i64,
+    #[case] x: u32,
+    #[case] position: usize,
+    #[case] expected: usize,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.x = Word::new(x, Some(true));
+    memory_unit_mut(&mut computer, unit).position = position;
+
+    program.add(Instruction::new(
+      operation >= 0,
+      operation.unsigned_abs() as u32,
+      0,
+      unit,
+      Operation::Ioc,
+    ));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(memory_unit(&computer, unit).position, expected);
+  }
+
+  #[rstest]
+  #[case(Operation::Jbus, true, true)]
+  #[case(Operation::Jbus, false, false)]
+  #[case(Operation::Jred, true, false)]
+  #[case(Operation::Jred, false, true)]
+  fn test_jbus_jred(
+    #[case] operation: Operation,
+    #[case] busy: bool,
+    #[case] expected: bool,
+    #[values(0, 16, 18, 20)] unit: u32,
+  ) {
+    let mut computer = Computer::new();
+
+    memory_unit_mut(&mut computer, unit).busy = busy;
+
+    assert_eq!(run_jump(&mut computer, operation, unit), expected);
+    assert_eq!(computer.j.read_data(), if expected { 1 } else { 0 });
+  }
+
+  #[test]
+  fn test_io_wait_loop() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 1, 0, 18, Operation::Jbus));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(memory_unit(&computer, 18).data.len(), 24);
+  }
+
+  #[test]
+  fn test_io_latency_wait_loop() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    memory_unit_mut(&mut computer, 18).latency = 100;
+
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 1, 0, 18, Operation::Jbus));
+    program.add(hlt());
+
+    // JBUS spins until the time the printer is done, then HLT takes 10
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.time, 101 + 10);
+  }
+
+  #[rstest]
+  #[case(Operation::Out, 18)]
+  #[case(Operation::In, 16)]
+  #[case(Operation::Ioc, 0)]
+  fn test_io_latency_waits_for_unit(#[case] operation: Operation, #[case] unit: u32) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    memory_unit_mut(&mut computer, unit).latency = 100;
+
+    // The second operation waits for the unit to be done with the first,
+    // then JBUS spins until it is done with the second
+    program.add(Instruction::new(true, 1000, 0, unit, operation));
+    program.add(Instruction::new(true, 1000, 0, unit, operation));
+    program.add(Instruction::new(true, 2, 0, unit, Operation::Jbus));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.time, 201 + 10);
+  }
+
+  #[rstest]
+  fn test_jbus_jred_invalid_unit(#[values(Operation::Jbus, Operation::Jred)] operation: Operation) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 0, 0, 21, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Err(MixError::InvalidUnit(21)));
+  }
+
+  /// Unit that reads blocks of its count of reads and keeps the last block
+  /// written
+  struct Counter {
+    reads: u32,
+    last: Vec<Word>,
+  }
+
+  impl Device for Counter {
+    fn block_size(&self) -> usize {
+      2
+    }
+
+    fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+      self.reads += 1;
+
+      Ok(vec![Word::new(self.reads, Some(true)); 2])
+    }
+
+    fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+      self.last = block.to_vec();
+
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn test_custom_device() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    let previous = computer
+      .attach_device(
+        5,
+        Box::new(Counter {
+          reads: 0,
+          last: Vec::new(),
+        }),
+      )
+      .unwrap();
+
+    assert!(previous.unwrap().downcast_ref::<MemoryUnit>().is_some());
+
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::In));
+    program.add(Instruction::new(true, 1002, 0, 5, Operation::In));
+    program.add(Instruction::new(true, 1001, 0, 5, Operation::Out));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.memory[1000..1002], [Word::new(1, Some(true)); 2]);
+    assert_eq!(computer.memory[1002..1004], [Word::new(2, Some(true)); 2]);
+
+    let counter: &Counter = computer.device(5).unwrap().downcast_ref().unwrap();
+
+    assert_eq!(counter.reads, 2);
+    assert_eq!(
+      counter.last,
+      [Word::new(1, Some(true)), Word::new(2, Some(true))]
+    );
+  }
+
+  #[rstest]
+  fn test_detached_unit(
+    #[values(Operation::In, Operation::Out, Operation::Ioc, Operation::Jbus)] operation: Operation,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.detach_device(16).unwrap();
+
+    program.add(Instruction::new(true, 1000, 0, 16, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Err(MixError::InvalidUnit(16)));
+  }
+
+  #[test]
+  fn test_go() {
+    let mut computer = Computer::new();
+    let reader = memory_unit_mut(&mut computer, 16);
+    let mut card = vec![Word::default(); 16];
+
+    card[0] =
+      Instruction::new(true, 42, 0, 2, Operation::Ent(Target::A)).encode(ByteSize::default());
+    card[1] = hlt().encode(ByteSize::default());
+    reader.data = card;
+    reader.latency = 50;
+
+    assert_eq!(computer.go(), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(42, Some(true)));
+    assert_eq!(computer.time, 50 + 1 + 10);
+  }
+
+  #[test]
+  fn test_go_without_reader() {
+    let mut computer = Computer::new();
+
+    computer.devices[16] = None;
+
+    assert_eq!(computer.go(), Err(MixError::InvalidUnit(16)));
+  }
+
+  #[test]
+  fn test_attach_invalid_unit() {
+    let mut computer = Computer::new();
+    let device = MemoryUnit::new(DeviceKind::Tape);
+
+    assert!(matches!(
+      computer.attach_device(21, Box::new(device)),
+      Err(MixError::InvalidUnit(21))
+    ));
+
+    computer.detach_device(3).unwrap();
+
+    assert!(matches!(
+      computer.detach_device(3),
+      Err(MixError::InvalidUnit(3))
+    ));
+  }
+
+  #[test]
+  fn test_ioc_invalid_unit() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 0, 0, 21, Operation::Ioc));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Err(MixError::InvalidUnit(21)));
+  }
+
+  #[rstest]
+  #[case(9, 1)]
+  #[case(16, 0)]
+  #[case(17, 0)]
+  #[case(18, 1)]
+  #[case(19, 0)]
+  #[case(20, -1)]
+  fn test_ioc_unsupported(#[case] unit: u32, #[case] operation: i64) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    memory_unit_mut(&mut computer, unit).position = 28;
+
+    program.add(Instruction::new(
+      operation >= 0,
+      operation.unsigned_abs() as u32,
+      0,
+      unit,
+      Operation::Ioc,
+    ));
+    program.add(hlt());
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::UnsupportedOperation(unit))
+    );
+    assert_eq!(memory_unit(&computer, unit).position, 28);
+  }
+
+  #[test]
+  fn test_jmp_indexed() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.ri[0] = Register::new(3, Some(true));
+    computer.memory[1000] = Word::new(42, Some(true));
+
+    program.add(Instruction::new(true, 0, 1, 0, Operation::Jmp));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(42, Some(true)));
+    assert_eq!(computer.j.read_data(), 1);
+  }
+
+  #[test]
+  fn test_hlt() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.memory[1000] = Word::new(42, Some(true));
+
+    program.add(hlt());
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.pc, 1);
+    assert_eq!(computer.a, Word::default());
+
+    assert_eq!(computer.run(1), Ok(HaltReason::EndOfMemory));
+    assert_eq!(computer.pc, 4000);
+    assert_eq!(computer.a, Word::new(42, Some(true)));
+  }
+
+  #[test]
+  fn test_end_of_memory() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 3999, 0, 0, Operation::Jmp));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::EndOfMemory));
+    assert_eq!(computer.pc, 4000);
+  }
+
+  #[rstest]
+  #[case(3, Ok(HaltReason::Halted))]
+  #[case(2, Ok(HaltReason::LimitExceeded))]
+  #[case(0, Ok(HaltReason::LimitExceeded))]
+  fn test_execute_with_limit(
+    #[case] max_instructions: u64,
+    #[case] expected: Result<HaltReason, MixError>,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 1, 0, 0, Operation::Inc(Target::A)));
+    program.add(Instruction::new(true, 1, 0, 0, Operation::Inc(Target::A)));
+    program.add(hlt());
+
+    assert_eq!(
+      computer.execute_with_limit(&program, max_instructions),
+      expected
+    );
+    assert_eq!(computer.pc, max_instructions.min(3) as i64);
+  }
+
+  #[test]
+  fn test_limit_stops_endless_loop() {
+    let mut computer = Computer::new().with_limit(100);
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 1, 0, 0, Operation::Inc(Target::A)));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Jmp));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::LimitExceeded));
+    assert_eq!(computer.a, Word::new(50, Some(true)));
+    assert_eq!(computer.time, 100);
+
+    // The limit applies to every run, not to the machine as a whole
+    assert_eq!(computer.run(0), Ok(HaltReason::LimitExceeded));
+    assert_eq!(computer.a, Word::new(100, Some(true)));
+  }
+
+  #[rstest]
+  #[case(
+    Instruction::new(true, 4000, 0, 0, Operation::Jmp),
+    MixError::InvalidAddress(4000)
+  )]
+  #[case(Instruction::new(false, 1, 0, 5, Operation::Ld(Target::A)), MixError::InvalidAddress(-1))]
+  #[case(
+    Instruction::new(true, 3999, 1, 5, Operation::Cmp(Target::I1)),
+    MixError::InvalidAddress(4000)
+  )]
+  #[case(Instruction::new(false, 2, 0, 0, Operation::Sla), MixError::InvalidShift(-2))]
+  #[case(
+    Instruction::new(true, 3999, 0, 2, Operation::Move),
+    MixError::InvalidAddress(4000)
+  )]
+  fn test_execute_error(#[case] instruction: Instruction, #[case] expected: MixError) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.ri[0] = Register::new(1, Some(true));
+
+    program.add(instruction);
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Err(expected));
+    assert_eq!(computer.pc, 1);
+  }
+
+  #[rstest]
+  #[case(AddressPolicy::Fault, Err(MixError::InvalidAddress(-1)), 0, vec![])]
+  #[case(AddressPolicy::Wrap, Ok(HaltReason::Halted), 42, vec![])]
+  #[case(
+    AddressPolicy::Warn,
+    Ok(HaltReason::Halted),
+    42,
+    vec![Warning::AddressWrapped { location: 0, address: -1 }]
+  )]
+  fn test_address_policy(
+    #[case] policy: AddressPolicy,
+    #[case] expected: Result<HaltReason, MixError>,
+    #[case] a: u32,
+    #[case] warnings: Vec<Warning>,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.address_policy = policy;
+    computer.memory[3999] = Word::new(42, Some(true));
+
+    program.add(Instruction::new(false, 1, 0, 5, Operation::Ld(Target::A)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), expected);
+    assert_eq!(computer.a, Word::new(a, Some(true)));
+    assert_eq!(computer.warnings, warnings);
+  }
+
+  #[test]
+  fn test_address_wraps_after_indexing() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.address_policy = AddressPolicy::Wrap;
+    computer.ri[0] = Register::new(1002, Some(true));
+
+    program.add(Instruction::new(
+      true,
+      3999,
+      1,
+      5,
+      Operation::Ent(Target::A),
+    ));
+    program.add(Instruction::new(true, 3999, 1, 5, Operation::St(Target::A)));
+    program.add(Instruction::new(true, 2998, 1, 0, Operation::Jmp));
+    program.add(hlt());
+
+    // The jump goes to location 0 and the program starts over, ENTA is not
+    // affected by the policy as its address is not a memory location
+    assert_eq!(
+      computer.execute_with_limit(&program, 5),
+      Ok(HaltReason::LimitExceeded)
+    );
+    assert_eq!(computer.memory[1001], Word::new(5001, Some(true)));
+    assert_eq!(computer.pc, 2);
+  }
+
+  #[rstest]
+  #[case(
+    vec![Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::I1))],
+    Undefined::IndexOverflow(Target::I1)
+  )]
+  #[case(
+    vec![Instruction::new(true, 1000, 0, 5, Operation::Ldn(Target::I6))],
+    Undefined::IndexOverflow(Target::I6)
+  )]
+  #[case(
+    vec![
+      Instruction::new(true, 4095, 0, 2, Operation::Ent(Target::I2)),
+      Instruction::new(true, 1, 0, 0, Operation::Inc(Target::I2)),
+    ],
+    Undefined::IndexOverflow(Target::I2)
+  )]
+  #[case(
+    vec![
+      Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)),
+      Instruction::new(true, 1000, 0, 5, Operation::Div),
+    ],
+    Undefined::DivisionOverflow
+  )]
+  fn test_strict(#[case] instructions: Vec<Instruction>, #[case] expected: Undefined) {
+    let mut program = Program::new();
+
+    for instruction in instructions.iter() {
+      program.add(*instruction);
+    }
+    program.add(hlt());
+
+    let mut computer = Computer::new();
+
+    computer.memory[1000] = Word::new(4096, Some(true));
+
+    // A permissive machine goes on the way typical hardware does
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    let mut computer = Computer::new();
+
+    computer.strict = true;
+    computer.memory[1000] = Word::new(4096, Some(true));
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::Undefined(expected))
+    );
+    assert_eq!(computer.pc, instructions.len() as i64);
+  }
+
+  #[test]
+  fn test_strict_keeps_defined_behavior() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.strict = true;
+    computer.memory[1000] = Word::new(4095, Some(false));
+
+    program.add(Instruction::new(
+      true,
+      1000,
+      0,
+      5,
+      Operation::Ld(Target::I1),
+    ));
+    program.add(Instruction::new(
+      true,
+      4095,
+      0,
+      0,
+      Operation::Inc(Target::I1),
+    ));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Div));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.ri[0], Register::new(0, Some(false)));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(0b000000_000000_000000_001000_000101, Some(true)), DecodeError::InvalidOperation { code: 5, field: 8 })]
+  #[case(Word::new(0b000000_000000_000111_000101_001000, Some(true)), DecodeError::InvalidIndex(7))]
+  #[case(Word::new(0b000000_000000_000000_110100_000001, Some(false)), DecodeError::InvalidField(52))]
+  fn test_decode_error(#[case] word: Word, #[case] expected: DecodeError) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    computer.load(&program);
+    computer.memory[1] = word;
+
+    assert_eq!(computer.run(0), Err(MixError::Decode(expected)));
+    assert_eq!(computer.pc, 1);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Target::I1, 5, true, 5, true, 5, Compare::Equal)]
+  #[case(Target::I2, 5, true, 6, true, 5, Compare::Less)]
+  #[case(Target::I3, 6, true, 5, true, 5, Compare::Greater)]
+  #[case(Target::I4, 5, false, 5, true, 5, Compare::Less)]
+  #[case(Target::I5, 5, false, 5, true, 37, Compare::Equal)]
+  #[case(Target::I6, 0, false, 0, true, 5, Compare::Equal)]
+  #[case(Target::I1, 1, true, 0b0000_0001_0000_0000_0000_0000_0000, true, 5, Compare::Less)]
+  #[case(Target::I1, 1, true, 0b0000_0001_0000_0000_0000_0000_0000, true, 27, Compare::Equal)]
+  #[case(Target::I1, 2, true, 1, false, 37, Compare::Greater)]
+  fn test_cmp(
+    #[case] target: Target,
+    #[case] register: u16,
+    #[case] register_sign: bool,
+    #[case] number: u32,
+    #[case] sign: bool,
+    #[case] modifier: u32,
+    #[case] expected: Compare,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    set_index_register(
+      &mut computer,
+      target.offset(),
+      Register::new(register, Some(register_sign)),
+    );
+    computer.memory[1000] = Word::new(number, Some(sign));
+
+    program.add(Instruction::new(true, 1000, 0, modifier, Operation::Cmp(target)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.comparison, expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(5, 0b000001_010000_000011_000101_000100, false)]
+  #[case(13, 0b000001_010000_000011_000101_000100, true)]
+  #[case(29, 0b000000_000000_000011_000101_000100, true)]
+  #[case(3, 0b000000_000000_000001_010000_000011, false)]
+  #[case(36, 0b000000_000000_000000_000000_000101, true)]
+  #[case(0, 0, false)]
+  #[case(9, 0b000000_000000_000000_000000_000001, true)]
+  fn test_ld(
+    #[case] modifier: u32,
+    #[case] expected: u32,
+    #[case] sign: bool,
+    #[values(Target::A, Target::X)] target: Target,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.memory[2000] = Word::new(0b000001_010000_000011_000101_000100, Some(false));
+
+    program.add(Instruction::new(true, 2000, 0, modifier, Operation::Ld(target)));
+    program.add(Instruction::new(true, 2000, 0, modifier, Operation::Ldn(target)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    // LDN leaves the register with the opposite sign
+    assert_eq!(computer.register(target), Word::new(expected, Some(!sign)));
+  }
+
+  #[test]
+  fn test_ld_index_register() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.memory[2000] = Word::new(0b000001_000010_000011_000100_000101, Some(false));
+
+    program.add(Instruction::new(
+      true,
+      2000,
+      0,
+      5,
+      Operation::Ld(Target::I3),
+    ));
+    program.add(Instruction::new(
+      true,
+      2000,
+      0,
+      37,
+      Operation::Ldn(Target::I6),
+    ));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.ri[2], Register::new(0b000100_000101, Some(false)));
+    assert_eq!(computer.ri[5], Register::new(0b000100_000101, Some(false)));
+  }
+
+  #[rstest]
+  #[case(0, Err(MixError::Decode(DecodeError::InvalidIndex(0))))]
+  #[case(1, Ok(Register::new(1, Some(true))))]
+  #[case(6, Ok(Register::new(6, Some(true))))]
+  #[case(7, Err(MixError::Decode(DecodeError::InvalidIndex(7))))]
+  fn test_index(#[case] index: u8, #[case] expected: Result<Register, MixError>) {
+    let mut computer = Computer::new();
+
+    for (number, register) in computer.ri.iter_mut().enumerate() {
+      *register = Register::new(number as u16 + 1, Some(true));
+    }
+
+    assert_eq!(computer.index(index).copied(), expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(5, 0b000110_000111_001000_001001_000000, true)]
+  #[case(13, 0b000110_000111_001000_001001_000000, false)]
+  #[case(45, 0b000001_000010_000011_000100_000000, false)]
+  #[case(18, 0b000001_000000_000011_000100_000101, false)]
+  #[case(19, 0b000001_001001_000000_000100_000101, false)]
+  #[case(1, 0b000000_000010_000011_000100_000101, true)]
+  fn test_st(#[case] modifier: u32, #[case] expected: u32, #[case] sign: bool) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(0b000110_000111_001000_001001_000000, Some(true));
+    computer.memory[2000] = Word::new(0b000001_000010_000011_000100_000101, Some(false));
+
+    program.add(Instruction::new(true, 2000, 0, modifier, Operation::St(Target::A)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.memory[2000], Word::new(expected, Some(sign)));
+  }
+
+  #[test]
+  fn test_st_index_register_stj_stz() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.ri[1] = Register::new(0b000001_000010, Some(false));
+    computer.memory[2000] = Word::new(0b111111_111111_111111_111111_111111, Some(true));
+    computer.memory[2001] = Word::new(0b111111_111111_111111_111111_111111, Some(false));
+    computer.memory[2002] = Word::new(0b111111_111111_111111_111111_111111, Some(false));
+
+    program.add(Instruction::new(
+      true,
+      2000,
+      0,
+      5,
+      Operation::St(Target::I2),
+    ));
+    program.add(Instruction::new(true, 3, 0, 0, Operation::Jmp));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Nop));
+    program.add(Instruction::new(true, 2001, 0, 2, Operation::Stj));
+    program.add(Instruction::new(true, 2002, 0, 11, Operation::Stz));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(
+      computer.memory[2000],
+      Word::new(0b000001_000010, Some(false))
+    );
+    assert_eq!(
+      computer.memory[2001],
+      Word::new(0b000000_000010_111111_111111_111111, Some(true))
+    );
+    assert_eq!(
+      computer.memory[2002],
+      Word::new(0b000000_000000_000000_111111_111111, Some(false))
+    );
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Operation::Add, Word::new(5, Some(true)), Word::new(7, Some(true)), Word::new(12, Some(true)), false)]
+  #[case(Operation::Add, Word::new(5, Some(true)), Word::new(7, Some(false)), Word::new(2, Some(false)), false)]
+  #[case(Operation::Add, Word::new(5, Some(false)), Word::new(5, Some(true)), Word::new(0, Some(false)), false)]
+  #[case(Operation::Add, Word::new(1073741823, Some(true)), Word::new(1, Some(true)), Word::new(0, Some(true)), true)]
+  #[case(Operation::Add, Word::new(1073741823, Some(false)), Word::new(5, Some(false)), Word::new(4, Some(false)), true)]
+  #[case(Operation::Sub, Word::new(5, Some(true)), Word::new(7, Some(true)), Word::new(2, Some(false)), false)]
+  #[case(Operation::Sub, Word::new(5, Some(false)), Word::new(5, Some(false)), Word::new(0, Some(false)), false)]
+  #[case(Operation::Sub, Word::new(0, Some(true)), Word::new(0, Some(true)), Word::new(0, Some(true)), false)]
+  #[case(Operation::Sub, Word::new(1073741823, Some(true)), Word::new(2, Some(false)), Word::new(1, Some(true)), true)]
+  fn test_add_sub(
+    #[case] operation: Operation,
+    #[case] a: Word,
+    #[case] value: Word,
+    #[case] expected: Word,
+    #[case] overflow: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = a;
+    computer.memory[2000] = value;
+
+    program.add(Instruction::new(true, 2000, 0, 5, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, expected);
+    assert_eq!(computer.overflow(), overflow);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(0b000001_000001_000001_000001_000001, Some(true)), Word::new(0b000001_000001_000001_000001_000001, Some(true)), 5, 0b000000_000001_000010_000011_000100, 0b000101_000100_000011_000010_000001, true)]
+  #[case(Word::new(112, Some(false)), Word::new(0b000010_000000_000000_000000_000000, Some(true)), 9, 0, 224, false)]
+  #[case(Word::new(50, Some(false)), Word::new(0b000010_000000_000000_000000_000000, Some(false)), 1, 0, 100, true)]
+  #[case(Word::new(1073741823, Some(true)), Word::new(1073741823, Some(true)), 5, 1073741822, 1, true)]
+  fn test_mul(
+    #[case] a: Word,
+    #[case] value: Word,
+    #[case] modifier: u32,
+    #[case] expected_a: u32,
+    #[case] expected_x: u32,
+    #[case] sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = a;
+    computer.memory[2000] = value;
+
+    program.add(Instruction::new(true, 2000, 0, modifier, Operation::Mul));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(expected_a, Some(sign)));
+    assert_eq!(computer.x, Word::new(expected_x, Some(sign)));
+    assert!(!computer.overflow());
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(0, Some(true)), 17, Word::new(3, Some(true)), Word::new(5, Some(true)), Word::new(2, Some(true)))]
+  #[case(Word::new(0, Some(false)), 17, Word::new(3, Some(false)), Word::new(5, Some(true)), Word::new(2, Some(false)))]
+  #[case(Word::new(0, Some(true)), 17, Word::new(3, Some(false)), Word::new(5, Some(false)), Word::new(2, Some(true)))]
+  #[case(Word::new(1, Some(true)), 0, Word::new(2, Some(true)), Word::new(536870912, Some(true)), Word::new(0, Some(true)))]
+  #[case(Word::new(0, Some(true)), 2, Word::new(3, Some(true)), Word::new(0, Some(true)), Word::new(2, Some(true)))]
+  fn test_div(
+    #[case] a: Word,
+    #[case] x: u32,
+    #[case] value: Word,
+    #[case] quotient: Word,
+    #[case] remainder: Word,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    // The sign of rX is ignored, the remainder takes the sign of rA
+    computer.a = a;
+    computer.x = Word::new(x, Some(!a.read_sign()));
+    computer.memory[2000] = value;
+
+    program.add(Instruction::new(true, 2000, 0, 5, Operation::Div));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, quotient);
+    assert_eq!(computer.x, remainder);
+    assert!(!computer.overflow());
+  }
+
+  #[rstest]
+  #[case(5, 0)]
+  #[case(5, 3)]
+  #[case(0, 0)]
+  fn test_div_overflow(#[case] a: u32, #[case] number: u32) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(a, Some(true));
+    computer.x = Word::new(7, Some(false));
+    computer.memory[2000] = Word::new(number, Some(true));
+
+    program.add(Instruction::new(true, 2000, 0, 5, Operation::Div));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(a, Some(true)));
+    assert_eq!(computer.x, Word::new(7, Some(false)));
+    assert!(computer.overflow());
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Operation::Ent(Target::A), true, 5, 0, 5, true)]
+  #[case(Operation::Ent(Target::A), false, 5, 0, 5, false)]
+  #[case(Operation::Ent(Target::A), false, 0, 0, 0, false)]
+  #[case(Operation::Ent(Target::X), true, 5, -5, 0, true)]
+  #[case(Operation::Ent(Target::I4), true, 5, -7, 2, false)]
+  #[case(Operation::Enn(Target::A), true, 5, 0, 5, false)]
+  #[case(Operation::Enn(Target::X), true, 0, 0, 0, false)]
+  #[case(Operation::Enn(Target::I2), false, 5, 7, 2, false)]
+  #[case(Operation::Inc(Target::A), true, 5, 0, 15, true)]
+  #[case(Operation::Inc(Target::X), false, 10, 0, 0, true)]
+  #[case(Operation::Inc(Target::I5), false, 15, 0, 5, false)]
+  #[case(Operation::Dec(Target::A), true, 15, 0, 5, false)]
+  #[case(Operation::Dec(Target::I1), true, 10, 0, 0, true)]
+  #[case(Operation::Dec(Target::X), false, 5, -5, 20, true)]
+  fn test_address_transfer(
+    #[case] operation: Operation,
+    #[case] sign: bool,
+    #[case] address: u32,
+    #[case] index: i64,
+    #[case] expected: u32,
+    #[case] expected_sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.set_register(operation_target(operation), Word::new(10, Some(true))).unwrap();
+    computer.ri[5] = Register::new(index.unsigned_abs() as u16, Some(index >= 0));
+
+    program.add(Instruction::new(sign, address, if index == 0 { 0 } else { 6 }, 0, operation));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(
+      computer.register(operation_target(operation)),
+      Word::new(expected, Some(expected_sign))
+    );
+  }
+
+  fn operation_target(operation: Operation) -> Target {
+    match operation {
+      Operation::Ent(target)
+      | Operation::Enn(target)
+      | Operation::Inc(target)
+      | Operation::Dec(target) => target,
+      _ => unreachable!(),
+    }
+  }
+
+  #[rstest]
+  #[case(Target::A, 1073741823, true, 1, 0, true)]
+  #[case(Target::X, 1073741823, false, 2, 1, false)]
+  fn test_inc_overflow(
+    #[case] target: Target,
+    #[case] number: u32,
+    #[case] sign: bool,
+    #[case] address: u32,
+    #[case] expected: u32,
+    #[case] expected_sign: bool,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .set_register(target, Word::new(number, Some(sign)))
+      .unwrap();
+
+    program.add(Instruction::new(
+      sign,
+      address,
+      0,
+      0,
+      Operation::Inc(target),
+    ));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(
+      computer.register(target),
+      Word::new(expected, Some(expected_sign))
+    );
+    assert!(computer.overflow());
+  }
+
+  #[rstest]
+  #[case(3, 2000, 1000)]
+  #[case(0, 2000, 1000)]
+  #[case(10, 1001, 1000)]
+  fn test_move(#[case] count: u32, #[case] destination: u16, #[case] source: u32) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    for offset in 0..count as usize {
+      computer.memory[source as usize + offset] = Word::new(offset as u32 + 1, Some(true));
+    }
+    computer.ri[0] = Register::new(destination, Some(true));
+
+    program.add(Instruction::new(true, source, 0, count, Operation::Move));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(
+      computer.ri[0],
+      Register::new(destination + count as u16, Some(true))
+    );
+    if destination as u32 == source + 1 {
+      // Overlapping blocks spread the first word over the destination
+      assert!(
+        computer.memory[source as usize..=source as usize + count as usize]
+          .iter()
+          .all(|word| *word == Word::new(1, Some(true)))
+      );
+    } else {
+      for offset in 0..count as usize {
+        assert_eq!(
+          computer.memory[destination as usize + offset],
+          Word::new(offset as u32 + 1, Some(true))
+        );
+      }
+    }
+  }
+
+  #[rstest]
+  #[case(Target::A, 5, true, 5, true, 5, Compare::Equal)]
+  #[case(Target::A, 0, false, 0, true, 5, Compare::Equal)]
+  #[case(Target::X, 5, false, 3, false, 5, Compare::Less)]
+  #[case(Target::X, 5, false, 3, false, 13, Compare::Greater)]
+  #[case(
+    Target::A,
+    0b000001_000000,
+    true,
+    0b000010_000000,
+    true,
+    45,
+    Compare::Equal
+  )]
+  fn test_cmp_a_x(
+    #[case] target: Target,
+    #[case] register: u32,
+    #[case] register_sign: bool,
+    #[case] number: u32,
+    #[case] sign: bool,
+    #[case] modifier: u32,
+    #[case] expected: Compare,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .set_register(target, Word::new(register, Some(register_sign)))
+      .unwrap();
+    computer.memory[1000] = Word::new(number, Some(sign));
+
+    program.add(Instruction::new(
+      true,
+      1000,
+      0,
+      modifier,
+      Operation::Cmp(target),
+    ));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.comparison, expected);
+  }
+
+  #[test]
+  fn test_time() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    // Sums the words at 1001-1005 counting rI1 down from 5 to 1
+    for address in 1001..=1005 {
+      computer.memory[address] = Word::new(address as u32, Some(true));
+    }
+
+    program.add(Instruction::new(true, 5, 0, 0, Operation::Ent(Target::I1)));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Ent(Target::A)));
+    program.add(Instruction::new(true, 1000, 1, 5, Operation::Add));
+    program.add(Instruction::new(true, 1, 0, 0, Operation::Dec(Target::I1)));
+    program.add(Instruction::new(
+      true,
+      2,
+      0,
+      0,
+      Operation::Jump(Target::I1, Condition::Positive),
+    ));
+    program.add(Instruction::new(true, 2000, 0, 3, Operation::Move));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(5015, Some(true)));
+    assert_eq!(computer.time, 1 + 1 + 5 * (2 + 1 + 1) + 7 + 10);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(5, Some(true)), Instruction::new(false, 0, 0, 0, Operation::Ent(Target::A)), Word::new(0, Some(false)))]
+  #[case(Word::new(5, Some(true)), Instruction::new(true, 0, 0, 0, Operation::Ent(Target::A)), Word::new(0, Some(true)))]
+  #[case(Word::new(5, Some(true)), Instruction::new(true, 0, 1, 0, Operation::Ent(Target::A)), Word::new(0, Some(true)))]
+  #[case(Word::new(5, Some(true)), Instruction::new(false, 0, 1, 0, Operation::Ent(Target::A)), Word::new(0, Some(false)))]
+  #[case(Word::new(5, Some(true)), Instruction::new(true, 0, 0, 0, Operation::Enn(Target::A)), Word::new(0, Some(false)))]
+  #[case(Word::new(5, Some(false)), Instruction::new(true, 5, 0, 0, Operation::Inc(Target::A)), Word::new(0, Some(false)))]
+  #[case(Word::new(5, Some(true)), Instruction::new(true, 5, 0, 0, Operation::Dec(Target::A)), Word::new(0, Some(true)))]
+  #[case(Word::new(0, Some(true)), Instruction::new(true, 1000, 0, 5, Operation::Add), Word::new(0, Some(true)))]
+  #[case(Word::new(0, Some(false)), Instruction::new(true, 1000, 0, 5, Operation::Sub), Word::new(0, Some(false)))]
+  #[case(Word::new(5, Some(false)), Instruction::new(true, 1001, 0, 5, Operation::Add), Word::new(0, Some(false)))]
+  #[case(Word::new(0, Some(true)), Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)), Word::new(0, Some(false)))]
+  #[case(Word::new(0, Some(true)), Instruction::new(true, 1000, 0, 5, Operation::Ldn(Target::A)), Word::new(0, Some(true)))]
+  #[case(Word::new(0, Some(false)), Instruction::new(true, 1000, 0, 13, Operation::Ld(Target::A)), Word::new(0, Some(true)))]
+  #[case(Word::new(0, Some(true)), Instruction::new(true, 1000, 0, 5, Operation::Mul), Word::new(0, Some(false)))]
+  fn test_negative_zero(
+    #[case] a: Word,
+    #[case] instruction: Instruction,
+    #[case] expected: Word,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = a;
+    computer.ri[0] = Register::new(0, Some(false));
+    computer.memory[1000] = Word::new(0, Some(false));
+    computer.memory[1001] = Word::new(5, Some(true));
+
+    program.add(instruction);
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, expected);
+    assert!(!computer.overflow());
+  }
+
+  #[test]
+  fn test_negative_zero_is_zero() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.a = Word::new(0, Some(false));
+    computer.memory[1000] = Word::new(0, Some(true));
+
+    program.add(Instruction::new(
+      true,
+      1000,
+      0,
+      5,
+      Operation::Cmp(Target::A),
+    ));
+    program.add(Instruction::new(
+      true,
+      3,
+      0,
+      0,
+      Operation::Jump(Target::A, Condition::Zero),
+    ));
+    program.add(hlt());
+    program.add(Instruction::new(true, 5, 0, 0, Operation::Je));
+    program.add(hlt());
+    program.add(Instruction::new(true, 1, 0, 0, Operation::Ent(Target::X)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.comparison, Compare::Equal);
+    assert_eq!(computer.x, Word::new(1, Some(true)));
+  }
+
+  #[test]
+  fn test_overflow_is_kept() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.set_overflow();
+    computer.memory[1000] = Word::new(7, Some(true));
+
+    // None of the operations below may touch the toggle
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Mul));
+    program.add(Instruction::new(true, 1001, 0, 5, Operation::St(Target::X)));
+    program.add(Instruction::new(
+      true,
+      1000,
+      0,
+      5,
+      Operation::Cmp(Target::A),
+    ));
+    program.add(Instruction::new(true, 2, 0, 0, Operation::Slc));
+    program.add(Instruction::new(true, 3, 0, 0, Operation::Ent(Target::I1)));
+    program.add(Instruction::new(true, 1, 0, 0, Operation::Inc(Target::I1)));
+    program.add(Instruction::new(true, 1000, 0, 2, Operation::Move));
+    program.add(Instruction::new(true, 9, 0, 0, Operation::Jmp));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Char));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Ent(Target::A)));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Add));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert!(computer.overflow());
+
+    computer.clear_overflow();
+    assert!(!computer.overflow());
+  }
+
+  #[rstest]
+  fn test_byte_size_add_overflow(
+    #[values(ByteSize::BINARY, ByteSize::DECIMAL)] byte_size: ByteSize,
+  ) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.byte_size = byte_size;
+    computer.a = byte_size.word(byte_size.capacity(5) - 1, true);
+    computer.memory[1000] = byte_size.word(2, true);
+
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Add));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert!(computer.overflow());
+    assert_eq!(computer.a, byte_size.word(1, true));
+  }
+
+  #[test]
+  fn test_decimal_addresses() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.byte_size = ByteSize::DECIMAL;
+    computer.memory[3999] = ByteSize::DECIMAL.word(1_234_567_890, false);
+
+    program.add(Instruction::new(true, 3999, 0, 5, Operation::Ld(Target::A)));
+    program.add(Instruction::new(
+      true,
+      3998,
+      0,
+      0,
+      Operation::Ent(Target::I1),
+    ));
+    program.add(Instruction::new(true, 1, 1, 5, Operation::St(Target::A)));
+    program.add(Instruction::new(true, 5, 0, 0, Operation::Jmp));
+    program.add(hlt());
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.ri[0], Register::from_bytes([39, 98], true));
+    assert_eq!(computer.j, Register::from_bytes([0, 4], true));
+    assert_eq!(computer.a, Word::from_bytes([12, 34, 56, 78, 90], false));
+  }
+
+  #[test]
+  fn test_decimal_mul_div() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.byte_size = ByteSize::DECIMAL;
+    computer.a = ByteSize::DECIMAL.word(123_456, true);
+    computer.memory[1000] = ByteSize::DECIMAL.word(100_000, false);
+
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Mul));
+    program.add(Instruction::new(true, 2000, 0, 5, Operation::St(Target::A)));
+    program.add(Instruction::new(true, 2001, 0, 5, Operation::St(Target::X)));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Div));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(
+      computer.memory[2000],
+      Word::from_bytes([0, 0, 0, 0, 1], false)
+    );
+    assert_eq!(
+      computer.memory[2001],
+      Word::from_bytes([23, 45, 60, 0, 0], false)
+    );
+    assert_eq!(computer.a, ByteSize::DECIMAL.word(123_456, true));
+    assert_eq!(computer.x, Word::from_bytes([0; 5], false));
+  }
+
+  #[test]
+  fn test_decimal_num_char() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.byte_size = ByteSize::DECIMAL;
+    computer.a = ByteSize::DECIMAL.word(9_876_543_210, false);
+
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Char));
+    program.add(Instruction::new(true, 2000, 0, 5, Operation::St(Target::A)));
+    program.add(Instruction::new(true, 2001, 0, 5, Operation::St(Target::X)));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Num));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(
+      computer.memory[2000],
+      Word::from_bytes([39, 38, 37, 36, 35], false)
+    );
+    assert_eq!(
+      computer.memory[2001],
+      Word::from_bytes([34, 33, 32, 31, 30], true)
+    );
+    assert_eq!(computer.a, ByteSize::DECIMAL.word(9_876_543_210, false));
+    assert!(!computer.overflow());
+  }
+
+  #[rstest]
+  #[case(Operation::Fadd)]
+  #[case(Operation::Slb)]
+  fn test_attachments_on_decimal_machine(#[case] operation: Operation) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.byte_size = ByteSize::DECIMAL;
+    computer.floating_point = true;
+    computer.binary = true;
+
+    program.add(Instruction::new(true, 1000, 0, 0, operation));
+    program.add(hlt());
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::MissingAttachment(operation))
+    );
+  }
+
+  #[test]
+  fn test_decimal_num_takes_digits_of_large_bytes() {
+    let mut computer = Computer::decimal();
+    let mut program = Program::new();
+
+    computer.a = Word::from_bytes([99, 64, 75, 30, 31], true);
+    computer.x = Word::from_bytes([88, 92, 36, 47, 50], false);
+
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Num));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, ByteSize::DECIMAL.word(9_450_182_670, true));
+    assert!(!computer.overflow());
+  }
+
+  #[test]
+  fn test_decimal_display() {
+    let mut computer = Computer::decimal();
+
+    computer.a = Word::from_bytes([12, 34, 56, 78, 90], false);
+    computer.ri[0] = Register::from_bytes([39, 99], true);
+
+    let display = computer.to_string();
+
+    assert!(display.contains("A: -12 34 56 78 90\n"));
+    assert!(display.contains("I1: +39 99\n"));
+    assert!(display.contains("0F9F: +00 00 00 00 00\n"));
   }
 }
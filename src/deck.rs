@@ -0,0 +1,149 @@
+use std::{
+  fs::File,
+  io::{self, BufRead, BufReader, Write},
+  path::Path,
+};
+
+use crate::{charcode, word::Word};
+
+/// Deck of punched cards kept as text
+///
+/// Each line of the text is a card of up to 80 characters of the MIX
+/// character code, a shorter line is filled with blanks and an empty line is
+/// a blank card. A line starting with `!` is a comment and is no card, since
+/// `!` is not a character of MIX. The characters Δ, Σ and Π may be written as
+/// `~`, `[` and `#`.
+///
+/// A deck is written one card to a line without the trailing blanks and with
+/// no comments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Deck {
+  /// Cards of 16 words each, in order
+  pub cards: Vec<Vec<Word>>,
+}
+
+impl Deck {
+  /// Number of words in a card
+  pub const CARD_SIZE: usize = 16;
+
+  /// Number of characters in a card
+  pub const COLUMNS: usize = Self::CARD_SIZE * Word::BYTES;
+
+  /// Marks the line as a comment
+  pub const COMMENT: char = '!';
+
+  /// Reads the deck from the file
+  pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::from_reader(BufReader::new(File::open(path)?))
+  }
+
+  /// Reads the deck from the text, a line that is too long or has a
+  /// character outside the MIX character code is refused
+  pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+    let mut cards = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+      let line = line?;
+
+      if !line.starts_with(Self::COMMENT) {
+        cards.push(Self::card(index + 1, &line)?);
+      }
+    }
+
+    Ok(Self { cards })
+  }
+
+  /// Writes the deck as text, a card with a byte that is not a character is
+  /// refused
+  pub fn to_writer(&self, mut writer: impl Write) -> io::Result<()> {
+    for (index, card) in self.cards.iter().enumerate() {
+      let line = Self::line(card)
+        .map_err(|error| io::Error::new(error.kind(), format!("Card {}: {}", index + 1, error)))?;
+
+      writeln!(writer, "{}", line)?;
+    }
+
+    Ok(())
+  }
+
+  /// Encodes the line of the text with the number as a card
+  pub(crate) fn card(number: usize, line: &str) -> io::Result<Vec<Word>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    if line.chars().count() > Self::COLUMNS {
+      return Err(invalid(format!(
+        "Line {} has more than {} characters",
+        number,
+        Self::COLUMNS
+      )));
+    }
+
+    let line = format!("{:<1$}", line, Self::COLUMNS);
+
+    charcode::str_to_words(&line).map_err(|error| invalid(format!("Line {}: {}", number, error)))
+  }
+
+  /// Decodes the card as a line of the text without its trailing blanks
+  pub(crate) fn line(card: &[Word]) -> io::Result<String> {
+    let text = charcode::words_to_string(card).ok_or_else(|| {
+      io::Error::new(io::ErrorKind::InvalidData, "Byte is not a character of MIX")
+    })?;
+
+    Ok(text.trim_end().to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  #[test]
+  fn test_from_reader() {
+    let deck = Deck::from_reader("! Loader\nHELLO\n\n!\n[#~".as_bytes()).unwrap();
+
+    assert_eq!(deck.cards.len(), 3);
+    assert_eq!(
+      charcode::words_to_string(&deck.cards[0][..1]).unwrap(),
+      "HELLO"
+    );
+    assert_eq!(deck.cards[1], vec![Word::default(); 16]);
+    assert_eq!(
+      charcode::words_to_string(&deck.cards[2][..1]).unwrap(),
+      "ΣΠΔ  "
+    );
+  }
+
+  #[rstest]
+  #[case("C".repeat(81))]
+  #[case("OK\nnot ok".to_string())]
+  #[case("50%".to_string())]
+  fn test_from_reader_invalid(#[case] text: String) {
+    let error = Deck::from_reader(text.as_bytes()).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let text = "FIRST CARD\n\n".to_string() + &"9".repeat(80) + "\n";
+    let deck = Deck::from_reader(text.as_bytes()).unwrap();
+    let mut written = Vec::new();
+
+    deck.to_writer(&mut written).unwrap();
+
+    assert_eq!(String::from_utf8(written).unwrap(), text);
+  }
+
+  #[test]
+  fn test_to_writer_invalid() {
+    let deck = Deck {
+      cards: vec![vec![Word::from_bytes([1, 2, 3, 4, 63], true); 16]],
+    };
+
+    let error = deck.to_writer(io::sink()).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  }
+}
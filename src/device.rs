@@ -0,0 +1,628 @@
+use std::{
+  any::Any,
+  fs::{File, OpenOptions},
+  io::{self, Read, Seek, SeekFrom, Write},
+  path::Path,
+};
+
+use crate::{error::DeviceError, word::Word, Signed};
+
+/// Number of I/O units attached to the machine
+pub const UNITS: usize = 21;
+
+/// Number of lines on a page of the line printer
+pub const PAGE_LINES: usize = 60;
+
+/// Kind of I/O unit, determined by the unit number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceKind {
+  Tape,
+  Disk,
+  CardReader,
+  CardPunch,
+  LinePrinter,
+  Typewriter,
+  PaperTape,
+}
+
+impl DeviceKind {
+  /// Returns the kind of the unit by its number
+  pub fn of(unit: u32) -> Option<Self> {
+    match unit {
+      0..=7 => Some(Self::Tape),
+      8..=15 => Some(Self::Disk),
+      16 => Some(Self::CardReader),
+      17 => Some(Self::CardPunch),
+      18 => Some(Self::LinePrinter),
+      19 => Some(Self::Typewriter),
+      20 => Some(Self::PaperTape),
+      _ => None,
+    }
+  }
+
+  /// Number of words transferred by a single I/O operation
+  pub fn block_size(self) -> usize {
+    match self {
+      Self::Tape | Self::Disk => 100,
+      Self::CardReader | Self::CardPunch => 16,
+      Self::LinePrinter => 24,
+      Self::Typewriter | Self::PaperTape => 14,
+    }
+  }
+
+  /// Checks if the unit can be read by IN
+  pub fn is_input(self) -> bool {
+    !matches!(self, Self::CardPunch | Self::LinePrinter)
+  }
+
+  /// Checks if the unit can be written by OUT
+  pub fn is_output(self) -> bool {
+    !matches!(self, Self::CardReader)
+  }
+}
+
+/// I/O unit attached to the machine, which IN, OUT, IOC, JBUS and JRED talk
+/// to by its unit number
+///
+/// A transfer moves one block of words at a time. The operations a unit does
+/// not support fault.
+pub trait Device: Any {
+  /// Number of words transferred by a single I/O operation
+  fn block_size(&self) -> usize;
+
+  /// Checks if the unit is busy on its own account, such as a card reader
+  /// that ran out of cards, apart from the time its transfers take
+  ///
+  /// Nothing the program does makes such a unit ready, so IN, OUT and IOC
+  /// on it fault instead of waiting.
+  fn is_busy(&self) -> bool {
+    false
+  }
+
+  /// Units of time a transfer or a control operation keeps the unit busy
+  fn latency(&self) -> u64 {
+    0
+  }
+
+  /// Positions the unit by the magnitude of rX before IN or OUT, as a disk
+  /// selects its block, other units ignore rX
+  fn select(&mut self, _x: u64) -> Result<(), DeviceError> {
+    Ok(())
+  }
+
+  /// Reads the next block of words, as IN does, a unit with nothing to read
+  /// gives fewer words and the rest of the block in memory is left as it is
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    Err(DeviceError::Unsupported)
+  }
+
+  /// Writes the block of words, as OUT does
+  fn write(&mut self, _block: &[Word]) -> Result<(), DeviceError> {
+    Err(DeviceError::Unsupported)
+  }
+
+  /// Carries out IOC with the effective address M of the instruction and
+  /// the magnitude of rX
+  fn control(&mut self, _operation: i64, _x: u64) -> Result<(), DeviceError> {
+    Err(DeviceError::Unsupported)
+  }
+}
+
+impl dyn Device {
+  /// Returns the unit as its own type, none if it is of another type
+  pub fn downcast_ref<T: Device>(&self) -> Option<&T> {
+    (self as &dyn Any).downcast_ref()
+  }
+
+  /// Returns the unit as its own type for the operation to change it, none
+  /// if it is of another type
+  pub fn downcast_mut<T: Device>(&mut self) -> Option<&mut T> {
+    (self as &mut dyn Any).downcast_mut()
+  }
+}
+
+/// Layout of the words in the file of a unit, a file of zeros reads as +0
+/// in either
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileFormat {
+  /// Six bytes a word, the sign first with 1 for minus and then bytes 1-5
+  #[default]
+  Mixi,
+  /// Four bytes a word as the `mixvm` of GNU MDK keeps its tapes and disks,
+  /// a little endian number with the bytes in its lowest 30 bits and the
+  /// sign as bit 30 with 1 for minus, so a byte holds up to 64 values
+  Mdk,
+}
+
+impl FileFormat {
+  /// Number of bytes a word takes in the file
+  pub(crate) fn word_bytes(self) -> usize {
+    match self {
+      Self::Mixi => 1 + Word::BYTES,
+      Self::Mdk => 4,
+    }
+  }
+
+  /// Encodes the word, a byte that does not fit the format is refused
+  pub(crate) fn encode(self, word: &Word, bytes: &mut Vec<u8>) -> io::Result<()> {
+    match self {
+      Self::Mixi => {
+        bytes.push(!word.read_sign() as u8);
+        bytes.extend(word.to_bytes());
+      }
+      Self::Mdk => {
+        let mut number = (!word.read_sign() as u32) << 30;
+
+        for (index, byte) in word.bytes().enumerate() {
+          if byte >= 64 {
+            return Err(io::Error::new(
+              io::ErrorKind::InvalidData,
+              "Byte does not fit into six bits",
+            ));
+          }
+
+          number |= (byte as u32) << (6 * (Word::BYTES - 1 - index));
+        }
+
+        bytes.extend(number.to_le_bytes());
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Decodes the word from the bytes it takes in the file
+  pub(crate) fn decode(self, chunk: &[u8]) -> Word {
+    let mut data = [0; Word::BYTES];
+
+    match self {
+      Self::Mixi => {
+        data.copy_from_slice(&chunk[1..]);
+        Word::from_bytes(data, chunk[0] == 0)
+      }
+      Self::Mdk => {
+        let number = u32::from_le_bytes(chunk.try_into().expect("Word takes four bytes"));
+
+        for (index, byte) in data.iter_mut().enumerate() {
+          *byte = (number >> (6 * (Word::BYTES - 1 - index)) & 0o77) as u8;
+        }
+
+        Word::from_bytes(data, number & 1 << 30 == 0)
+      }
+    }
+  }
+}
+
+/// File of the host that keeps the blocks of a unit, such as a tape or a
+/// disk, so that the data is there for the next run
+///
+/// The blocks follow one another with nothing in between.
+pub(crate) struct BlockFile {
+  file: File,
+  block_size: usize,
+  format: FileFormat,
+}
+
+impl BlockFile {
+  /// Opens the file for blocks of the size, the file is created if it does
+  /// not exist and its data is kept if it does
+  pub fn open(path: impl AsRef<Path>, block_size: usize, format: FileFormat) -> io::Result<Self> {
+    let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create(true)
+      .truncate(false)
+      .open(path)?;
+
+    Ok(Self {
+      file,
+      block_size,
+      format,
+    })
+  }
+
+  /// Number of bytes a block takes in the file
+  fn block_bytes(&self) -> u64 {
+    (self.block_size * self.format.word_bytes()) as u64
+  }
+
+  /// Reads the block with the number, the words past the end of the file
+  /// are read as zeros
+  pub fn read(&mut self, block: u64) -> io::Result<Vec<Word>> {
+    let size = self.block_bytes();
+    let mut bytes = Vec::with_capacity(size as usize);
+
+    self.file.seek(SeekFrom::Start(block * size))?;
+    (&mut self.file).take(size).read_to_end(&mut bytes)?;
+    bytes.resize(size as usize, 0);
+
+    Ok(
+      bytes
+        .chunks(self.format.word_bytes())
+        .map(|chunk| self.format.decode(chunk))
+        .collect(),
+    )
+  }
+
+  /// Writes the block with the number, a block past the end of the file
+  /// leaves zeros in between
+  pub fn write(&mut self, block: u64, words: &[Word]) -> io::Result<()> {
+    let start = block * self.block_bytes();
+    let mut bytes = Vec::with_capacity(self.block_bytes() as usize);
+
+    for word in words {
+      self.format.encode(word, &mut bytes)?;
+    }
+
+    if self.file.metadata()?.len() < start {
+      self.file.set_len(start)?;
+    }
+
+    self.file.seek(SeekFrom::Start(start))?;
+    self.file.write_all(&bytes)
+  }
+}
+
+/// I/O unit keeping the words it transfers in memory, each unit of the
+/// machine is one of these unless replaced
+pub struct MemoryUnit {
+  pub kind: DeviceKind,
+  pub data: Vec<Word>,
+  pub position: usize,
+  /// Whether the unit is busy whatever the time, as set by the user
+  pub busy: bool,
+  /// Units of time each operation keeps the unit busy, transfers complete
+  /// instantly by default
+  pub latency: u64,
+}
+
+impl MemoryUnit {
+  pub fn new(kind: DeviceKind) -> Self {
+    Self {
+      kind,
+      data: Vec::new(),
+      position: 0,
+      busy: false,
+      latency: 0,
+    }
+  }
+
+  /// Moves the unit back to its first block
+  pub fn rewind(&mut self) {
+    self.position = 0;
+  }
+
+  /// Skips the number of blocks forward, or backward if negative, stopping
+  /// at the first block
+  pub fn skip(&mut self, blocks: i64) {
+    let offset = blocks.unsigned_abs() as usize * self.kind.block_size();
+
+    self.position = if blocks < 0 {
+      self.position.saturating_sub(offset)
+    } else {
+      self.position + offset
+    };
+  }
+
+  /// Moves the unit to the block with the number
+  pub fn seek(&mut self, block: usize) {
+    self.position = block * self.kind.block_size();
+  }
+
+  /// Moves the unit to the top of the following page, the rest of the current
+  /// page is left blank
+  pub fn skip_page(&mut self) {
+    let page = PAGE_LINES * self.kind.block_size();
+
+    self.position = (self.position / page + 1) * page;
+    self.data.resize(self.position, Word::default());
+  }
+}
+
+impl Device for MemoryUnit {
+  fn block_size(&self) -> usize {
+    self.kind.block_size()
+  }
+
+  fn is_busy(&self) -> bool {
+    self.busy
+  }
+
+  fn latency(&self) -> u64 {
+    self.latency
+  }
+
+  /// Positions a disk on the block selected by rX, other kinds of unit go
+  /// on from where they are
+  fn select(&mut self, x: u64) -> Result<(), DeviceError> {
+    if self.kind == DeviceKind::Disk {
+      self.seek(x as usize);
+    }
+
+    Ok(())
+  }
+
+  /// Reads the next block of words, the words past the end of the data are
+  /// read as zeros
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    if !self.kind.is_input() {
+      return Err(DeviceError::Unsupported);
+    }
+
+    let size = self.kind.block_size();
+    let mut block = vec![Word::default(); size];
+
+    if self.position < self.data.len() {
+      let end = self.data.len().min(self.position + size);
+
+      block[..end - self.position].copy_from_slice(&self.data[self.position..end]);
+    }
+
+    self.position += size;
+
+    Ok(block)
+  }
+
+  /// Writes the block of words at the current position, replacing the data
+  /// that is already there
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    if !self.kind.is_output() {
+      return Err(DeviceError::Unsupported);
+    }
+
+    let end = self.position + block.len();
+
+    if self.data.len() < end {
+      self.data.resize(end, Word::default());
+    }
+
+    self.data[self.position..end].copy_from_slice(block);
+    self.position = end;
+
+    Ok(())
+  }
+
+  /// Carries out the control operations of the kind of unit, the others
+  /// fault
+  ///
+  /// | Unit       | M   | Operation                             |
+  /// |------------|-----|---------------------------------------|
+  /// | Tape       | 0   | Rewinds                               |
+  /// | Tape       | < 0 | Skips back -M blocks, or rewinds      |
+  /// | Tape       | > 0 | Skips forward M blocks                |
+  /// | Disk       | 0   | Positions on the block selected by rX |
+  /// | Printer    | 0   | Skips to the top of the next page     |
+  /// | Paper tape | 0   | Rewinds                               |
+  fn control(&mut self, operation: i64, x: u64) -> Result<(), DeviceError> {
+    match (self.kind, operation) {
+      (DeviceKind::Tape, 0) => self.rewind(),
+      (DeviceKind::Tape, _) => self.skip(operation),
+      (DeviceKind::Disk, 0) => self.seek(x as usize),
+      (DeviceKind::LinePrinter, 0) => self.skip_page(),
+      (DeviceKind::PaperTape, 0) => self.rewind(),
+      _ => return Err(DeviceError::Unsupported),
+    }
+
+    Ok(())
+  }
+}
+
+/// Output that a test keeps a handle to after a printer or a typewriter
+/// takes it
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct Paper(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+#[cfg(test)]
+impl Write for Paper {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.0.borrow_mut().write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+impl Paper {
+  /// Text written so far
+  pub(crate) fn text(&self) -> String {
+    String::from_utf8(self.0.borrow().clone()).unwrap()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(0, DeviceKind::Tape, 100)]
+  #[case(7, DeviceKind::Tape, 100)]
+  #[case(8, DeviceKind::Disk, 100)]
+  #[case(15, DeviceKind::Disk, 100)]
+  #[case(16, DeviceKind::CardReader, 16)]
+  #[case(17, DeviceKind::CardPunch, 16)]
+  #[case(18, DeviceKind::LinePrinter, 24)]
+  #[case(19, DeviceKind::Typewriter, 14)]
+  #[case(20, DeviceKind::PaperTape, 14)]
+  fn test_kind_of(#[case] unit: u32, #[case] kind: DeviceKind, #[case] block_size: usize) {
+    assert_eq!(DeviceKind::of(unit), Some(kind));
+    assert_eq!(kind.block_size(), block_size);
+  }
+
+  #[rstest]
+  #[case(DeviceKind::Tape, true, true)]
+  #[case(DeviceKind::Disk, true, true)]
+  #[case(DeviceKind::CardReader, true, false)]
+  #[case(DeviceKind::CardPunch, false, true)]
+  #[case(DeviceKind::LinePrinter, false, true)]
+  #[case(DeviceKind::Typewriter, true, true)]
+  #[case(DeviceKind::PaperTape, true, true)]
+  fn test_kind_direction(#[case] kind: DeviceKind, #[case] input: bool, #[case] output: bool) {
+    assert_eq!(kind.is_input(), input);
+    assert_eq!(kind.is_output(), output);
+  }
+
+  #[test]
+  fn test_kind_of_invalid_unit() {
+    assert_eq!(DeviceKind::of(UNITS as u32), None);
+  }
+
+  #[rstest]
+  #[case(0, 0)]
+  #[case(14, 1)]
+  #[case(20, 2)]
+  #[case(28, 2)]
+  #[case(30, 3)]
+  fn test_read(#[case] length: u32, #[case] blocks: usize) {
+    let mut device = MemoryUnit::new(DeviceKind::PaperTape);
+
+    device.data = (1..=length).map(|n| Word::new(n, Some(true))).collect();
+
+    for block in 0..blocks {
+      let words = device.read().unwrap();
+      let start = block * 14;
+      let end = (start + 14).min(device.data.len());
+
+      assert_eq!(words.len(), 14);
+      assert_eq!(words[..end - start], device.data[start..end]);
+      assert!(words[end - start..]
+        .iter()
+        .all(|word| *word == Word::default()));
+    }
+
+    assert_eq!(device.read().unwrap(), vec![Word::default(); 14]);
+  }
+
+  #[rstest]
+  #[case(DeviceKind::CardPunch)]
+  #[case(DeviceKind::LinePrinter)]
+  fn test_read_unsupported(#[case] kind: DeviceKind) {
+    let mut device = MemoryUnit::new(kind);
+
+    assert_eq!(device.read(), Err(DeviceError::Unsupported));
+    assert_eq!(device.position, 0);
+  }
+
+  #[test]
+  fn test_write_unsupported() {
+    let mut device = MemoryUnit::new(DeviceKind::CardReader);
+
+    assert_eq!(
+      device.write(&[Word::default(); 16]),
+      Err(DeviceError::Unsupported)
+    );
+    assert!(device.data.is_empty());
+  }
+
+  #[test]
+  fn test_downcast() {
+    let mut device: Box<dyn Device> = Box::new(MemoryUnit::new(DeviceKind::Tape));
+
+    assert!(device.downcast_ref::<MemoryUnit>().is_some());
+
+    device.downcast_mut::<MemoryUnit>().unwrap().position = 100;
+
+    assert_eq!(device.downcast_ref::<MemoryUnit>().unwrap().position, 100);
+  }
+
+  #[test]
+  fn test_write() {
+    let mut device = MemoryUnit::new(DeviceKind::LinePrinter);
+    let first = [Word::new(1, Some(true)); 24];
+    let second = [Word::new(2, Some(false)); 24];
+
+    device.write(&first).unwrap();
+    device.write(&second).unwrap();
+
+    assert_eq!(device.position, 48);
+    assert_eq!(device.data[..24], first);
+    assert_eq!(device.data[24..], second);
+  }
+
+  #[test]
+  fn test_write_over_data() {
+    let mut device = MemoryUnit::new(DeviceKind::Tape);
+
+    device.data = vec![Word::new(1, Some(true)); 300];
+    device.position = 100;
+    device.write(&[Word::new(2, Some(true)); 100]).unwrap();
+
+    assert_eq!(device.data.len(), 300);
+    assert_eq!(device.data[99], Word::new(1, Some(true)));
+    assert_eq!(device.data[100..200], [Word::new(2, Some(true)); 100]);
+    assert_eq!(device.data[200], Word::new(1, Some(true)));
+  }
+
+  #[test]
+  fn test_write_past_end() {
+    let mut device = MemoryUnit::new(DeviceKind::PaperTape);
+
+    device.read().unwrap();
+    device.write(&[Word::new(3, Some(true)); 14]).unwrap();
+
+    assert_eq!(device.data[..14], [Word::default(); 14]);
+    assert_eq!(device.data[14..], [Word::new(3, Some(true)); 14]);
+  }
+
+  #[rstest]
+  #[case(0, 3, 300)]
+  #[case(200, 2, 400)]
+  #[case(300, -1, 200)]
+  #[case(300, -3, 0)]
+  #[case(300, -10, 0)]
+  #[case(250, -1, 150)]
+  fn test_skip(#[case] position: usize, #[case] blocks: i64, #[case] expected: usize) {
+    let mut device = MemoryUnit::new(DeviceKind::Tape);
+
+    device.position = position;
+    device.skip(blocks);
+
+    assert_eq!(device.position, expected);
+  }
+
+  #[test]
+  fn test_rewind() {
+    let mut device = MemoryUnit::new(DeviceKind::Tape);
+
+    device.write(&[Word::new(1, Some(true)); 100]).unwrap();
+    device.rewind();
+
+    assert_eq!(device.position, 0);
+    assert_eq!(device.read().unwrap(), vec![Word::new(1, Some(true)); 100]);
+  }
+
+  #[rstest]
+  #[case(0, 0)]
+  #[case(1, 100)]
+  #[case(42, 4200)]
+  fn test_seek(#[case] block: usize, #[case] expected: usize) {
+    let mut device = MemoryUnit::new(DeviceKind::Disk);
+
+    device.seek(block);
+
+    assert_eq!(device.position, expected);
+  }
+
+  #[rstest]
+  #[case(0, 1440)]
+  #[case(24, 1440)]
+  #[case(1416, 1440)]
+  #[case(1440, 2880)]
+  fn test_skip_page(#[case] position: usize, #[case] expected: usize) {
+    let mut device = MemoryUnit::new(DeviceKind::LinePrinter);
+
+    device.data = vec![Word::new(1, Some(true)); position];
+    device.position = position;
+    device.skip_page();
+
+    assert_eq!(device.position, expected);
+    assert_eq!(device.data.len(), expected);
+    assert!(device.data[position..]
+      .iter()
+      .all(|word| *word == Word::default()));
+  }
+}
@@ -0,0 +1,182 @@
+use crate::{
+  assembler::{Assembler, Assembly},
+  byte::ByteSize,
+  error::RoundTripError,
+  instruction::Instruction,
+  Signed,
+};
+
+impl Assembly {
+  /// Writes the words back as a MIXAL source for the machine with the byte
+  /// size, one statement to each word
+  ///
+  /// A word that decodes is written as its instruction and any other as
+  /// CON, with ORIG where the locations skip and END with the start. The
+  /// symbols, literals and comments of the source are not written back.
+  pub fn disassemble(&self, byte_size: ByteSize) -> String {
+    let mut text = String::new();
+    let mut next = 0;
+
+    for &(location, word) in &self.words {
+      if location != next {
+        text.push_str(&statement("ORIG", &location.to_string()));
+      }
+
+      text.push_str(&match Instruction::decode(&word, byte_size) {
+        Ok(instruction) => {
+          let instruction = instruction.to_string();
+          let (operation, address) = instruction.split_once(' ').unwrap_or((&instruction, ""));
+
+          statement(operation, address)
+        }
+        Err(_) => {
+          let sign = if word.read_sign() { "" } else { "-" };
+
+          statement("CON", &format!("{}{}", sign, byte_size.magnitude(&word)))
+        }
+      });
+      next = location + 1;
+    }
+
+    if let Some(start) = self.start {
+      text.push_str(&statement("END", &start.to_string()));
+    }
+
+    text
+  }
+
+  /// Checks that the words and the start come back bit for bit once the
+  /// assembly is disassembled and assembled again
+  pub fn check_round_trip(&self, byte_size: ByteSize) -> Result<(), RoundTripError> {
+    let assembly = Assembler {
+      byte_size,
+      ..Assembler::new()
+    }
+    .assemble(&self.disassemble(byte_size))
+    .map_err(RoundTripError::Assembly)?;
+
+    for index in 0..self.words.len().max(assembly.words.len()) {
+      let (expected, found) = (self.words.get(index), assembly.words.get(index));
+
+      if expected != found {
+        let &(location, _) = expected.or(found).expect("Word is at the index");
+
+        return Err(RoundTripError::Mismatch(location));
+      }
+    }
+
+    if assembly.start != self.start {
+      return Err(RoundTripError::StartMismatch);
+    }
+
+    Ok(())
+  }
+}
+
+/// Line of MIXAL without a location field
+fn statement(operation: &str, address: &str) -> String {
+  format!("{:8}{:<4} {}\n", "", operation, address)
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    examples,
+    instruction::{Operation, Target},
+    sourcemap::SourceMap,
+    symbols::SymbolTable,
+    word::Word,
+  };
+
+  fn assembly_of(words: &[Word]) -> Assembly {
+    Assembly {
+      words: words.iter().copied().enumerate().collect(),
+      start: Some(0),
+      symbols: SymbolTable::default(),
+      source_map: SourceMap::default(),
+    }
+  }
+
+  fn encode(sign: bool, address: u32, index: u32, field: u32, operation: Operation) -> Word {
+    Instruction::new(sign, address, index, field, operation).encode(ByteSize::BINARY)
+  }
+
+  #[test]
+  fn test_disassemble() {
+    let assembly = Assembler::new()
+      .assemble(
+        "
+        ORIG 100
+START   LDA  X,1(1:3)
+        JMP  START
+        ORIG 200
+X       CON  -7(0:3)
+        END  START
+",
+      )
+      .unwrap();
+
+    assert_eq!(
+      assembly.disassemble(ByteSize::BINARY),
+      "        ORIG 100
+        LDA  200,1(1:3)
+        JMP  100
+        ORIG 200
+        CON  -28672
+        END  100
+"
+    );
+  }
+
+  #[rstest]
+  #[case(examples::MAXIMUM, ByteSize::BINARY)]
+  #[case(examples::MAXIMUM, ByteSize::DECIMAL)]
+  #[case(examples::PRIMES, ByteSize::BINARY)]
+  #[case(examples::PRIMES, ByteSize::DECIMAL)]
+  #[case(examples::PERMUTATIONS, ByteSize::BINARY)]
+  #[case(examples::PERMUTATIONS, ByteSize::DECIMAL)]
+  fn test_round_trip_examples(#[case] source: &str, #[case] byte_size: ByteSize) {
+    let assembly = Assembler {
+      byte_size,
+      ..Assembler::new()
+    }
+    .assemble(source)
+    .unwrap();
+
+    assert_eq!(assembly.check_round_trip(byte_size), Ok(()));
+  }
+
+  #[rstest]
+  #[case(Word::new(0, Some(false)))]
+  #[case(Word::new(7, Some(false)))]
+  #[case(Word::from_bytes([63, 63, 63, 63, 63], true))]
+  #[case(encode(false, 0, 1, 5, Operation::Ld(Target::A)))]
+  #[case(encode(false, 0, 0, 0, Operation::Jmp))]
+  #[case(encode(true, 7, 0, 9, Operation::Nop))]
+  #[case(encode(true, 1000, 2, 5, Operation::Stj))]
+  #[case(encode(true, 1000, 0, 2, Operation::Stj))]
+  #[case(encode(true, 3, 2, 0, Operation::Move))]
+  #[case(encode(true, 0, 0, 16, Operation::In))]
+  #[case(encode(false, 4095, 6, 45, Operation::Ld(Target::X)))]
+  fn test_round_trip(#[case] word: Word) {
+    assert_eq!(
+      assembly_of(&[word]).check_round_trip(ByteSize::BINARY),
+      Ok(())
+    );
+  }
+
+  #[test]
+  fn test_round_trip_invalid() {
+    let mut assembly = assembly_of(&[Word::new(1, Some(true))]);
+
+    assembly.words[0].0 = 4000;
+
+    assert!(matches!(
+      assembly.check_round_trip(ByteSize::BINARY),
+      Err(RoundTripError::Assembly(_))
+    ));
+  }
+}
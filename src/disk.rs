@@ -0,0 +1,200 @@
+use std::{io, path::Path};
+
+use crate::{
+  device::{BlockFile, Device, FileFormat},
+  error::DeviceError,
+  word::Word,
+};
+
+/// Disk or drum unit kept in a file of the host, its blocks are reached in
+/// any order by the number in rX
+///
+/// IN and OUT transfer the block selected by rX, IOC 0 positions the unit on
+/// it ahead of time. A block past the capacity of the unit faults.
+pub struct FileDisk {
+  file: BlockFile,
+  /// Number of blocks the unit holds
+  capacity: u64,
+  /// Number of the block selected last
+  position: u64,
+}
+
+impl FileDisk {
+  /// Number of words in a block of a disk
+  pub const BLOCK_SIZE: usize = 100;
+
+  /// Opens the disk of the capacity in the file, the file is created if it
+  /// does not exist and its data is kept if it does
+  pub fn open(path: impl AsRef<Path>, capacity: u64) -> io::Result<Self> {
+    Ok(Self {
+      file: BlockFile::open(path, Self::BLOCK_SIZE, FileFormat::Mixi)?,
+      capacity,
+      position: 0,
+    })
+  }
+
+  /// Number of blocks the unit holds
+  pub fn capacity(&self) -> u64 {
+    self.capacity
+  }
+
+  /// Number of the block selected last
+  pub fn position(&self) -> u64 {
+    self.position
+  }
+
+  /// Selects the block, which must be within the capacity
+  fn seek(&mut self, block: u64) -> Result<(), DeviceError> {
+    if block >= self.capacity {
+      return Err(DeviceError::InvalidBlock(block));
+    }
+
+    self.position = block;
+
+    Ok(())
+  }
+}
+
+impl Device for FileDisk {
+  fn block_size(&self) -> usize {
+    Self::BLOCK_SIZE
+  }
+
+  fn select(&mut self, x: u64) -> Result<(), DeviceError> {
+    self.seek(x)
+  }
+
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    Ok(self.file.read(self.position)?)
+  }
+
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    Ok(self.file.write(self.position, block)?)
+  }
+
+  /// Positions the unit on the block selected by rX, M must be zero
+  fn control(&mut self, operation: i64, x: u64) -> Result<(), DeviceError> {
+    if operation != 0 {
+      return Err(DeviceError::Unsupported);
+    }
+
+    self.seek(x)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+  use tempfile::NamedTempFile;
+
+  use super::*;
+  use crate::{
+    computer::{Computer, HaltReason},
+    error::MixError,
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  fn block(number: u32, sign: bool) -> Vec<Word> {
+    vec![Word::new(number, Some(sign)); FileDisk::BLOCK_SIZE]
+  }
+
+  #[test]
+  fn test_random_access() {
+    let file = NamedTempFile::new().unwrap();
+    let mut disk = FileDisk::open(file.path(), 10).unwrap();
+
+    for number in [7, 2, 5] {
+      disk.select(number).unwrap();
+      disk.write(&block(number as u32, number % 2 == 0)).unwrap();
+    }
+
+    for number in [5, 7, 2] {
+      disk.select(number).unwrap();
+      assert_eq!(disk.read(), Ok(block(number as u32, number % 2 == 0)));
+    }
+
+    // A block that was never written reads as zeros
+    disk.select(3).unwrap();
+    assert_eq!(disk.read(), Ok(block(0, true)));
+  }
+
+  #[test]
+  fn test_persists_between_runs() {
+    let file = NamedTempFile::new().unwrap();
+    let mut disk = FileDisk::open(file.path(), 10).unwrap();
+
+    disk.select(4).unwrap();
+    disk.write(&block(9, false)).unwrap();
+
+    let mut disk = FileDisk::open(file.path(), 10).unwrap();
+
+    disk.select(4).unwrap();
+    assert_eq!(disk.read(), Ok(block(9, false)));
+  }
+
+  #[rstest]
+  #[case(0, Ok(0))]
+  #[case(9, Ok(9))]
+  #[case(10, Err(DeviceError::InvalidBlock(10)))]
+  #[case(4000, Err(DeviceError::InvalidBlock(4000)))]
+  fn test_capacity(#[case] x: u64, #[case] expected: Result<u64, DeviceError>) {
+    let file = NamedTempFile::new().unwrap();
+    let mut disk = FileDisk::open(file.path(), 10).unwrap();
+
+    assert_eq!(disk.control(0, x).map(|_| disk.position()), expected);
+    assert_eq!(disk.select(x).map(|_| disk.position()), expected);
+  }
+
+  #[test]
+  fn test_control_unsupported() {
+    let file = NamedTempFile::new().unwrap();
+    let mut disk = FileDisk::open(file.path(), 10).unwrap();
+
+    assert_eq!(disk.control(1, 0), Err(DeviceError::Unsupported));
+  }
+
+  #[test]
+  fn test_on_machine() {
+    let file = NamedTempFile::new().unwrap();
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(9, Box::new(FileDisk::open(file.path(), 100).unwrap()))
+      .unwrap();
+    computer.memory[1000..1100].copy_from_slice(&block(5, false));
+    computer.x = Word::new(42, Some(true));
+
+    program.add(Instruction::new(true, 1000, 0, 9, Operation::Out));
+    program.add(Instruction::new(true, 2000, 0, 9, Operation::In));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.memory[2000..2100], block(5, false));
+
+    let disk: &FileDisk = computer.device(9).unwrap().downcast_ref().unwrap();
+
+    assert_eq!(disk.position(), 42);
+  }
+
+  #[test]
+  fn test_on_machine_invalid_block() {
+    let file = NamedTempFile::new().unwrap();
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(9, Box::new(FileDisk::open(file.path(), 100).unwrap()))
+      .unwrap();
+    computer.x = Word::new(100, Some(true));
+
+    program.add(Instruction::new(true, 1000, 0, 9, Operation::In));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::InvalidBlock(9, 100))
+    );
+  }
+}
@@ -0,0 +1,454 @@
+use std::{
+  error, fmt, io,
+  path::{Path, PathBuf},
+};
+
+use crate::{
+  field::FieldSpec,
+  instruction::{Operation, Target},
+};
+
+/// Faults raised by the machine while executing a program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MixError {
+  /// The effective address does not refer to a memory location
+  InvalidAddress(i64),
+  /// The number of bytes to shift by is negative
+  InvalidShift(i64),
+  /// There is no I/O unit with the number
+  InvalidUnit(u32),
+  /// The I/O unit does not support the operation
+  UnsupportedOperation(u32),
+  /// The file or other host resource behind the I/O unit failed
+  DeviceFailure(u32, io::ErrorKind),
+  /// The block is past the capacity of the I/O unit
+  InvalidBlock(u32, u64),
+  /// The I/O unit is busy on its own account, such as a card reader out of
+  /// cards, so IN, OUT and IOC would wait for it forever
+  NotReady(u32),
+  /// The word at the location counter is not a valid instruction
+  Decode(DecodeError),
+  /// The operation needs an attachment the machine does not have, such as
+  /// the interrupt facility, the floating point attachment or the binary
+  /// extension
+  MissingAttachment(Operation),
+  /// The program relies on behavior MIX leaves undefined, raised by a strict
+  /// machine only
+  Undefined(Undefined),
+}
+
+/// Behavior MIX leaves undefined that a strict machine refuses to guess
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Undefined {
+  /// The value does not fit into the two bytes of the index register
+  IndexOverflow(Target),
+  /// The quotient does not fit into rA, so the contents of rA and rX are
+  /// undefined
+  DivisionOverflow,
+}
+
+/// Reasons why a word cannot be decoded as an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+  /// No operation has the opcode with the field
+  InvalidOperation { code: u32, field: u32 },
+  /// The index is not one of the index registers rI1-rI6
+  InvalidIndex(u32),
+  /// The field is not a valid (L:R) specification
+  InvalidField(u32),
+}
+
+/// Reasons why a field of a word or a register cannot be accessed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+  /// The field reaches past the last byte
+  OutOfRange(FieldSpec),
+}
+
+/// Reasons why an I/O unit cannot carry out an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceError {
+  /// The unit does not support the operation, such as IN on a printer
+  Unsupported,
+  /// The file or other host resource behind the unit failed
+  Io(io::ErrorKind),
+  /// The block is past the capacity of the unit
+  InvalidBlock(u64),
+}
+
+/// The number does not fit into the bytes of a word or a register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange(pub i64);
+
+/// The character has no code in the MIX character code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidChar(pub char);
+
+/// Behavior the program relies on that MIX leaves undefined, recorded by the
+/// machine instead of a fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warning {
+  /// The effective address of the instruction at the location was out of
+  /// memory and wrapped around
+  AddressWrapped { location: i64, address: i64 },
+}
+
+/// Error in a MIXAL source, found at the text of the line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblyError {
+  /// File the line is in, none for the source given as text
+  pub file: Option<PathBuf>,
+  /// Number of the line, from 1
+  pub line: usize,
+  /// Number of the character of the line the text starts at, from 1
+  pub column: usize,
+  /// Text the error is found at, empty if it is something missing
+  pub token: String,
+  pub kind: AssemblyErrorKind,
+}
+
+/// What is wrong with a line of a MIXAL source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssemblyErrorKind {
+  /// The text does not read as the part of the line, such as an expression
+  InvalidSyntax(String),
+  /// There is no operation with the name
+  UnknownOperation(String),
+  /// The symbol is used but never defined, or defined only after a use that
+  /// needs its value at once
+  UndefinedSymbol(String),
+  /// The symbol is defined more than once
+  DuplicateSymbol(String),
+  /// The value does not fit into the part of the word it goes to
+  OutOfRange(i64),
+  /// The location counter is out of memory
+  InvalidLocation(i64),
+  /// The value of a relocatable module depends on its locations or on an
+  /// external symbol in a way linking cannot adjust
+  NotRelocatable(String),
+  /// The file INCLUDE names is not found or cannot be read
+  CannotInclude(String),
+  /// The file INCLUDE names is one of the files including it
+  CyclicInclude(String),
+}
+
+/// Error linking relocatable objects into one program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkError {
+  /// No object defines the symbol another one refers to
+  UndefinedSymbol(String),
+  /// More than one object defines the symbol
+  DuplicateSymbol(String),
+  /// The address or the word does not fit once the values are added
+  OutOfRange(i64),
+  /// A word of an object goes out of memory
+  InvalidLocation(i64),
+  /// The objects are assembled for machines of different byte sizes
+  ByteSizeMismatch,
+  /// More than one object gives the location the program starts at
+  MultipleStarts,
+}
+
+/// Error found when an assembly is disassembled and assembled again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundTripError {
+  /// The source of the disassembly does not assemble
+  Assembly(AssemblyError),
+  /// The word at the location comes back different, or does not come back
+  Mismatch(usize),
+  /// The program comes back with another start
+  StartMismatch,
+}
+
+impl AssemblyError {
+  pub(crate) fn new(line: usize, column: usize, token: &str, kind: AssemblyErrorKind) -> Self {
+    Self {
+      file: None,
+      line,
+      column,
+      token: token.to_string(),
+      kind,
+    }
+  }
+
+  /// Gives the error the file its line is in
+  pub(crate) fn in_file(self, file: Option<&Path>) -> Self {
+    Self {
+      file: file.map(Path::to_path_buf),
+      ..self
+    }
+  }
+
+  /// Shows the error together with the line of the source it is found on,
+  /// the way rustc shows its errors
+  ///
+  /// The source is the text of the file the error is in, which for an
+  /// included file is not the one given to the assembler.
+  pub fn display_with_source<'a>(&'a self, source: &'a str) -> Diagnostic<'a> {
+    Diagnostic {
+      error: self,
+      source,
+    }
+  }
+}
+
+impl AssemblyErrorKind {
+  /// Short advice on how to fix the error
+  pub fn hint(&self) -> &'static str {
+    match self {
+      Self::InvalidSyntax(_) => "expected an expression, `A,I(F)` or a W-value here",
+      Self::UnknownOperation(_) => "not an operation of MIX nor a pseudo-operation",
+      Self::UndefinedSymbol(_) => "define it in the location field of a line",
+      Self::DuplicateSymbol(_) => "use another name or a local symbol such as 2H",
+      Self::OutOfRange(_) => "the value does not fit in its part of the word",
+      Self::InvalidLocation(_) => "memory holds locations 0 to 3999",
+      Self::NotRelocatable(_) => "add or subtract a single location or external symbol",
+      Self::CannotInclude(_) => "put it by the source or in one of the include paths",
+      Self::CyclicInclude(_) => "a file cannot include one that includes it",
+    }
+  }
+}
+
+/// Error of a MIXAL source shown with the line it is found on, the text
+/// marked under it
+///
+/// ```text
+/// error: Symbol NOWHERE is not defined
+///   --> line 1, column 6
+///   |
+/// 1 |  JMP NOWHERE
+///   |      ^^^^^^^ define it in the location field of a line
+/// ```
+pub struct Diagnostic<'a> {
+  error: &'a AssemblyError,
+  source: &'a str,
+}
+
+impl fmt::Display for MixError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidAddress(address) => write!(f, "Address {} is out of memory", address),
+      Self::InvalidShift(count) => write!(f, "Cannot shift by {} bytes", count),
+      Self::InvalidUnit(unit) => write!(f, "Unit {} does not exist", unit),
+      Self::UnsupportedOperation(unit) => {
+        write!(f, "Unit {} does not support the operation", unit)
+      }
+      Self::DeviceFailure(unit, kind) => write!(f, "Unit {} failed: {}", unit, kind),
+      Self::InvalidBlock(unit, block) => {
+        write!(f, "Block {} is past the capacity of unit {}", block, unit)
+      }
+      Self::NotReady(unit) => write!(f, "Unit {} is not ready", unit),
+      Self::Decode(error) => write!(f, "Cannot decode instruction: {}", error),
+      Self::MissingAttachment(operation) => {
+        write!(
+          f,
+          "Operation {} needs an attachment that is missing",
+          operation
+        )
+      }
+      Self::Undefined(behavior) => write!(f, "Undefined behavior: {}", behavior),
+    }
+  }
+}
+
+impl error::Error for MixError {}
+
+impl From<DecodeError> for MixError {
+  fn from(value: DecodeError) -> Self {
+    Self::Decode(value)
+  }
+}
+
+impl From<Undefined> for MixError {
+  fn from(value: Undefined) -> Self {
+    Self::Undefined(value)
+  }
+}
+
+impl fmt::Display for Undefined {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::IndexOverflow(target) => write!(f, "Register rI{} cannot hold the value", target),
+      Self::DivisionOverflow => write!(f, "The quotient does not fit into rA"),
+    }
+  }
+}
+
+impl fmt::Display for Warning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::AddressWrapped { location, address } => write!(
+        f,
+        "Address {} of the instruction at {} is out of memory and wrapped around",
+        address, location
+      ),
+    }
+  }
+}
+
+impl fmt::Display for AssemblyError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(file) = &self.file {
+      write!(f, "{}, ", file.display())?;
+    }
+
+    write!(
+      f,
+      "Line {}, column {}: {}",
+      self.line, self.column, self.kind
+    )
+  }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let AssemblyError {
+      file,
+      line,
+      column,
+      token,
+      kind,
+    } = self.error;
+    let text = self.source.lines().nth(line - 1).unwrap_or("");
+    let gutter = " ".repeat(line.to_string().len());
+    let place = match file {
+      Some(file) => format!("{}:{}", file.display(), line),
+      None => format!("line {}", line),
+    };
+
+    writeln!(f, "error: {}", kind)?;
+    writeln!(f, "{} --> {}, column {}", gutter, place, column)?;
+    writeln!(f, "{} |", gutter)?;
+    writeln!(f, "{} | {}", line, text)?;
+    write!(
+      f,
+      "{} | {}{} {}",
+      gutter,
+      " ".repeat(column - 1),
+      "^".repeat(token.chars().count().max(1)),
+      kind.hint()
+    )
+  }
+}
+
+impl error::Error for AssemblyError {}
+
+impl fmt::Display for AssemblyErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidSyntax(text) => write!(f, "Cannot read '{}'", text),
+      Self::UnknownOperation(name) => write!(f, "Operation {} does not exist", name),
+      Self::UndefinedSymbol(name) => write!(f, "Symbol {} is not defined", name),
+      Self::DuplicateSymbol(name) => write!(f, "Symbol {} is already defined", name),
+      Self::OutOfRange(value) => write!(f, "Value {} does not fit", value),
+      Self::InvalidLocation(location) => write!(f, "Location {} is out of memory", location),
+      Self::NotRelocatable(text) => write!(f, "Value of '{}' cannot be relocated", text),
+      Self::CannotInclude(name) => write!(f, "Cannot include file {}", name),
+      Self::CyclicInclude(name) => write!(f, "File {} includes itself", name),
+    }
+  }
+}
+
+impl fmt::Display for LinkError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UndefinedSymbol(name) => write!(f, "Symbol {} is not defined by any object", name),
+      Self::DuplicateSymbol(name) => {
+        write!(f, "Symbol {} is defined by more than one object", name)
+      }
+      Self::OutOfRange(value) => write!(f, "Value {} does not fit", value),
+      Self::InvalidLocation(location) => write!(f, "Location {} is out of memory", location),
+      Self::ByteSizeMismatch => write!(f, "Objects are for machines of different byte sizes"),
+      Self::MultipleStarts => write!(f, "More than one object gives the start"),
+    }
+  }
+}
+
+impl error::Error for LinkError {}
+
+impl fmt::Display for RoundTripError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Assembly(error) => write!(f, "Disassembly does not assemble: {}", error),
+      Self::Mismatch(location) => {
+        write!(
+          f,
+          "Word at location {} does not come back the same",
+          location
+        )
+      }
+      Self::StartMismatch => write!(f, "Program does not come back with the same start"),
+    }
+  }
+}
+
+impl error::Error for RoundTripError {}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidOperation { code, field } => {
+        write!(f, "Operation {} with field {} does not exist", code, field)
+      }
+      Self::InvalidIndex(index) => write!(f, "Index register {} does not exist", index),
+      Self::InvalidField(field) => write!(f, "Field {} is not a valid field", field),
+    }
+  }
+}
+
+impl error::Error for DecodeError {}
+
+impl DeviceError {
+  /// Makes the fault of the machine for the error of the unit
+  pub(crate) fn on(self, unit: u32) -> MixError {
+    match self {
+      Self::Unsupported => MixError::UnsupportedOperation(unit),
+      Self::Io(kind) => MixError::DeviceFailure(unit, kind),
+      Self::InvalidBlock(block) => MixError::InvalidBlock(unit, block),
+    }
+  }
+}
+
+impl fmt::Display for DeviceError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Unsupported => write!(f, "The unit does not support the operation"),
+      Self::Io(kind) => write!(f, "The unit failed: {}", kind),
+      Self::InvalidBlock(block) => write!(f, "Block {} is past the capacity", block),
+    }
+  }
+}
+
+impl error::Error for DeviceError {}
+
+impl From<io::Error> for DeviceError {
+  fn from(value: io::Error) -> Self {
+    Self::Io(value.kind())
+  }
+}
+
+impl fmt::Display for FieldError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::OutOfRange(field) => write!(f, "Field {} is out of range", field),
+    }
+  }
+}
+
+impl error::Error for FieldError {}
+
+impl fmt::Display for OutOfRange {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Number {} is out of range", self.0)
+  }
+}
+
+impl error::Error for OutOfRange {}
+
+impl fmt::Display for InvalidChar {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Character {:?} is not in the MIX character code", self.0)
+  }
+}
+
+impl error::Error for InvalidChar {}
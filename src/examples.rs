@@ -0,0 +1,292 @@
+use crate::assembler::{Assembler, Assembly};
+
+/// Program M of section 1.3.2, the subroutine that finds the maximum of
+/// `X[1]` to `X[n]`, with a program that calls it for eight numbers
+///
+/// The subroutine takes n in rI1 and leaves the maximum in rA and its index
+/// in rI2.
+pub const MAXIMUM: &str = "
+* Maximum of X[1..N], Program M of TAOCP section 1.3.2
+X       EQU  1000
+N       EQU  8
+        ORIG 3000
+MAXIMUM STJ  EXIT
+INIT    ENT3 0,1
+        JMP  CHANGEM
+LOOP    CMPA X,3
+        JGE  *+3
+CHANGEM ENT2 0,3
+        LDA  X,3
+        DEC3 1
+        J3P  LOOP
+EXIT    JMP  *
+START   ENT1 N
+        JMP  MAXIMUM
+        HLT
+        ORIG X+1
+        CON  3
+        CON  1
+        CON  4
+        CON  1
+        CON  5
+        CON  9
+        CON  2
+        CON  6
+        END  START
+";
+
+/// Program P of section 1.3.2, which prints a table of the first five
+/// hundred primes on the line printer, unit 18
+pub const PRIMES: &str = "
+* Table of primes, Program P of TAOCP section 1.3.2
+L       EQU  500
+PRINTER EQU  18
+PRIME   EQU  -1
+BUF0    EQU  2000
+BUF1    EQU  BUF0+25
+        ORIG 3000
+START   IOC  0(PRINTER)
+        LD1  =1-L=
+        LD2  =3=
+2H      INC1 1
+        ST2  PRIME+L,1
+        J1Z  2F
+4H      INC2 2
+        ENT3 2
+6H      ENTA 0
+        ENTX 0,2
+        DIV  PRIME,3
+        JXZ  4B
+        CMPA PRIME,3
+        INC3 1
+        JG   6B
+        JMP  2B
+2H      OUT  TITLE(PRINTER)
+        ENT4 BUF1+10
+        ENT5 -50
+2H      INC5 L+1
+4H      LDA  PRIME,5
+        CHAR
+        STX  0,4(1:4)
+        DEC4 1
+        DEC5 50
+        J5P  4B
+        OUT  0,4(PRINTER)
+        LD4  24,4
+        J5N  2B
+        HLT
+* Initial contents of the tables and the buffers
+        ORIG PRIME+1
+        CON  2
+        ORIG BUF0-5
+TITLE   ALF  FIRST
+        ALF  \" FIVE\"
+        ALF  \" HUND\"
+        ALF  \"RED P\"
+        ALF  RIMES
+        ORIG BUF0+24
+        CON  BUF1+10
+        ORIG BUF1+24
+        CON  BUF0+10
+        END  START
+";
+
+/// Program A of section 1.3.3, which multiplies permutations in cycle form
+///
+/// It reads the cycles from the card reader, unit 16, a symbol to each five
+/// columns and the last card ending with `=`. It prints the cards and then
+/// the product as cycles on the line printer, unit 18.
+pub const PERMUTATIONS: &str = "
+* Multiplication of permutations, Program A of TAOCP section 1.3.3
+MAXWDS  EQU  1200
+PERM    ORIG *+MAXWDS
+ANS     ORIG *+MAXWDS
+OUTBUF  ORIG *+24
+CARDS   EQU  16
+PRINTER EQU  18
+BEGIN   IN   PERM(CARDS)
+        ENT2 0
+        LDA  EQUALS
+1H      JBUS *(CARDS)
+        CMPA PERM+15,2
+        JE   *+2
+        IN   PERM+16,2(CARDS)
+        ENT1 OUTBUF
+        JBUS *(PRINTER)
+        MOVE PERM,2(16)
+        OUT  OUTBUF(PRINTER)
+        JE   1F
+        INC2 16
+        CMP2 =MAXWDS-16=
+        JLE  1B
+        HLT  666
+1H      INC2 15
+        ST2  SIZE
+        ENT3 0
+2H      LDAN PERM,3
+        CMPA LPREN(1:5)
+        JNE  1F
+        STA  PERM,3
+        INC3 1
+        LDXN PERM,3
+        JXZ  *-2
+1H      CMPA RPREN(1:5)
+        JNE  *+2
+        STX  PERM,3
+        INC3 1
+        CMP3 SIZE
+        JL   2B
+        LDA  LPREN
+        ENT1 ANS
+OPEN    ENT3 0
+1H      LDXN PERM,3
+        JXN  GO
+        INC3 1
+        CMP3 SIZE
+        JL   1B
+DONE    CMP1 =ANS=
+        JNE  *+2
+        MOVE LPREN(2)
+        MOVE =0=
+        MOVE -1,1(22)
+        ENT3 0
+        OUT  ANS,3(PRINTER)
+        INC3 24
+        LDX  ANS,3
+        JXNZ *-3
+        HLT
+LPREN   ALF  \"    (\"
+RPREN   ALF  \"    )\"
+EQUALS  ALF  \"    =\"
+SIZE    CON  0
+START   CON  0
+GO      MOVE LPREN
+        MOVE PERM,3
+        STX  START
+SUCC    STX  PERM,3
+        INC3 1
+        LDXN PERM,3(1:5)
+        JXN  1F
+        JMP  *-3
+5H      STX  0,1
+        INC1 1
+        ENT3 0
+4H      CMPX PERM,3(1:5)
+        JE   SUCC
+1H      INC3 1
+        CMP3 SIZE
+        JL   4B
+        CMPX START(1:5)
+        JNE  5B
+CLOSE   MOVE RPREN
+        CMPA -3,1
+        JNE  OPEN
+        INC1 -3
+        JMP  OPEN
+        END  BEGIN
+";
+
+/// Assembles [`MAXIMUM`] for a binary machine
+pub fn maximum() -> Assembly {
+  assemble(MAXIMUM)
+}
+
+/// Assembles [`PRIMES`] for a binary machine
+pub fn primes() -> Assembly {
+  assemble(PRIMES)
+}
+
+/// Assembles [`PERMUTATIONS`] for a binary machine
+pub fn permutations() -> Assembly {
+  assemble(PERMUTATIONS)
+}
+
+fn assemble(source: &str) -> Assembly {
+  Assembler::new()
+    .assemble(source)
+    .expect("Example assembles")
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use super::*;
+  use crate::{
+    card::CardReader,
+    computer::{Computer, HaltReason},
+    error::MixError,
+    printer::LinePrinter,
+    register::Register,
+    word::Word,
+  };
+
+  /// Runs the assembly with a line printer and the cards of the text, gives
+  /// the reason it halted and the text printed
+  fn run(assembly: &Assembly, cards: &str) -> (Result<HaltReason, MixError>, String) {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let mut computer = Computer::new();
+
+    computer
+      .attach_device(18, Box::new(LinePrinter::new(file.reopen().unwrap())))
+      .unwrap();
+    computer
+      .attach_device(
+        16,
+        Box::new(CardReader::from_reader(cards.as_bytes()).unwrap()),
+      )
+      .unwrap();
+    assembly.load(&mut computer);
+
+    let reason = computer.run(assembly.start.unwrap());
+
+    (reason, fs::read_to_string(file.path()).unwrap())
+  }
+
+  #[test]
+  fn test_maximum() {
+    let mut computer = Computer::new();
+
+    computer.execute(&maximum().to_program()).unwrap();
+
+    assert_eq!(computer.a, Word::new(9, Some(true)));
+    assert_eq!(computer.ri[1], Register::new(6, Some(true)));
+  }
+
+  #[test]
+  fn test_primes() {
+    let (reason, text) = run(&primes(), "");
+    let lines = text.lines().collect::<Vec<_>>();
+
+    assert_eq!(reason, Ok(HaltReason::Halted));
+    assert_eq!(lines.len(), 51);
+    assert_eq!(lines[0], "\x0cFIRST FIVE HUNDRED PRIMES");
+    assert_eq!(
+      lines[1],
+      "     0002 0233 0547 0877 1229 1597 1993 2371 2749 3187"
+    );
+    assert_eq!(
+      lines[50],
+      "     0229 0541 0863 1223 1583 1987 2357 2741 3181 3571"
+    );
+  }
+
+  #[test]
+  fn test_permutations() {
+    let symbols = "(ACFG)(BCD)(AED)(FADE)(BGFAE)"
+      .chars()
+      .map(|symbol| format!("{:>5}", symbol))
+      .collect::<String>();
+    let cards = format!("{}\n{:<75}    =\n", &symbols[..80], &symbols[80..]);
+    let (reason, text) = run(&permutations(), &cards);
+    let lines = text.lines().collect::<Vec<_>>();
+
+    assert_eq!(reason, Ok(HaltReason::Halted));
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], &symbols[..80]);
+    assert_eq!(
+      lines[2],
+      "    (    A    D    G    )    (    C    E    B    )"
+    );
+  }
+}
@@ -0,0 +1,231 @@
+use std::io;
+
+use crate::{device::Device, error::DeviceError, word::Word};
+
+/// Way a unit fails once its faults are injected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+  /// Every operation fails with the error
+  Error(io::ErrorKind),
+  /// The unit stays busy for good, so a program waiting for it with JBUS
+  /// never sees it ready, and IN, OUT and IOC on it fault as they would wait
+  /// forever
+  Busy,
+}
+
+/// Unit that works as the device it wraps for a number of operations and
+/// then fails, so that tests exercise how a program handles a broken unit
+///
+/// IN, OUT and IOC count as operations, the operation past the number is the
+/// first to fail.
+pub struct FaultyDevice {
+  device: Box<dyn Device>,
+  fault: Fault,
+  /// Number of operations that work
+  after: u64,
+  /// Number of operations carried out so far
+  operations: u64,
+}
+
+impl FaultyDevice {
+  /// Wraps the device, which fails in the way of the fault after the number
+  /// of operations
+  pub fn new(device: Box<dyn Device>, after: u64, fault: Fault) -> Self {
+    Self {
+      device,
+      fault,
+      after,
+      operations: 0,
+    }
+  }
+
+  /// Number of operations carried out so far, the failed ones included
+  pub fn operations(&self) -> u64 {
+    self.operations
+  }
+
+  /// Returns the device it wraps
+  pub fn inner(&self) -> &dyn Device {
+    self.device.as_ref()
+  }
+
+  /// Unwraps the device
+  pub fn into_inner(self) -> Box<dyn Device> {
+    self.device
+  }
+
+  fn has_failed(&self) -> bool {
+    self.operations >= self.after
+  }
+
+  /// Counts the operation, which fails if the faults are injected by now
+  fn operate(&mut self) -> Result<(), DeviceError> {
+    let failed = self.has_failed();
+
+    self.operations += 1;
+
+    match self.fault {
+      Fault::Error(kind) if failed => Err(DeviceError::Io(kind)),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl Device for FaultyDevice {
+  fn block_size(&self) -> usize {
+    self.device.block_size()
+  }
+
+  fn is_busy(&self) -> bool {
+    (self.fault == Fault::Busy && self.has_failed()) || self.device.is_busy()
+  }
+
+  fn latency(&self) -> u64 {
+    self.device.latency()
+  }
+
+  fn select(&mut self, x: u64) -> Result<(), DeviceError> {
+    self.device.select(x)
+  }
+
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    self.operate()?;
+    self.device.read()
+  }
+
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    self.operate()?;
+    self.device.write(block)
+  }
+
+  fn control(&mut self, operation: i64, x: u64) -> Result<(), DeviceError> {
+    self.operate()?;
+    self.device.control(operation, x)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    computer::{Computer, HaltReason},
+    device::{DeviceKind, MemoryUnit},
+    error::MixError,
+    instruction::{Condition, Instruction, Operation, Target},
+    program::Program,
+    register::Register,
+  };
+
+  fn tape(after: u64, fault: Fault) -> FaultyDevice {
+    FaultyDevice::new(Box::new(MemoryUnit::new(DeviceKind::Tape)), after, fault)
+  }
+
+  #[test]
+  fn test_error() {
+    let mut device = tape(2, Fault::Error(io::ErrorKind::TimedOut));
+    let block = vec![Word::default(); 100];
+
+    assert_eq!(device.write(&block), Ok(()));
+    assert_eq!(device.control(0, 0), Ok(()));
+    assert_eq!(device.read(), Err(DeviceError::Io(io::ErrorKind::TimedOut)));
+    assert_eq!(
+      device.write(&block),
+      Err(DeviceError::Io(io::ErrorKind::TimedOut))
+    );
+    assert!(!device.is_busy());
+    assert_eq!(device.operations(), 4);
+  }
+
+  #[test]
+  fn test_busy() {
+    let mut device = tape(1, Fault::Busy);
+
+    assert!(!device.is_busy());
+    assert_eq!(device.control(0, 0), Ok(()));
+    assert!(device.is_busy());
+
+    let unit: &MemoryUnit = device.inner().downcast_ref().unwrap();
+
+    assert_eq!(unit.position, 0);
+  }
+
+  #[test]
+  fn test_error_on_machine() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(3, Box::new(tape(1, Fault::Error(io::ErrorKind::Other))))
+      .unwrap();
+
+    program.add(Instruction::new(true, 1000, 0, 3, Operation::Out));
+    program.add(Instruction::new(true, 1000, 0, 3, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::DeviceFailure(3, io::ErrorKind::Other))
+    );
+    assert_eq!(computer.pc, 2);
+  }
+
+  #[test]
+  fn test_busy_on_machine() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(3, Box::new(tape(1, Fault::Busy)))
+      .unwrap();
+    computer.memory[1100] = Word::new(7, Some(true));
+
+    program.add(Instruction::new(true, 1000, 0, 3, Operation::Out));
+    program.add(Instruction::new(true, 1100, 0, 3, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Err(MixError::NotReady(3)));
+    assert_eq!(computer.location, 1);
+
+    let device = computer.detach_device(3).unwrap();
+    let device: &FaultyDevice = device.downcast_ref().unwrap();
+    let unit: &MemoryUnit = device.inner().downcast_ref().unwrap();
+
+    assert_eq!(device.operations(), 1);
+    assert_eq!(unit.data, vec![Word::default(); 100]);
+  }
+
+  #[test]
+  fn test_jbus_timeout() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(3, Box::new(tape(0, Fault::Busy)))
+      .unwrap();
+
+    // Gives up on the unit once JBUS has found it busy 100 times, rI1
+    // counts down the tries
+    program.add(Instruction::new(
+      true,
+      100,
+      0,
+      2,
+      Operation::Ent(Target::I1),
+    ));
+    program.add(Instruction::new(true, 3, 0, 3, Operation::Jbus));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+    program.add(Instruction::new(true, 1, 0, 1, Operation::Dec(Target::I1)));
+    program.add(Instruction::new(
+      true,
+      1,
+      0,
+      2,
+      Operation::Jump(Target::I1, Condition::Positive),
+    ));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.pc, 6);
+    assert_eq!(computer.ri[0], Register::default());
+  }
+}
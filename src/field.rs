@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::error::DecodeError;
+
+/// Field specification (L:R) of a word, the bytes L through R where byte 0
+/// is the sign
+///
+/// Instructions keep the specification in their field as 8L + R.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldSpec {
+  pub left: u32,
+  pub right: u32,
+}
+
+impl FieldSpec {
+  /// The whole word with the sign, the usual field of an instruction
+  pub const WHOLE: Self = Self::new(0, 5);
+
+  /// Creates the specification, the left byte must not come after the
+  /// right one and the right byte must be one of the five bytes of a word
+  pub const fn new(left: u32, right: u32) -> Self {
+    assert!(left <= right && right <= 5, "Invalid field specification");
+
+    Self { left, right }
+  }
+
+  /// Encodes the specification as the field of an instruction
+  pub fn encode(self) -> u32 {
+    8 * self.left + self.right
+  }
+
+  /// Checks if the sign is part of the field
+  pub fn includes_sign(self) -> bool {
+    self.left == 0
+  }
+}
+
+impl Default for FieldSpec {
+  fn default() -> Self {
+    Self::WHOLE
+  }
+}
+
+impl TryFrom<u32> for FieldSpec {
+  type Error = DecodeError;
+
+  fn try_from(value: u32) -> Result<Self, Self::Error> {
+    let (left, right) = (value / 8, value % 8);
+
+    if left > right || right > 5 {
+      return Err(DecodeError::InvalidField(value));
+    }
+
+    Ok(Self { left, right })
+  }
+}
+
+impl From<FieldSpec> for u32 {
+  fn from(value: FieldSpec) -> Self {
+    value.encode()
+  }
+}
+
+impl fmt::Display for FieldSpec {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "({}:{})", self.left, self.right)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(0, FieldSpec::new(0, 0))]
+  #[case(1, FieldSpec::new(0, 1))]
+  #[case(5, FieldSpec::WHOLE)]
+  #[case(9, FieldSpec::new(1, 1))]
+  #[case(13, FieldSpec::new(1, 5))]
+  #[case(18, FieldSpec::new(2, 2))]
+  #[case(28, FieldSpec::new(3, 4))]
+  #[case(37, FieldSpec::new(4, 5))]
+  #[case(45, FieldSpec::new(5, 5))]
+  fn test_encode_decode(#[case] value: u32, #[case] expected: FieldSpec) {
+    assert_eq!(FieldSpec::try_from(value), Ok(expected));
+    assert_eq!(expected.encode(), value);
+    assert_eq!(u32::from(expected), value);
+  }
+
+  #[rstest]
+  #[case(6)]
+  #[case(7)]
+  #[case(8)]
+  #[case(17)]
+  #[case(46)]
+  #[case(63)]
+  fn test_decode_invalid(#[case] value: u32) {
+    assert_eq!(
+      FieldSpec::try_from(value),
+      Err(DecodeError::InvalidField(value))
+    );
+  }
+
+  #[rstest]
+  #[case(FieldSpec::WHOLE, "(0:5)")]
+  #[case(FieldSpec::new(0, 0), "(0:0)")]
+  #[case(FieldSpec::new(2, 4), "(2:4)")]
+  fn test_display(#[case] field: FieldSpec, #[case] expected: &str) {
+    assert_eq!(field.to_string(), expected);
+  }
+
+  #[rstest]
+  #[case(FieldSpec::new(0, 3), true)]
+  #[case(FieldSpec::new(1, 5), false)]
+  fn test_includes_sign(#[case] field: FieldSpec, #[case] expected: bool) {
+    assert_eq!(field.includes_sign(), expected);
+  }
+
+  #[test]
+  #[should_panic(expected = "Invalid field specification")]
+  fn test_new_invalid() {
+    FieldSpec::new(3, 2);
+  }
+}
@@ -0,0 +1,414 @@
+use std::cmp::Ordering;
+
+use crate::{field::FieldSpec, word::Word, Data, Signed};
+
+/// Excess added to the exponent in byte 1, so that exponents -32 through 31
+/// fit into a byte
+pub const EXCESS: i64 = 32;
+
+/// Location of the tolerance FCMP compares the numbers with
+pub const EPSILON: i64 = 0;
+
+/// Bits kept below the four bytes of the fraction while the result is
+/// computed, the lowest one is set when any bits are shifted out
+const GUARD: u32 = 72;
+
+/// Fraction 1 with the guard bits, the fraction of a normalized number is at
+/// least 1/64 and less than 1
+const ONE: u128 = 1 << (GUARD + 24);
+
+/// Floating point number taken apart, with the fraction scaled by `ONE`
+#[derive(Debug, Clone, Copy)]
+struct Unpacked {
+  sign: bool,
+  exponent: i64,
+  fraction: u128,
+}
+
+impl Unpacked {
+  fn new(word: &Word) -> Self {
+    Self {
+      sign: word.read_sign(),
+      exponent: word.get_byte(1) as i64,
+      fraction: (word.pack_field(FieldSpec::new(2, 5)) as u128) << GUARD,
+    }
+  }
+
+  /// Value of the number, which is exact as the fraction has 24 bits only
+  fn value(self) -> f64 {
+    let magnitude =
+      (self.fraction >> GUARD) as f64 * 2f64.powi(6 * (self.exponent - EXCESS) as i32 - 24);
+
+    if self.sign {
+      magnitude
+    } else {
+      -magnitude
+    }
+  }
+}
+
+/// Shifts the fraction right keeping a sticky bit for the bits shifted out
+fn shift_right(fraction: u128, bits: u32) -> u128 {
+  if bits >= u128::BITS {
+    return (fraction != 0) as u128;
+  }
+
+  let lost = fraction & ((1 << bits) - 1);
+
+  fraction >> bits | (lost != 0) as u128
+}
+
+/// Normalizes the fraction and rounds it to four bytes, returns the number
+/// and whether the exponent does not fit into a byte
+///
+/// Halfway cases are rounded to an even fraction. The exponent is kept modulo
+/// 64 on overflow or underflow, as the machine does.
+fn normalize(sign: bool, mut exponent: i64, mut fraction: u128) -> (Word, bool) {
+  if fraction == 0 {
+    return (Word::new(0, Some(sign)), false);
+  }
+
+  while fraction >= ONE {
+    fraction = shift_right(fraction, 6);
+    exponent += 1;
+  }
+
+  while fraction < ONE >> 6 {
+    fraction <<= 6;
+    exponent -= 1;
+  }
+
+  let half = 1 << (GUARD - 1);
+  let rest = fraction & ((1 << GUARD) - 1);
+  let mut digits = (fraction >> GUARD) as u32;
+
+  if rest > half || (rest == half && digits & 1 == 1) {
+    digits += 1;
+  }
+
+  // Rounding up 0.777... carries into a new byte
+  if digits == 1 << 24 {
+    digits = 1 << 18;
+    exponent += 1;
+  }
+
+  let overflow = !(0..64).contains(&exponent);
+  let word = Word::new((exponent.rem_euclid(64) as u32) << 24 | digits, Some(sign));
+
+  (word, overflow)
+}
+
+/// Adds the floating point numbers, returns the sum and whether the exponent
+/// overflowed
+pub fn add(u: &Word, v: &Word) -> (Word, bool) {
+  let (mut u, mut v) = (Unpacked::new(u), Unpacked::new(v));
+
+  if u.exponent < v.exponent {
+    std::mem::swap(&mut u, &mut v);
+  }
+
+  v.fraction = shift_right(v.fraction, 6 * (u.exponent - v.exponent) as u32);
+
+  if u.sign == v.sign {
+    normalize(u.sign, u.exponent, u.fraction + v.fraction)
+  } else if u.fraction >= v.fraction {
+    // Numbers that cancel out give +0
+    let sign = u.sign || u.fraction == v.fraction;
+
+    normalize(sign, u.exponent, u.fraction - v.fraction)
+  } else {
+    normalize(v.sign, u.exponent, v.fraction - u.fraction)
+  }
+}
+
+/// Subtracts the floating point numbers, returns the difference and whether
+/// the exponent overflowed
+pub fn subtract(u: &Word, v: &Word) -> (Word, bool) {
+  let mut v = *v;
+
+  v.write_sign(!v.read_sign());
+
+  add(u, &v)
+}
+
+/// Multiplies the floating point numbers, returns the product and whether the
+/// exponent overflowed
+pub fn multiply(u: &Word, v: &Word) -> (Word, bool) {
+  let (u, v) = (Unpacked::new(u), Unpacked::new(v));
+  let fraction = ((u.fraction >> GUARD) * (v.fraction >> GUARD)) << (GUARD - 24);
+
+  normalize(u.sign == v.sign, u.exponent + v.exponent - EXCESS, fraction)
+}
+
+/// Divides the floating point numbers, returns the quotient and whether the
+/// exponent overflowed
+///
+/// Division by zero is reported as an overflow with the dividend unchanged.
+pub fn divide(u: &Word, v: &Word) -> (Word, bool) {
+  let (dividend, divisor) = (Unpacked::new(u), Unpacked::new(v));
+  let divisor_digits = divisor.fraction >> GUARD;
+
+  if divisor_digits == 0 {
+    return (*u, true);
+  }
+
+  let quotient = dividend.fraction / divisor_digits;
+  let fraction = quotient << 24 | (dividend.fraction % divisor_digits != 0) as u128;
+
+  normalize(
+    dividend.sign == divisor.sign,
+    dividend.exponent - divisor.exponent + EXCESS,
+    fraction,
+  )
+}
+
+/// Converts the integer into a floating point number, as FLOT does
+pub fn from_integer(word: &Word) -> Word {
+  let fraction = (word.read_data() as u128) << (GUARD + 24 - 30);
+
+  // An integer has five bytes, so it never overflows the exponent
+  normalize(word.read_sign(), EXCESS + 5, fraction).0
+}
+
+/// Converts the floating point number into the nearest integer, as FIX does,
+/// returns the integer and whether it does not fit into a word
+pub fn to_integer(word: &Word) -> (Word, bool) {
+  let number = Unpacked::new(word);
+  let digits = number.fraction >> GUARD;
+  let shift = 6 * (number.exponent - EXCESS) - 24;
+
+  let (magnitude, overflow) = if shift >= 0 {
+    let magnitude = digits.checked_shl(shift as u32).unwrap_or(0);
+
+    (
+      magnitude,
+      digits != 0 && (shift >= 30 || magnitude >> 30 != 0),
+    )
+  } else if -shift > 24 {
+    (0, false)
+  } else {
+    let bits = -shift as u32;
+    let half = 1 << (bits - 1);
+    let rest = digits & ((1 << bits) - 1);
+    let mut magnitude = digits >> bits;
+
+    if rest > half || (rest == half && magnitude & 1 == 1) {
+      magnitude += 1;
+    }
+
+    (magnitude, false)
+  };
+
+  let integer = Word::new((magnitude & ((1 << 30) - 1)) as u32, Some(word.read_sign()));
+
+  (integer, overflow)
+}
+
+/// Compares the floating point numbers as FCMP does, they are equal when they
+/// differ by no more than epsilon times the scale of the larger exponent
+pub fn compare(u: &Word, v: &Word, epsilon: &Word) -> Ordering {
+  let (u, v) = (Unpacked::new(u), Unpacked::new(v));
+  let scale = 2f64.powi(6 * (u.exponent.max(v.exponent) - EXCESS) as i32);
+  let tolerance = Unpacked::new(epsilon).value().abs() * scale;
+  let difference = u.value() - v.value();
+
+  if difference.abs() <= tolerance {
+    Ordering::Equal
+  } else if difference < 0.0 {
+    Ordering::Less
+  } else {
+    Ordering::Greater
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    computer::{Compare, Computer, HaltReason},
+    error::MixError,
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  /// Builds the floating point number from its exponent byte and fraction
+  fn float(sign: bool, exponent: u32, fraction: u32) -> Word {
+    Word::new(exponent << 24 | fraction, Some(sign))
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  // 1 + 1 = 2
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000010_000000_000000_000000), false)]
+  // 1 + -1 = +0
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(false, 33, 0b000001_000000_000000_000000), float(true, 0, 0), false)]
+  // 0.5 + 63.5 = 64
+  #[case(float(true, 32, 0b100000_000000_000000_000000), float(true, 33, 0b111111_100000_000000_000000), float(true, 34, 0b000001_000000_000000_000000), false)]
+  // -3 + 1 = -2
+  #[case(float(false, 33, 0b000011_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), float(false, 33, 0b000010_000000_000000_000000), false)]
+  // The smaller number is rounded away when it is too far below
+  #[case(float(true, 40, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), float(true, 40, 0b000001_000000_000000_000000), false)]
+  // A half of the last digit is rounded to an even fraction
+  #[case(float(true, 36, 0b000001_000000_000000_000001), float(true, 32, 0b100000_000000_000000_000000), float(true, 36, 0b000001_000000_000000_000010), false)]
+  #[case(float(true, 36, 0b000001_000000_000000_000010), float(true, 32, 0b100000_000000_000000_000000), float(true, 36, 0b000001_000000_000000_000010), false)]
+  // The exponent overflows past 63
+  #[case(float(true, 63, 0b100000_000000_000000_000000), float(true, 63, 0b100000_000000_000000_000000), float(true, 0, 0b000001_000000_000000_000000), true)]
+  fn test_add(#[case] u: Word, #[case] v: Word, #[case] expected: Word, #[case] overflow: bool) {
+    assert_eq!(add(&u, &v), (expected, overflow));
+    assert_eq!(add(&v, &u), (expected, overflow));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  // 2 - 1 = 1
+  #[case(float(true, 33, 0b000010_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), false)]
+  // 1 - 1.015625 = -0.015625
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000001_000000_000000), float(false, 32, 0b000001_000000_000000_000000), false)]
+  // The exponent underflows below 0
+  #[case(float(true, 0, 0b000001_000001_000000_000000), float(true, 0, 0b000001_000000_000000_000000), float(true, 63, 0b000001_000000_000000_000000), true)]
+  fn test_subtract(#[case] u: Word, #[case] v: Word, #[case] expected: Word, #[case] overflow: bool) {
+    assert_eq!(subtract(&u, &v), (expected, overflow));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  // 2 * 3 = 6
+  #[case(float(true, 33, 0b000010_000000_000000_000000), float(true, 33, 0b000011_000000_000000_000000), float(true, 33, 0b000110_000000_000000_000000), false)]
+  // 16 * -8 = -128
+  #[case(float(true, 33, 0b010000_000000_000000_000000), float(false, 33, 0b001000_000000_000000_000000), float(false, 34, 0b000010_000000_000000_000000), false)]
+  // 0.5 * 0.5 = 0.25
+  #[case(float(true, 32, 0b100000_000000_000000_000000), float(true, 32, 0b100000_000000_000000_000000), float(true, 32, 0b010000_000000_000000_000000), false)]
+  // Zero times any number is zero
+  #[case(float(true, 0, 0), float(false, 40, 0b000011_000000_000000_000000), float(false, 0, 0), false)]
+  // The exponent overflows past 63
+  #[case(float(true, 60, 0b000001_000000_000000_000000), float(true, 40, 0b000001_000000_000000_000000), float(true, 3, 0b000001_000000_000000_000000), true)]
+  fn test_multiply(#[case] u: Word, #[case] v: Word, #[case] expected: Word, #[case] overflow: bool) {
+    assert_eq!(multiply(&u, &v), (expected, overflow));
+    assert_eq!(multiply(&v, &u), (expected, overflow));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  // 6 / 3 = 2
+  #[case(float(true, 33, 0b000110_000000_000000_000000), float(true, 33, 0b000011_000000_000000_000000), float(true, 33, 0b000010_000000_000000_000000), false)]
+  // 1 / 3 = 0.333... rounded down
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000011_000000_000000_000000), float(true, 32, 0b010101_010101_010101_010101), false)]
+  // 2 / 3 = 0.666... rounded up
+  #[case(float(true, 33, 0b000010_000000_000000_000000), float(true, 33, 0b000011_000000_000000_000000), float(true, 32, 0b101010_101010_101010_101011), false)]
+  // -1 / 0.5 = -2
+  #[case(float(false, 33, 0b000001_000000_000000_000000), float(true, 32, 0b100000_000000_000000_000000), float(false, 33, 0b000010_000000_000000_000000), false)]
+  // Division by zero keeps the dividend
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 0, 0), float(true, 33, 0b000001_000000_000000_000000), true)]
+  fn test_divide(#[case] u: Word, #[case] v: Word, #[case] expected: Word, #[case] overflow: bool) {
+    assert_eq!(divide(&u, &v), (expected, overflow));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(0, Some(true)), float(true, 0, 0))]
+  #[case(Word::new(1, Some(true)), float(true, 33, 0b000001_000000_000000_000000))]
+  #[case(Word::new(100, Some(false)), float(false, 34, 0b000001_100100_000000_000000))]
+  #[case(Word::new(64 * 64 * 64 * 64, Some(true)), float(true, 37, 0b000001_000000_000000_000000))]
+  // Five significant bytes are rounded to four
+  #[case(Word::new(0b000001_000000_000000_000000_100001, Some(true)), float(true, 37, 0b000001_000000_000000_000001))]
+  fn test_from_integer(#[case] word: Word, #[case] expected: Word) {
+    assert_eq!(from_integer(&word), expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(float(true, 33, 0b000110_000000_000000_000000), Word::new(6, Some(true)), false)]
+  #[case(float(false, 34, 0b000001_100100_000000_000000), Word::new(100, Some(false)), false)]
+  // 2.5 and 3.5 are rounded to an even integer
+  #[case(float(true, 33, 0b000010_100000_000000_000000), Word::new(2, Some(true)), false)]
+  #[case(float(true, 33, 0b000011_100000_000000_000000), Word::new(4, Some(true)), false)]
+  #[case(float(true, 32, 0b110000_000000_000000_000000), Word::new(1, Some(true)), false)]
+  #[case(float(true, 20, 0b110000_000000_000000_000000), Word::new(0, Some(true)), false)]
+  #[case(float(true, 37, 0b111111_000000_000000_000001), Word::new(0b111111_000000_000000_000001_000000, Some(true)), false)]
+  // 64^5 does not fit into a word
+  #[case(float(true, 38, 0b000001_000000_000000_000000), Word::new(0, Some(true)), true)]
+  #[case(float(true, 63, 0b000001_000000_000000_000000), Word::new(0, Some(true)), true)]
+  fn test_to_integer(#[case] word: Word, #[case] expected: Word, #[case] overflow: bool) {
+    assert_eq!(to_integer(&word), (expected, overflow));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000010_000000_000000_000000), float(true, 0, 0), Ordering::Less)]
+  #[case(float(true, 33, 0b000010_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), float(true, 0, 0), Ordering::Greater)]
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), float(true, 0, 0), Ordering::Equal)]
+  #[case(float(false, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000000_000000_000000), float(true, 0, 0), Ordering::Less)]
+  // +0 and -0 are equal
+  #[case(float(true, 0, 0), float(false, 0, 0), float(true, 0, 0), Ordering::Equal)]
+  // 1 and 1.015625 are equal within 1/4096 of the scale of 64
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000001_000000_000000), float(true, 31, 0b000001_000000_000000_000000), Ordering::Equal)]
+  #[case(float(true, 33, 0b000001_000000_000000_000000), float(true, 33, 0b000001_000010_000000_000000), float(true, 31, 0b000001_000000_000000_000000), Ordering::Less)]
+  fn test_compare(#[case] u: Word, #[case] v: Word, #[case] epsilon: Word, #[case] expected: Ordering) {
+    assert_eq!(compare(&u, &v, &epsilon), expected);
+  }
+
+  #[rstest]
+  #[case(Operation::Fadd)]
+  #[case(Operation::Flot)]
+  #[case(Operation::Fcmp)]
+  fn test_operation_without_attachment(#[case] operation: Operation) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(Instruction::new(true, 2000, 0, 0, operation));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::MissingAttachment(operation))
+    );
+  }
+
+  #[test]
+  fn test_operations() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.floating_point = true;
+    computer.a = Word::new(3, Some(true));
+    computer.memory[2000] = float(true, 33, 0b000010_000000_000000_000000);
+    computer.memory[2001] = float(true, 33, 0b000100_000000_000000_000000);
+
+    // ((3 + 2) * 2 - 2) / 4 compared to 2, then converted back to 2
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Flot));
+    program.add(Instruction::new(true, 2000, 0, 0, Operation::Fadd));
+    program.add(Instruction::new(true, 2000, 0, 0, Operation::Fmul));
+    program.add(Instruction::new(true, 2000, 0, 0, Operation::Fsub));
+    program.add(Instruction::new(true, 2001, 0, 0, Operation::Fdiv));
+    program.add(Instruction::new(true, 2000, 0, 0, Operation::Fcmp));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Fix));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.a, Word::new(2, Some(true)));
+    assert_eq!(computer.comparison, Compare::Equal);
+    assert!(!computer.overflow());
+    assert_eq!(computer.time, 3 + 4 + 9 + 4 + 11 + 4 + 3 + 10);
+  }
+
+  #[test]
+  fn test_overflow() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.floating_point = true;
+    computer.a = float(true, 63, 0b100000_000000_000000_000000);
+    computer.memory[2000] = float(true, 63, 0b100000_000000_000000_000000);
+
+    program.add(Instruction::new(true, 2000, 0, 0, Operation::Fadd));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert!(computer.overflow());
+    assert_eq!(computer.a, float(true, 0, 0b000001_000000_000000_000000));
+  }
+}
@@ -0,0 +1,105 @@
+use std::{
+  thread,
+  time::{Duration, Instant},
+};
+
+/// Governor that holds the machine back to a speed of the wall clock, so that
+/// a program takes about as long as on the machine of the book
+///
+/// The machine is let ahead of the clock by a little before the governor
+/// sleeps, since sleeping for every unit of time would take longer than the
+/// unit itself. A machine that falls behind, such as while the typewriter
+/// waits for the user, goes on from where it is instead of catching up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Governor {
+  /// Wall clock time of a unit of time of the machine
+  pub unit: Duration,
+  /// Moment and time of the machine the pace is kept from
+  origin: Option<(Instant, u64)>,
+}
+
+impl Governor {
+  /// Time the machine may be ahead of the clock before the governor sleeps
+  const SLACK: Duration = Duration::from_millis(1);
+
+  /// Time the machine may be behind the clock before it stops catching up
+  const LAG: Duration = Duration::from_millis(100);
+
+  /// Creates the governor taking the wall clock time for a unit of time of
+  /// the machine, such as 10 µs
+  pub fn new(unit: Duration) -> Self {
+    Self { unit, origin: None }
+  }
+
+  /// Keeps the pace from the time of the machine on, as a run starts
+  pub(crate) fn start(&mut self, time: u64) {
+    self.origin = Some((Instant::now(), time));
+  }
+
+  /// Sleeps until the wall clock catches up with the time of the machine
+  pub(crate) fn pace(&mut self, time: u64) {
+    let Some((start, origin)) = self.origin else {
+      return self.start(time);
+    };
+
+    let units = u32::try_from(time.saturating_sub(origin)).unwrap_or(u32::MAX);
+    let due = start + self.unit.saturating_mul(units);
+    let now = Instant::now();
+
+    if due > now + Self::SLACK {
+      thread::sleep(due - now);
+    } else if now > due + Self::LAG {
+      self.start(time);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    computer::{Computer, HaltReason},
+    instruction::{Instruction, Operation},
+    program::Program,
+    register::Register,
+  };
+
+  #[test]
+  fn test_pace() {
+    let mut governor = Governor::new(Duration::from_millis(1));
+    let start = Instant::now();
+
+    governor.start(100);
+    governor.pace(120);
+
+    assert!(start.elapsed() >= Duration::from_millis(20));
+  }
+
+  #[test]
+  fn test_no_catching_up() {
+    let mut governor = Governor::new(Duration::from_micros(10));
+
+    governor.origin = Some((Instant::now() - Duration::from_secs(1), 0));
+    governor.pace(10);
+
+    let (start, origin) = governor.origin.unwrap();
+
+    assert_eq!(origin, 10);
+    assert!(start.elapsed() < Governor::LAG);
+  }
+
+  #[test]
+  fn test_on_machine() {
+    let mut computer = Computer::new().with_governor(Governor::new(Duration::from_millis(1)));
+    let mut program = Program::new();
+    let start = Instant::now();
+
+    computer.ri[0] = Register::new(2000, Some(true));
+    program.add(Instruction::new(true, 1000, 0, 10, Operation::Move));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.time, 31);
+    assert!(start.elapsed() >= Duration::from_millis(30));
+  }
+}
@@ -1,37 +1,447 @@
-use crate::{word::Word, Data};
+use std::fmt;
 
+use crate::{byte::ByteSize, error::DecodeError, field::FieldSpec, word::Word, Data, Signed};
+
+/// Register an operation works with, in the order the opcodes follow
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum Command {
-  Noop = 0,
-  Lda = 8,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Target {
+  A,
+  I1,
+  I2,
+  I3,
+  I4,
+  I5,
+  I6,
+  X,
+}
+
+impl Target {
+  const ALL: [Self; 8] = [
+    Self::A,
+    Self::I1,
+    Self::I2,
+    Self::I3,
+    Self::I4,
+    Self::I5,
+    Self::I6,
+    Self::X,
+  ];
+
+  /// Returns the register by its offset from the first opcode of a group
+  fn from_offset(offset: u32) -> Self {
+    Self::ALL[offset as usize]
+  }
+
+  /// Offset of the register from the first opcode of a group, which is also
+  /// the number of an index register
+  pub fn offset(self) -> u32 {
+    self as u32
+  }
 }
 
-impl From<u32> for Command {
-  fn from(value: u32) -> Self {
-    match value {
-      0 => Self::Noop,
-      8 => Self::Lda,
-      _ => unreachable!("Command not implemented"),
+impl fmt::Display for Target {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::A => write!(f, "A"),
+      Self::X => write!(f, "X"),
+      _ => write!(f, "{}", self.offset()),
     }
   }
 }
 
-impl From<Command> for u32 {
-  fn from(value: Command) -> Self {
-    match value {
-      Command::Noop => 0,
-      Command::Lda => 8,
+/// Condition checked by the register jumps, in the order of the field
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Condition {
+  Negative,
+  Zero,
+  Positive,
+  NonNegative,
+  NonZero,
+  NonPositive,
+  /// Checks the lowest bit, provided by the binary extension for rA and rX
+  Even,
+  Odd,
+}
+
+impl Condition {
+  const ALL: [Self; 8] = [
+    Self::Negative,
+    Self::Zero,
+    Self::Positive,
+    Self::NonNegative,
+    Self::NonZero,
+    Self::NonPositive,
+    Self::Even,
+    Self::Odd,
+  ];
+}
+
+impl fmt::Display for Condition {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Negative => write!(f, "N"),
+      Self::Zero => write!(f, "Z"),
+      Self::Positive => write!(f, "P"),
+      Self::NonNegative => write!(f, "NN"),
+      Self::NonZero => write!(f, "NZ"),
+      Self::NonPositive => write!(f, "NP"),
+      Self::Even => write!(f, "E"),
+      Self::Odd => write!(f, "O"),
     }
   }
 }
 
+/// Operation of the machine, decoded from the opcode (C) and field (F) bytes
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operation {
+  Nop,
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Num,
+  Char,
+  Hlt,
+  Int,
+  Sla,
+  Sra,
+  Slax,
+  Srax,
+  Slc,
+  Src,
+  Move,
+  Ld(Target),
+  Ldn(Target),
+  St(Target),
+  Stj,
+  Stz,
+  Jbus,
+  Ioc,
+  In,
+  Out,
+  Jred,
+  Jmp,
+  Jsj,
+  Jov,
+  Jnov,
+  Jl,
+  Je,
+  Jg,
+  Jge,
+  Jne,
+  Jle,
+  Jump(Target, Condition),
+  Inc(Target),
+  Dec(Target),
+  Ent(Target),
+  Enn(Target),
+  Cmp(Target),
+  Fadd,
+  Fsub,
+  Fmul,
+  Fdiv,
+  Flot,
+  Fix,
+  Fcmp,
+  And,
+  Or,
+  Xor,
+  Slb,
+  Srb,
+}
+
+impl Operation {
+  /// Decodes the operation from the opcode and field bytes
+  pub fn decode(code: u32, field: u32) -> Result<Self, DecodeError> {
+    const SPECIAL: [Operation; 3] = [Operation::Num, Operation::Char, Operation::Hlt];
+
+    const SHIFTS: [Operation; 6] = [
+      Operation::Sla,
+      Operation::Sra,
+      Operation::Slax,
+      Operation::Srax,
+      Operation::Slc,
+      Operation::Src,
+    ];
+
+    const JUMPS: [Operation; 10] = [
+      Operation::Jmp,
+      Operation::Jsj,
+      Operation::Jov,
+      Operation::Jnov,
+      Operation::Jl,
+      Operation::Je,
+      Operation::Jg,
+      Operation::Jge,
+      Operation::Jne,
+      Operation::Jle,
+    ];
+
+    const ADDRESS_TRANSFERS: [fn(Target) -> Operation; 4] = [
+      Operation::Inc,
+      Operation::Dec,
+      Operation::Ent,
+      Operation::Enn,
+    ];
+
+    let operation = match (code, field) {
+      (0, _) => Self::Nop,
+      (1, 6) => Self::Fadd,
+      (2, 6) => Self::Fsub,
+      (3, 6) => Self::Fmul,
+      (4, 6) => Self::Fdiv,
+      (1, _) => Self::Add,
+      (2, _) => Self::Sub,
+      (3, _) => Self::Mul,
+      (4, _) => Self::Div,
+      (5, 0..=2) => SPECIAL[field as usize],
+      (5, 6) => Self::Flot,
+      (5, 7) => Self::Fix,
+      (5, 9) => Self::Int,
+      (5, 3) => Self::And,
+      (5, 4) => Self::Or,
+      (5, 5) => Self::Xor,
+      (6, 0..=5) => SHIFTS[field as usize],
+      (6, 6) => Self::Slb,
+      (6, 7) => Self::Srb,
+      (7, _) => Self::Move,
+      (8..=15, _) => Self::Ld(Target::from_offset(code - 8)),
+      (16..=23, _) => Self::Ldn(Target::from_offset(code - 16)),
+      (24..=31, _) => Self::St(Target::from_offset(code - 24)),
+      (32, _) => Self::Stj,
+      (33, _) => Self::Stz,
+      (34, _) => Self::Jbus,
+      (35, _) => Self::Ioc,
+      (36, _) => Self::In,
+      (37, _) => Self::Out,
+      (38, _) => Self::Jred,
+      (39, 0..=9) => JUMPS[field as usize],
+      (40..=47, 0..=5) | (40 | 47, 6..=7) => Self::Jump(
+        Target::from_offset(code - 40),
+        Condition::ALL[field as usize],
+      ),
+      (48..=55, 0..=3) => ADDRESS_TRANSFERS[field as usize](Target::from_offset(code - 48)),
+      (56, 6) => Self::Fcmp,
+      (56..=63, _) => Self::Cmp(Target::from_offset(code - 56)),
+      _ => return Err(DecodeError::InvalidOperation { code, field }),
+    };
+
+    Ok(operation)
+  }
+
+  /// Finds the operation by its name in MIXAL, such as `LDA` or `J1NZ`
+  pub fn from_mnemonic(name: &str) -> Option<Self> {
+    // Every operation is decoded from one of the fields 0-9
+    (0..64)
+      .flat_map(|code| (0..10).map(move |field| (code, field)))
+      .filter_map(|(code, field)| Self::decode(code, field).ok())
+      .find(|operation| operation.to_string() == name)
+  }
+
+  /// Opcode of the operation
+  pub fn code(self) -> u32 {
+    match self {
+      Self::Nop => 0,
+      Self::Add | Self::Fadd => 1,
+      Self::Sub | Self::Fsub => 2,
+      Self::Mul | Self::Fmul => 3,
+      Self::Div | Self::Fdiv => 4,
+      Self::Num
+      | Self::Char
+      | Self::Hlt
+      | Self::Int
+      | Self::Flot
+      | Self::Fix
+      | Self::And
+      | Self::Or
+      | Self::Xor => 5,
+      Self::Sla
+      | Self::Sra
+      | Self::Slax
+      | Self::Srax
+      | Self::Slc
+      | Self::Src
+      | Self::Slb
+      | Self::Srb => 6,
+      Self::Move => 7,
+      Self::Ld(target) => 8 + target.offset(),
+      Self::Ldn(target) => 16 + target.offset(),
+      Self::St(target) => 24 + target.offset(),
+      Self::Stj => 32,
+      Self::Stz => 33,
+      Self::Jbus => 34,
+      Self::Ioc => 35,
+      Self::In => 36,
+      Self::Out => 37,
+      Self::Jred => 38,
+      Self::Jmp
+      | Self::Jsj
+      | Self::Jov
+      | Self::Jnov
+      | Self::Jl
+      | Self::Je
+      | Self::Jg
+      | Self::Jge
+      | Self::Jne
+      | Self::Jle => 39,
+      Self::Jump(target, _) => 40 + target.offset(),
+      Self::Inc(target) | Self::Dec(target) | Self::Ent(target) | Self::Enn(target) => {
+        48 + target.offset()
+      }
+      Self::Cmp(target) => 56 + target.offset(),
+      Self::Fcmp => 56,
+    }
+  }
+
+  /// Field byte that selects the operation within its opcode, the other
+  /// operations take the field from the instruction
+  pub const fn field(self) -> Option<u32> {
+    match self {
+      Self::Num | Self::Sla | Self::Jmp | Self::Inc(_) => Some(0),
+      Self::Char | Self::Sra | Self::Jsj | Self::Dec(_) => Some(1),
+      Self::Hlt | Self::Slax | Self::Jov | Self::Ent(_) => Some(2),
+      Self::Srax | Self::Jnov | Self::Enn(_) | Self::And => Some(3),
+      Self::Slc | Self::Jl | Self::Or => Some(4),
+      Self::Src | Self::Je | Self::Xor => Some(5),
+      Self::Jg
+      | Self::Fadd
+      | Self::Fsub
+      | Self::Fmul
+      | Self::Fdiv
+      | Self::Flot
+      | Self::Fcmp
+      | Self::Slb => Some(6),
+      Self::Jge | Self::Fix | Self::Srb => Some(7),
+      Self::Jne => Some(8),
+      Self::Jle | Self::Int => Some(9),
+      Self::Jump(_, condition) => Some(condition as u32),
+      _ => None,
+    }
+  }
+
+  /// Execution time of the operation in units of time, the field is needed
+  /// for MOVE which takes two units per word moved
+  ///
+  /// This is the compute time only, so the I/O operations take one unit.
+  /// The computer adds the time a unit keeps the machine waiting: IN, OUT
+  /// and IOC on a unit still busy with its last operation wait on interlock
+  /// for the rest of its latency. That time, and the time JBUS finds the
+  /// unit busy, is counted apart in [`Timing::wait`](crate::timing::Timing)
+  pub fn time(self, field: u32) -> u64 {
+    match self {
+      Self::Nop => 1,
+      Self::Add | Self::Sub => 2,
+      Self::Mul => 10,
+      Self::Div => 12,
+      Self::Num | Self::Char | Self::Hlt => 10,
+      Self::Int => 2,
+      Self::Sla | Self::Sra | Self::Slax | Self::Srax | Self::Slc | Self::Src => 2,
+      Self::Move => 1 + 2 * field as u64,
+      Self::Ld(_) | Self::Ldn(_) | Self::St(_) | Self::Stj | Self::Stz => 2,
+      Self::Jbus | Self::Ioc | Self::In | Self::Out | Self::Jred => 1,
+      Self::Jmp
+      | Self::Jsj
+      | Self::Jov
+      | Self::Jnov
+      | Self::Jl
+      | Self::Je
+      | Self::Jg
+      | Self::Jge
+      | Self::Jne
+      | Self::Jle
+      | Self::Jump(_, _) => 1,
+      Self::Inc(_) | Self::Dec(_) | Self::Ent(_) | Self::Enn(_) => 1,
+      Self::Cmp(_) => 2,
+      Self::Fadd | Self::Fsub | Self::Fcmp => 4,
+      Self::Fmul => 9,
+      Self::Fdiv => 11,
+      Self::Flot | Self::Fix => 3,
+      Self::And | Self::Or | Self::Xor => 2,
+      Self::Slb | Self::Srb => 2,
+    }
+  }
+
+  /// Checks if the operation needs the floating point attachment
+  pub fn is_floating_point(self) -> bool {
+    matches!(
+      self,
+      Self::Fadd | Self::Fsub | Self::Fmul | Self::Fdiv | Self::Flot | Self::Fix | Self::Fcmp
+    )
+  }
+
+  /// Checks if the operation belongs to the binary extension of GNU MDK,
+  /// which works with the bits of the registers
+  pub fn is_binary(self) -> bool {
+    matches!(
+      self,
+      Self::And
+        | Self::Or
+        | Self::Xor
+        | Self::Slb
+        | Self::Srb
+        | Self::Jump(_, Condition::Even | Condition::Odd)
+    )
+  }
+
+  /// Checks if the field of the operation is an (L:R) specification, rather
+  /// than a count or a unit number
+  pub(crate) const fn has_field_spec(self) -> bool {
+    matches!(
+      self,
+      Self::Add
+        | Self::Sub
+        | Self::Mul
+        | Self::Div
+        | Self::Ld(_)
+        | Self::Ldn(_)
+        | Self::St(_)
+        | Self::Stj
+        | Self::Stz
+        | Self::Cmp(_)
+    )
+  }
+
+  /// Field an instruction takes when MIXAL gives none, the one of the
+  /// opcode for the operations that take their field from it
+  pub const fn default_field(self) -> u32 {
+    match (self, self.field()) {
+      (_, Some(field)) => field,
+      (Self::Stj, None) => 2,
+      (Self::Move, None) => 1,
+      _ if self.has_field_spec() => 5,
+      _ => 0,
+    }
+  }
+}
+
+impl fmt::Display for Operation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Ld(target) => write!(f, "LD{}", target),
+      Self::Ldn(target) => write!(f, "LD{}N", target),
+      Self::St(target) => write!(f, "ST{}", target),
+      Self::Jump(target, condition) => write!(f, "J{}{}", target, condition),
+      Self::Inc(target) => write!(f, "INC{}", target),
+      Self::Dec(target) => write!(f, "DEC{}", target),
+      Self::Ent(target) => write!(f, "ENT{}", target),
+      Self::Enn(target) => write!(f, "ENN{}", target),
+      Self::Cmp(target) => write!(f, "CMP{}", target),
+      _ => write!(f, "{}", format!("{:?}", self).to_uppercase()),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Instruction {
   pub sign: bool,
   pub address: u32,
   pub index: u32,
   pub modifier: u32,
-  pub command: Command,
+  pub operation: Operation,
 }
 
 impl Instruction {
@@ -50,44 +460,178 @@ impl Instruction {
   #[rustfmt::skip]
   const SIGN_MASK:     u32 = 0b0100_0000_0000_0000_0000_0000_0000_0000;
 
-  pub fn new(sign: bool, address: u32, index: u32, modifier: u32, command: Command) -> Self {
+  /// Creates the instruction, the modifier is ignored if the operation
+  /// determines the field by itself
+  pub fn new(sign: bool, address: u32, index: u32, modifier: u32, operation: Operation) -> Self {
     Self {
       sign,
       address,
       index,
+      modifier: operation.field().unwrap_or(modifier),
+      operation,
+    }
+  }
+
+  /// Creates the instruction from its parts the way MIXAL writes them, the
+  /// address with its sign and no field for the default one of the
+  /// operation
+  ///
+  /// Panics if the address does not fit two bytes of a binary machine, the
+  /// index is not 0-6, or the field is not the one the operation takes from
+  /// its opcode or an (L:R) specification for an operation on a part of a
+  /// word, so that a mistake in a constant stops the build.
+  pub const fn from_parts(
+    operation: Operation,
+    address: i64,
+    index: i64,
+    field: Option<i64>,
+  ) -> Self {
+    let modifier = match field {
+      Some(field) => field,
+      None => operation.default_field() as i64,
+    };
+
+    assert!(address > -4096 && address < 4096, "Address does not fit");
+    assert!(index >= 0 && index <= 6, "Index is not one of rI1-rI6");
+    assert!(modifier >= 0 && modifier < 64, "Field does not fit");
+
+    if let Some(own) = operation.field() {
+      assert!(modifier == own as i64, "Operation takes another field");
+    }
+
+    if operation.has_field_spec() {
+      assert!(
+        modifier / 8 <= modifier % 8 && modifier % 8 <= 5,
+        "Field is not an (L:R) specification"
+      );
+    }
+
+    Self {
+      sign: address >= 0,
+      address: address.unsigned_abs() as u32,
+      index: index as u32,
+      modifier: modifier as u32,
+      operation,
+    }
+  }
+
+  /// Checks the index and the field, which the operation alone does not
+  /// determine
+  fn validate(self) -> Result<Self, DecodeError> {
+    if self.index > 6 {
+      return Err(DecodeError::InvalidIndex(self.index));
+    }
+
+    if self.operation.has_field_spec() {
+      self.field_spec()?;
+    }
+
+    Ok(self)
+  }
+
+  /// Reads the field of the instruction as an (L:R) specification, for the
+  /// operations that work on a field of a word
+  pub fn field_spec(&self) -> Result<FieldSpec, DecodeError> {
+    FieldSpec::try_from(self.modifier)
+  }
+
+  /// Decodes the instruction from a word of the machine with the byte size,
+  /// the address takes bytes 1-2 as a number
+  pub fn decode(word: &Word, byte_size: ByteSize) -> Result<Self, DecodeError> {
+    let modifier = word.get_byte(4) as u32;
+    let address = word.get_byte(1) as u32 * byte_size.get() + word.get_byte(2) as u32;
+
+    Self {
+      sign: word.read_sign(),
+      address,
+      index: word.get_byte(3) as u32,
       modifier,
-      command,
+      operation: Operation::decode(word.get_byte(5) as u32, modifier)?,
+    }
+    .validate()
+  }
+
+  /// Encodes the instruction into a word of the machine with the byte size,
+  /// the parts that do not fit are truncated
+  pub fn encode(&self, byte_size: ByteSize) -> Word {
+    let [high, low] = byte_size.bytes(self.address as u64);
+    let byte = |value: u32| (value % byte_size.get()) as u8;
+
+    Word::from_bytes(
+      [
+        high,
+        low,
+        byte(self.index),
+        byte(self.modifier),
+        byte(self.operation.code()),
+      ],
+      self.sign,
+    )
+  }
+}
+
+/// Shows the instruction the way it is written in MIXAL, such as
+/// `LDA -2000,1(1:3)`, the index and a whole word field are left out
+///
+/// The text assembles back to the same instruction: STJ shows its whole
+/// word field, as it defaults to (0:2), and NOP shows any field but 0.
+impl fmt::Display for Instruction {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let sign = if self.sign { "" } else { "-" };
+
+    write!(f, "{} {}{}", self.operation, sign, self.address)?;
+
+    if self.index != 0 {
+      write!(f, ",{}", self.index)?;
+    }
+
+    if self.operation.field().is_some() || (self.operation == Operation::Nop && self.modifier == 0)
+    {
+      return Ok(());
+    }
+
+    match self.field_spec() {
+      Ok(FieldSpec::WHOLE)
+        if self.operation.has_field_spec() && self.operation != Operation::Stj =>
+      {
+        Ok(())
+      }
+      Ok(field) if self.operation.has_field_spec() => write!(f, "{}", field),
+      _ => write!(f, "({})", self.modifier),
     }
   }
 }
 
 impl From<Instruction> for u32 {
   fn from(value: Instruction) -> u32 {
-    (u32::from(value.command) & 0b111111)
+    (value.operation.code() & 0b111111)
       | ((value.modifier & 0b111111) << 6)
       | ((value.index & 0b111111) << 12)
-      | ((value.address & 0b1111111111111) << 18)
+      | ((value.address & 0b111111111111) << 18)
       | ((value.sign as u32) << 30)
   }
 }
 
-impl From<u32> for Instruction {
-  fn from(value: u32) -> Self {
+impl TryFrom<u32> for Instruction {
+  type Error = DecodeError;
+
+  fn try_from(value: u32) -> Result<Self, Self::Error> {
+    let modifier = (value & Self::MODIFIER_MASK) >> 6;
+
     Self {
-      command: Command::from(value & Self::COMMAND_MASK),
-      modifier: (value & Self::MODIFIER_MASK) >> 6,
+      operation: Operation::decode(value & Self::COMMAND_MASK, modifier)?,
+      modifier,
       index: (value & Self::INDEX_MASK) >> 12,
       address: (value & Self::ADDRESS_MASK) >> 18,
       sign: (value & Self::SIGN_MASK) != 0,
     }
+    .validate()
   }
 }
 
 impl From<Instruction> for Word {
   fn from(value: Instruction) -> Self {
-    let sign = Some(value.sign);
-
-    Word::new(u32::from(value), sign)
+    value.encode(ByteSize::BINARY)
   }
 }
 
@@ -98,15 +642,11 @@ impl From<&Instruction> for Word {
   }
 }
 
-impl From<Word> for Instruction {
-  fn from(value: Word) -> Self {
-    Self {
-      sign: value.read_with_modifier(0) != 0,
-      address: value.read_with_modifier(12),
-      index: value.read_with_modifier(33),
-      modifier: value.read_with_modifier(44),
-      command: Command::from(value.read_with_modifier(55)),
-    }
+impl TryFrom<Word> for Instruction {
+  type Error = DecodeError;
+
+  fn try_from(value: Word) -> Result<Self, Self::Error> {
+    Self::decode(&value, ByteSize::BINARY)
   }
 }
 
@@ -115,39 +655,324 @@ mod tests {
   use rstest::rstest;
   use rstest_reuse::{self, *};
 
-  use crate::Data;
+  use crate::FieldRead;
 
   use super::*;
 
   #[template]
   #[rstest]
-  #[case(Command::Noop, 0)]
-  #[case(Command::Lda, 8)]
-  fn from_command_cases(#[case] command: Command, #[case] expected: u32) {}
+  #[case(Operation::Nop, 0, 0)]
+  #[case(Operation::Add, 1, 5)]
+  #[case(Operation::Sub, 2, 13)]
+  #[case(Operation::Mul, 3, 5)]
+  #[case(Operation::Div, 4, 5)]
+  #[case(Operation::Num, 5, 0)]
+  #[case(Operation::Char, 5, 1)]
+  #[case(Operation::Hlt, 5, 2)]
+  #[case(Operation::Int, 5, 9)]
+  #[case(Operation::Sla, 6, 0)]
+  #[case(Operation::Sra, 6, 1)]
+  #[case(Operation::Slax, 6, 2)]
+  #[case(Operation::Srax, 6, 3)]
+  #[case(Operation::Slc, 6, 4)]
+  #[case(Operation::Src, 6, 5)]
+  #[case(Operation::Move, 7, 10)]
+  #[case(Operation::Ld(Target::A), 8, 5)]
+  #[case(Operation::Ld(Target::I1), 9, 5)]
+  #[case(Operation::Ld(Target::I6), 14, 5)]
+  #[case(Operation::Ld(Target::X), 15, 5)]
+  #[case(Operation::Ldn(Target::A), 16, 5)]
+  #[case(Operation::Ldn(Target::I3), 19, 5)]
+  #[case(Operation::Ldn(Target::X), 23, 5)]
+  #[case(Operation::St(Target::A), 24, 5)]
+  #[case(Operation::St(Target::I2), 26, 5)]
+  #[case(Operation::St(Target::X), 31, 5)]
+  #[case(Operation::Stj, 32, 2)]
+  #[case(Operation::Stz, 33, 5)]
+  #[case(Operation::Jbus, 34, 16)]
+  #[case(Operation::Ioc, 35, 0)]
+  #[case(Operation::In, 36, 16)]
+  #[case(Operation::Out, 37, 18)]
+  #[case(Operation::Jred, 38, 16)]
+  #[case(Operation::Jmp, 39, 0)]
+  #[case(Operation::Jsj, 39, 1)]
+  #[case(Operation::Jov, 39, 2)]
+  #[case(Operation::Jnov, 39, 3)]
+  #[case(Operation::Jl, 39, 4)]
+  #[case(Operation::Je, 39, 5)]
+  #[case(Operation::Jg, 39, 6)]
+  #[case(Operation::Jge, 39, 7)]
+  #[case(Operation::Jne, 39, 8)]
+  #[case(Operation::Jle, 39, 9)]
+  #[case(Operation::Jump(Target::A, Condition::Negative), 40, 0)]
+  #[case(Operation::Jump(Target::A, Condition::NonPositive), 40, 5)]
+  #[case(Operation::Jump(Target::I1, Condition::Zero), 41, 1)]
+  #[case(Operation::Jump(Target::I6, Condition::NonZero), 46, 4)]
+  #[case(Operation::Jump(Target::X, Condition::Positive), 47, 2)]
+  #[case(Operation::Jump(Target::X, Condition::NonNegative), 47, 3)]
+  #[case(Operation::Inc(Target::A), 48, 0)]
+  #[case(Operation::Dec(Target::A), 48, 1)]
+  #[case(Operation::Ent(Target::A), 48, 2)]
+  #[case(Operation::Enn(Target::A), 48, 3)]
+  #[case(Operation::Inc(Target::I4), 52, 0)]
+  #[case(Operation::Enn(Target::I5), 53, 3)]
+  #[case(Operation::Ent(Target::X), 55, 2)]
+  #[case(Operation::Cmp(Target::A), 56, 5)]
+  #[case(Operation::Cmp(Target::I1), 57, 5)]
+  #[case(Operation::Cmp(Target::X), 63, 5)]
+  #[case(Operation::Fadd, 1, 6)]
+  #[case(Operation::Fsub, 2, 6)]
+  #[case(Operation::Fmul, 3, 6)]
+  #[case(Operation::Fdiv, 4, 6)]
+  #[case(Operation::Flot, 5, 6)]
+  #[case(Operation::Fix, 5, 7)]
+  #[case(Operation::Fcmp, 56, 6)]
+  #[case(Operation::And, 5, 3)]
+  #[case(Operation::Or, 5, 4)]
+  #[case(Operation::Xor, 5, 5)]
+  #[case(Operation::Slb, 6, 6)]
+  #[case(Operation::Srb, 6, 7)]
+  #[case(Operation::Jump(Target::A, Condition::Even), 40, 6)]
+  #[case(Operation::Jump(Target::A, Condition::Odd), 40, 7)]
+  #[case(Operation::Jump(Target::X, Condition::Even), 47, 6)]
+  #[case(Operation::Jump(Target::X, Condition::Odd), 47, 7)]
+  fn operation_cases(#[case] operation: Operation, #[case] code: u32, #[case] field: u32) {}
 
   #[rustfmt::skip]
   #[template]
   #[rstest]
-  #[case(true, 2000, 0, 0, Command::Lda, 0b0101_1111_0100_0000_0000_0000_0000_1000)]
-  #[case(false, 2000, 0, 0, Command::Lda, 0b0001_1111_0100_0000_0000_0000_0000_1000)]
+  #[case(Operation::Ld(Target::A), "LDA")]
+  #[case(Operation::Ld(Target::I2), "LD2")]
+  #[case(Operation::Ldn(Target::X), "LDXN")]
+  #[case(Operation::Ldn(Target::I6), "LD6N")]
+  #[case(Operation::St(Target::I1), "ST1")]
+  #[case(Operation::Stj, "STJ")]
+  #[case(Operation::Jump(Target::A, Condition::Negative), "JAN")]
+  #[case(Operation::Jump(Target::I3, Condition::NonPositive), "J3NP")]
+  #[case(Operation::Jump(Target::X, Condition::NonZero), "JXNZ")]
+  #[case(Operation::Inc(Target::I1), "INC1")]
+  #[case(Operation::Dec(Target::X), "DECX")]
+  #[case(Operation::Ent(Target::A), "ENTA")]
+  #[case(Operation::Enn(Target::I4), "ENN4")]
+  #[case(Operation::Cmp(Target::I5), "CMP5")]
+  #[case(Operation::Nop, "NOP")]
+  #[case(Operation::Hlt, "HLT")]
+  #[case(Operation::Int, "INT")]
+  #[case(Operation::Slax, "SLAX")]
+  #[case(Operation::Jnov, "JNOV")]
+  #[case(Operation::Jbus, "JBUS")]
+  #[case(Operation::Fadd, "FADD")]
+  #[case(Operation::Fix, "FIX")]
+  #[case(Operation::Fcmp, "FCMP")]
+  #[case(Operation::Xor, "XOR")]
+  #[case(Operation::Srb, "SRB")]
+  #[case(Operation::Jump(Target::A, Condition::Even), "JAE")]
+  #[case(Operation::Jump(Target::X, Condition::Odd), "JXO")]
+  fn operation_name_cases(#[case] operation: Operation, #[case] expected: &str) {}
+
+  #[rustfmt::skip]
+  #[template]
+  #[rstest]
+  #[case(true, 2000, 0, 0, Operation::Ld(Target::A), 0b0101_1111_0100_0000_0000_0000_0000_1000)]
+  #[case(false, 2000, 0, 0, Operation::Ld(Target::A), 0b0001_1111_0100_0000_0000_0000_0000_1000)]
+  #[case(true, 4095, 6, 5, Operation::Ld(Target::X), 0b0111_1111_1111_1100_0110_0001_0100_1111)]
+  #[case(false, 1, 1, 13, Operation::St(Target::A), 0b0000_0000_0000_0100_0001_0011_0101_1000)]
   fn from_instruction_cases(
     #[case] sign: bool,
     #[case] address: u32,
     #[case] index: u32,
     #[case] modifier: u32,
-    #[case] command: Command,
+    #[case] operation: Operation,
     #[case] expected: u32,
   ) {
   }
 
-  #[apply(from_command_cases)]
-  fn test_u32_from_command(command: Command, expected: u32) {
-    assert_eq!(Command::from(expected), command);
+  #[apply(operation_cases)]
+  fn test_decode_operation(operation: Operation, code: u32, field: u32) {
+    assert_eq!(Operation::decode(code, field), Ok(operation));
+  }
+
+  #[apply(operation_cases)]
+  fn test_operation_code(operation: Operation, code: u32, field: u32) {
+    assert_eq!(operation.code(), code);
+    assert_eq!(operation.field().unwrap_or(field), field);
+  }
+
+  #[test]
+  fn test_decode_every_operation() {
+    for code in 0..64 {
+      for field in 0..64 {
+        let valid = match code {
+          5 => field <= 7 || field == 9,
+          6 => field <= 7,
+          39 => field <= 9,
+          40 | 47 => field <= 7,
+          41..=46 => field <= 5,
+          48..=55 => field <= 3,
+          _ => true,
+        };
+
+        match Operation::decode(code, field) {
+          Ok(operation) => {
+            assert!(valid);
+            assert_eq!(operation.code(), code);
+            assert_eq!(operation.field().unwrap_or(field), field);
+          }
+          Err(error) => {
+            assert!(!valid);
+            assert_eq!(error, DecodeError::InvalidOperation { code, field });
+          }
+        }
+      }
+    }
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0b0000_0000_0000_0000_0000_0010_0000_0101, DecodeError::InvalidOperation { code: 5, field: 8 })]
+  #[case(0b0000_0000_0000_0000_0000_1010_0010_0111, DecodeError::InvalidOperation { code: 39, field: 40 })]
+  #[case(0b0000_0000_0000_0000_0000_0001_1011_0000, DecodeError::InvalidOperation { code: 48, field: 6 })]
+  #[case(0b0000_0000_0000_0000_0111_0001_0100_1000, DecodeError::InvalidIndex(7))]
+  #[case(0b0000_0000_0000_0011_1111_0000_0000_0000, DecodeError::InvalidIndex(63))]
+  #[case(0b0000_0000_0000_0000_0000_0001_1100_1000, DecodeError::InvalidField(7))]
+  #[case(0b0000_0000_0000_0000_0000_0101_1000_1000, DecodeError::InvalidField(22))]
+  #[case(0b0000_0000_0000_0000_0000_1100_1010_0001, DecodeError::InvalidField(50))]
+  #[case(0b0000_0000_0000_0000_0000_1001_1101_1000, DecodeError::InvalidField(39))]
+  fn test_instruction_decode_error(#[case] value: u32, #[case] expected: DecodeError) {
+    assert_eq!(Instruction::try_from(value), Err(expected));
+    assert_eq!(Instruction::try_from(Word::from(value)), Err(expected));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0b0000_0000_0000_0000_0000_0000_0100_0111, Operation::Move)]
+  #[case(0b0000_0000_0000_0000_0000_0100_1010_0100, Operation::In)]
+  #[case(0b0000_0000_0000_0000_0000_1111_1110_0011, Operation::Ioc)]
+  fn test_instruction_field_without_spec(#[case] value: u32, #[case] operation: Operation) {
+    assert_eq!(
+      Instruction::try_from(value).map(|instruction| instruction.operation),
+      Ok(operation)
+    );
+  }
+
+  #[rstest]
+  #[case(Operation::Nop, 0, 1)]
+  #[case(Operation::Add, 5, 2)]
+  #[case(Operation::Sub, 5, 2)]
+  #[case(Operation::Mul, 5, 10)]
+  #[case(Operation::Div, 5, 12)]
+  #[case(Operation::Num, 0, 10)]
+  #[case(Operation::Hlt, 2, 10)]
+  #[case(Operation::Srax, 3, 2)]
+  #[case(Operation::Move, 0, 1)]
+  #[case(Operation::Move, 1, 3)]
+  #[case(Operation::Move, 10, 21)]
+  #[case(Operation::Ld(Target::A), 5, 2)]
+  #[case(Operation::Ldn(Target::I4), 5, 2)]
+  #[case(Operation::St(Target::X), 5, 2)]
+  #[case(Operation::Stj, 2, 2)]
+  #[case(Operation::Stz, 5, 2)]
+  #[case(Operation::Jbus, 16, 1)]
+  #[case(Operation::Ioc, 18, 1)]
+  #[case(Operation::In, 16, 1)]
+  #[case(Operation::Out, 18, 1)]
+  #[case(Operation::Jmp, 0, 1)]
+  #[case(Operation::Jump(Target::I2, Condition::Zero), 1, 1)]
+  #[case(Operation::Ent(Target::A), 2, 1)]
+  #[case(Operation::Dec(Target::X), 1, 1)]
+  #[case(Operation::Cmp(Target::I1), 5, 2)]
+  #[case(Operation::Fadd, 6, 4)]
+  #[case(Operation::Fmul, 6, 9)]
+  #[case(Operation::Fdiv, 6, 11)]
+  #[case(Operation::Fix, 7, 3)]
+  #[case(Operation::Fcmp, 6, 4)]
+  fn test_operation_time(#[case] operation: Operation, #[case] field: u32, #[case] expected: u64) {
+    assert_eq!(operation.time(field), expected);
+  }
+
+  #[apply(operation_name_cases)]
+  fn test_operation_display(operation: Operation, expected: &str) {
+    assert_eq!(operation.to_string(), expected);
+  }
+
+  #[apply(operation_name_cases)]
+  fn test_from_mnemonic(operation: Operation, expected: &str) {
+    assert_eq!(Operation::from_mnemonic(expected), Some(operation));
   }
 
-  #[apply(from_command_cases)]
-  fn test_command_from_u32(command: Command, expected: u32) {
-    assert_eq!(u32::from(command), expected);
+  #[rstest]
+  #[case("LDAX")]
+  #[case("lda")]
+  #[case("")]
+  fn test_from_mnemonic_unknown(#[case] name: &str) {
+    assert_eq!(Operation::from_mnemonic(name), None);
+  }
+
+  #[rstest]
+  #[case(5, Ok(FieldSpec::WHOLE))]
+  #[case(11, Ok(FieldSpec::new(1, 3)))]
+  #[case(45, Ok(FieldSpec::new(5, 5)))]
+  #[case(29, Ok(FieldSpec::new(3, 5)))]
+  #[case(7, Err(DecodeError::InvalidField(7)))]
+  #[case(55, Err(DecodeError::InvalidField(55)))]
+  fn test_field_spec(#[case] modifier: u32, #[case] expected: Result<FieldSpec, DecodeError>) {
+    let instruction = Instruction::new(true, 0, 0, modifier, Operation::Ld(Target::A));
+
+    assert_eq!(instruction.field_spec(), expected);
+  }
+
+  #[rstest]
+  #[case(
+    Instruction::new(true, 2000, 0, 5, Operation::Ld(Target::A)),
+    "LDA 2000"
+  )]
+  #[case(
+    Instruction::new(false, 2000, 1, 11, Operation::Ld(Target::A)),
+    "LDA -2000,1(1:3)"
+  )]
+  #[case(Instruction::new(true, 1000, 0, 2, Operation::Stj), "STJ 1000(0:2)")]
+  #[case(Instruction::new(true, 1000, 0, 16, Operation::In), "IN 1000(16)")]
+  #[case(Instruction::new(true, 3, 2, 0, Operation::Move), "MOVE 3,2(0)")]
+  #[case(Instruction::new(true, 1, 0, 0, Operation::Dec(Target::I2)), "DEC2 1")]
+  #[case(Instruction::new(true, 0, 0, 0, Operation::Hlt), "HLT 0")]
+  #[case(Instruction::new(true, 0, 0, 0, Operation::Nop), "NOP 0")]
+  #[case(Instruction::new(true, 0, 0, 9, Operation::Nop), "NOP 0(9)")]
+  #[case(Instruction::new(true, 1000, 0, 5, Operation::Stj), "STJ 1000(0:5)")]
+  #[case(Instruction::new(false, 0, 3, 5, Operation::Jmp), "JMP -0,3")]
+  fn test_instruction_display(#[case] instruction: Instruction, #[case] expected: &str) {
+    assert_eq!(instruction.to_string(), expected);
+  }
+
+  #[test]
+  fn test_new_takes_field_from_operation() {
+    assert_eq!(Instruction::new(true, 0, 0, 5, Operation::Hlt).modifier, 2);
+    assert_eq!(Instruction::new(true, 0, 0, 5, Operation::Jmp).modifier, 0);
+    assert_eq!(
+      Instruction::new(true, 0, 0, 13, Operation::Ld(Target::A)).modifier,
+      13
+    );
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, 3999, [62, 31, 2, 5, 8])]
+  #[case(ByteSize::DECIMAL, 3999, [39, 99, 2, 5, 8])]
+  #[case(ByteSize::DECIMAL, 64, [0, 64, 2, 5, 8])]
+  fn test_encode_decode(#[case] byte_size: ByteSize, #[case] address: u32, #[case] bytes: [u8; 5]) {
+    let instruction = Instruction::new(false, address, 2, 5, Operation::Ld(Target::A));
+    let word = Word::from_bytes(bytes, false);
+
+    assert_eq!(instruction.encode(byte_size), word);
+    assert_eq!(Instruction::decode(&word, byte_size), Ok(instruction));
+  }
+
+  #[test]
+  fn test_decode_decimal_opcode() {
+    let word = Word::from_bytes([0, 0, 0, 5, 64], true);
+
+    assert_eq!(
+      Instruction::decode(&word, ByteSize::DECIMAL),
+      Err(DecodeError::InvalidOperation { code: 64, field: 5 })
+    );
   }
 
   #[apply(from_instruction_cases)]
@@ -156,11 +981,11 @@ mod tests {
     address: u32,
     index: u32,
     modifier: u32,
-    command: Command,
+    operation: Operation,
     expected: u32,
   ) {
     assert_eq!(
-      u32::from(Instruction::new(sign, address, index, modifier, command)),
+      u32::from(Instruction::new(sign, address, index, modifier, operation)),
       expected
     );
   }
@@ -171,12 +996,12 @@ mod tests {
     address: u32,
     index: u32,
     modifier: u32,
-    command: Command,
+    operation: Operation,
     expected: u32,
   ) {
     assert_eq!(
-      Instruction::from(expected),
-      Instruction::new(sign, address, index, modifier, command)
+      Instruction::try_from(expected),
+      Ok(Instruction::new(sign, address, index, modifier, operation))
     );
   }
 
@@ -186,17 +1011,32 @@ mod tests {
     address: u32,
     index: u32,
     modifier: u32,
-    command: Command,
+    operation: Operation,
     expected: u32,
   ) {
-    let instruction = Instruction::from(expected);
+    let instruction = Instruction::try_from(expected).unwrap();
     let word = Word::from(instruction);
 
-    assert_eq!(word.read_with_modifier(0), sign as u32);
-    assert_eq!(word.read_with_modifier(12), address);
-    assert_eq!(word.read_with_modifier(33), index);
-    assert_eq!(word.read_with_modifier(44), modifier);
-    assert_eq!(word.read_with_modifier(55), u32::from(command));
+    assert_eq!(
+      word.read_with_modifier(FieldSpec::new(0, 0)).unwrap(),
+      sign as u32
+    );
+    assert_eq!(
+      word.read_with_modifier(FieldSpec::new(1, 2)).unwrap(),
+      address
+    );
+    assert_eq!(
+      word.read_with_modifier(FieldSpec::new(3, 3)).unwrap(),
+      index
+    );
+    assert_eq!(
+      word.read_with_modifier(FieldSpec::new(4, 4)).unwrap(),
+      modifier
+    );
+    assert_eq!(
+      word.read_with_modifier(FieldSpec::new(5, 5)).unwrap(),
+      operation.code()
+    );
   }
 
   #[apply(from_instruction_cases)]
@@ -205,10 +1045,10 @@ mod tests {
     address: u32,
     index: u32,
     modifier: u32,
-    command: Command,
+    operation: Operation,
     expected: u32,
   ) {
-    let instruction = Instruction::new(sign, address, index, modifier, command);
+    let instruction = Instruction::new(sign, address, index, modifier, operation);
 
     assert_eq!(Word::from(instruction).read(), expected);
   }
@@ -219,14 +1059,14 @@ mod tests {
     address: u32,
     index: u32,
     modifier: u32,
-    command: Command,
+    operation: Operation,
     expected: u32,
   ) {
     let word = Word::new(expected, Some(sign));
 
     assert_eq!(
-      Instruction::from(word),
-      Instruction::new(sign, address, index, modifier, command)
+      Instruction::try_from(word),
+      Ok(Instruction::new(sign, address, index, modifier, operation))
     );
   }
 }
@@ -0,0 +1,389 @@
+use crate::{
+  computer::{Compare, Computer},
+  register::Register,
+  word::Word,
+  Data, Signed,
+};
+
+/// Number of words in the control memory, at locations -3999 through -1
+pub const CONTROL_MEMORY: usize = 3999;
+
+/// Location of the clock, which counts down the units of time spent in the
+/// normal state while it is positive
+pub const CLOCK: i64 = -10;
+
+/// Location control goes to when the clock runs down to zero
+pub const CLOCK_INTERRUPT: i64 = -11;
+
+/// Location control goes to when the program executes INT
+pub const PROGRAM_INTERRUPT: i64 = -12;
+
+/// First of the locations -9 through -1 that keep the state of the program
+/// while an interrupt is served
+pub const SAVE_AREA: i64 = -9;
+
+/// Returns the location control goes to when the unit completes an operation
+pub fn device_interrupt(unit: u32) -> i64 {
+  -20 - unit as i64
+}
+
+/// State of the machine with the interrupt facility attached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+  /// The program runs as usual and the control memory is out of reach
+  Normal,
+  /// An interrupt is being served, further interrupts wait until INT
+  Control,
+}
+
+impl Computer {
+  /// Requests an interrupt, control goes to the location as soon as the
+  /// machine is in the normal state
+  pub fn interrupt(&mut self, location: i64) {
+    if self.interrupts {
+      self.pending.push_back(location);
+    }
+  }
+
+  /// Serves the first pending interrupt if the machine is in the normal state
+  pub(crate) fn serve_interrupt(&mut self) {
+    if self.state != State::Normal {
+      return;
+    }
+
+    if let Some(location) = self.pending.pop_front() {
+      self.enter_control(location);
+    }
+  }
+
+  /// Saves the state of the program and goes to the location in the control
+  /// state
+  ///
+  /// The registers rA, rI1-rI6 and rX are saved at locations -9 through -2.
+  /// Location -1 keeps the location counter in bytes 1-2, the overflow toggle
+  /// and the comparison indicator in byte 3 and rJ in bytes 4-5.
+  pub(crate) fn enter_control(&mut self, location: i64) {
+    let registers = [
+      self.a,
+      Word::from(self.ri[0]),
+      Word::from(self.ri[1]),
+      Word::from(self.ri[2]),
+      Word::from(self.ri[3]),
+      Word::from(self.ri[4]),
+      Word::from(self.ri[5]),
+      self.x,
+    ];
+
+    for (offset, register) in registers.into_iter().enumerate() {
+      *self.cell_mut(SAVE_AREA + offset as i64) = register;
+    }
+
+    let comparison = match self.comparison {
+      Compare::None => 0,
+      Compare::Less => 1,
+      Compare::Equal => 2,
+      Compare::Greater => 3,
+    };
+    let flags = (self.overflow() as u8) << 2 | comparison;
+    let [high, low] = self.byte_size.bytes(self.pc as u64);
+
+    *self.cell_mut(-1) = Word::from_bytes(
+      [high, low, flags, self.j.get_byte(1), self.j.get_byte(2)],
+      true,
+    );
+
+    self.state = State::Control;
+    self.pc = location;
+  }
+
+  /// Restores the state of the program saved by the interrupt and goes back
+  /// to the normal state
+  pub(crate) fn leave_control(&mut self) {
+    let registers: Vec<Word> = (0..8)
+      .map(|offset| *self.cell(SAVE_AREA + offset))
+      .collect();
+
+    self.a = registers[0];
+    for (register, word) in self.ri.iter_mut().zip(&registers[1..7]) {
+      *register = Register::from(*word);
+    }
+    self.x = registers[7];
+
+    let saved = *self.cell(-1);
+    let flags = saved.get_byte(3);
+
+    if flags & 0b100 != 0 {
+      self.set_overflow();
+    } else {
+      self.clear_overflow();
+    }
+
+    self.comparison = match flags & 0b11 {
+      0 => Compare::None,
+      1 => Compare::Less,
+      2 => Compare::Equal,
+      _ => Compare::Greater,
+    };
+    self.j = Register::from_bytes([saved.get_byte(4), saved.get_byte(5)], true);
+    self.pc = saved.get_byte(1) as i64 * self.byte_size.get() as i64 + saved.get_byte(2) as i64;
+    self.state = State::Normal;
+  }
+
+  /// Counts the time down on the clock, an interrupt is requested when the
+  /// clock runs down to zero
+  pub(crate) fn tick(&mut self, time: u64) {
+    if self.state != State::Normal {
+      return;
+    }
+
+    let byte_size = self.byte_size;
+    let clock = self.cell_mut(CLOCK);
+    let remaining = byte_size.magnitude(clock);
+
+    if !clock.read_sign() || remaining == 0 {
+      return;
+    }
+
+    *clock = byte_size.word(remaining.saturating_sub(time), true);
+
+    if remaining <= time {
+      self.interrupt(CLOCK_INTERRUPT);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    computer::HaltReason,
+    device::MemoryUnit,
+    error::MixError,
+    instruction::{Instruction, Operation, Target},
+    program::Program,
+  };
+
+  fn hlt() -> Instruction {
+    Instruction::new(true, 0, 0, 0, Operation::Hlt)
+  }
+
+  fn int() -> Instruction {
+    Instruction::new(true, 0, 0, 0, Operation::Int)
+  }
+
+  /// Places the handler into the control memory from the start and a jump to
+  /// it at the location, as the locations next to it are taken
+  fn load_handler(computer: &mut Computer, location: i64, start: u32, handler: &[Instruction]) {
+    let jump = Instruction::new(false, start, 0, 0, Operation::Jmp);
+
+    *computer.cell_mut(location) = Word::from(jump);
+
+    for (offset, instruction) in handler.iter().enumerate() {
+      *computer.cell_mut(offset as i64 - start as i64) = Word::from(instruction);
+    }
+  }
+
+  #[test]
+  fn test_program_interrupt() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.interrupts = true;
+    computer.a = Word::new(5, Some(false));
+    computer.ri[2] = Register::new(7, Some(true));
+    computer.x = Word::new(9, Some(true));
+    computer.set_overflow();
+
+    // The handler keeps the saved rA and changes the registers it uses
+    load_handler(
+      &mut computer,
+      PROGRAM_INTERRUPT,
+      1000,
+      &[
+        Instruction::new(false, 9, 0, 5, Operation::Ld(Target::X)),
+        Instruction::new(true, 1000, 0, 5, Operation::St(Target::X)),
+        Instruction::new(true, 1, 0, 0, Operation::Ent(Target::A)),
+        Instruction::new(true, 1, 0, 0, Operation::Ent(Target::I3)),
+        int(),
+      ],
+    );
+
+    program.add(int());
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.state, State::Normal);
+    assert_eq!(computer.pc, 2);
+    assert_eq!(computer.a, Word::new(5, Some(false)));
+    assert_eq!(computer.ri[2], Register::new(7, Some(true)));
+    assert_eq!(computer.x, Word::new(9, Some(true)));
+    assert!(computer.overflow());
+    assert_eq!(computer.memory[1000], Word::new(5, Some(false)));
+  }
+
+  #[test]
+  fn test_saved_state() {
+    let mut computer = Computer::new();
+
+    computer.interrupts = true;
+    computer.pc = 1234;
+    computer.j = Register::new(321, Some(true));
+    computer.comparison = Compare::Greater;
+    computer.set_overflow();
+    computer.ri[5] = Register::new(6, Some(false));
+    computer.enter_control(-100);
+
+    assert_eq!(computer.state, State::Control);
+    assert_eq!(computer.pc, -100);
+    assert_eq!(*computer.cell(-3), Word::new(6, Some(false)));
+    assert_eq!(
+      *computer.cell(-1),
+      Word::new(1234 << 18 | 0b000111 << 12 | 321, Some(true))
+    );
+
+    computer.clear_overflow();
+    computer.comparison = Compare::None;
+    computer.leave_control();
+
+    assert_eq!(computer.pc, 1234);
+    assert_eq!(computer.j, Register::new(321, Some(true)));
+    assert_eq!(computer.comparison, Compare::Greater);
+    assert!(computer.overflow());
+  }
+
+  #[test]
+  fn test_device_interrupt() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.interrupts = true;
+    load_handler(
+      &mut computer,
+      device_interrupt(18),
+      1000,
+      &[
+        Instruction::new(true, 2000, 0, 5, Operation::Ld(Target::A)),
+        Instruction::new(true, 1, 0, 0, Operation::Inc(Target::A)),
+        Instruction::new(true, 2000, 0, 5, Operation::St(Target::A)),
+        int(),
+      ],
+    );
+
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    // Each block written to the printer is counted by the handler
+    assert_eq!(computer.memory[2000], Word::new(2, Some(true)));
+    assert_eq!(computer.a, Word::default());
+    let printer: &MemoryUnit = computer.device(18).unwrap().downcast_ref().unwrap();
+
+    assert_eq!(printer.data.len(), 48);
+  }
+
+  #[test]
+  fn test_interrupts_wait_in_control_state() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.interrupts = true;
+    load_handler(
+      &mut computer,
+      device_interrupt(16),
+      1000,
+      &[
+        Instruction::new(true, 1, 0, 0, Operation::Inc(Target::A)),
+        Instruction::new(true, 1001, 0, 5, Operation::St(Target::A)),
+        int(),
+      ],
+    );
+    load_handler(
+      &mut computer,
+      PROGRAM_INTERRUPT,
+      2000,
+      &[
+        Instruction::new(true, 1000, 0, 16, Operation::In),
+        Instruction::new(true, 1, 0, 0, Operation::Ent(Target::A)),
+        Instruction::new(true, 1000, 0, 5, Operation::St(Target::A)),
+        int(),
+      ],
+    );
+
+    program.add(int());
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    // The unit interrupt is served after the program interrupt returns
+    assert_eq!(computer.memory[1000], Word::new(1, Some(true)));
+    assert_eq!(computer.memory[1001], Word::new(1, Some(true)));
+    assert_eq!(computer.pc, 2);
+  }
+
+  #[rstest]
+  #[case(1)]
+  #[case(2)]
+  #[case(4)]
+  fn test_clock_interrupt(#[case] clock: u32) {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.interrupts = true;
+    *computer.cell_mut(CLOCK) = Word::new(clock, Some(true));
+    load_handler(
+      &mut computer,
+      CLOCK_INTERRUPT,
+      1000,
+      &[
+        Instruction::new(true, 1000, 0, 5, Operation::St(Target::A)),
+        int(),
+      ],
+    );
+
+    // Every INCA takes a unit of time and counts the instructions executed
+    for _ in 0..4 {
+      program.add(Instruction::new(true, 1, 0, 0, Operation::Inc(Target::A)));
+    }
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    assert_eq!(computer.memory[1000], Word::new(clock, Some(true)));
+    assert_eq!(*computer.cell(CLOCK), Word::default());
+  }
+
+  #[test]
+  fn test_control_memory_in_normal_state() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer.interrupts = true;
+
+    program.add(Instruction::new(false, 1, 0, 5, Operation::Ld(Target::A)));
+    program.add(hlt());
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::InvalidAddress(-1))
+    );
+  }
+
+  #[test]
+  fn test_int_without_interrupts() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    program.add(int());
+    program.add(hlt());
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::MissingAttachment(Operation::Int))
+    );
+  }
+}
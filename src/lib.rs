@@ -0,0 +1,92 @@
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+pub mod assembler;
+pub mod byte;
+pub mod card;
+pub mod charcode;
+pub mod checker;
+pub mod computer;
+pub mod deck;
+pub mod device;
+pub mod disassembler;
+pub mod disk;
+pub mod error;
+pub mod examples;
+pub mod fault;
+pub mod field;
+pub mod float;
+pub mod governor;
+pub mod instruction;
+pub mod interrupt;
+pub mod lint;
+pub mod loader;
+pub mod mapping;
+pub mod mdk;
+pub mod mnemonic;
+pub mod object;
+pub mod pair;
+pub mod printer;
+pub mod program;
+pub mod register;
+pub mod sourcemap;
+pub mod symbols;
+pub mod tape;
+#[cfg(feature = "tcp")]
+pub mod tcp;
+pub mod timing;
+pub mod transcript;
+mod transfer;
+pub mod typewriter;
+pub mod value;
+pub mod word;
+
+use error::FieldError;
+use field::FieldSpec;
+
+#[cfg(test)]
+#[allow(clippy::single_component_path_imports)]
+use rstest_reuse;
+
+/// Trait for reading and writing data
+pub trait Data<T> {
+  /// Reads the value including the sign
+  fn read(&self) -> T;
+
+  /// Reads the value without the sign
+  fn read_data(&self) -> T;
+
+  /// Writes the value, including the sign
+  fn write(&mut self, number: T, sign: bool);
+
+  /// Writes the value, without the sign
+  fn write_data(&mut self, number: T);
+
+  fn get_byte(&self, index: usize) -> u8;
+}
+
+/// Trait for reading fields of data, the field must lie within the bytes
+pub trait FieldRead<T> {
+  /// Reads the value by field specification, the sign comes as the highest
+  /// bit with 1 for plus when the field includes it
+  fn read_with_modifier(&self, field: FieldSpec) -> Result<T, FieldError>;
+
+  /// Reads the field as a magnitude and a sign, the field is negative only
+  /// if it includes the sign and the sign is minus
+  fn read_field(&self, field: FieldSpec) -> Result<(T, bool), FieldError>;
+}
+
+/// Trait for writing fields of data, the field must lie within the bytes
+pub trait FieldWrite<T> {
+  /// Writes the value by field specification, the rightmost bytes of the
+  /// value replace the bytes of the field and the rest is left untouched
+  fn write_with_modifier(&mut self, number: T, field: FieldSpec) -> Result<(), FieldError>;
+}
+
+/// Trait for reading and writing the sign
+pub trait Signed {
+  /// Reads the sign (true if positive, false if negative)
+  fn read_sign(&self) -> bool;
+
+  /// Writes the sign (true for positive, false for negative)
+  fn write_sign(&mut self, sign: bool);
+}
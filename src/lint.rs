@@ -0,0 +1,431 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fmt,
+  path::{Path, PathBuf},
+};
+
+use crate::{
+  assembler::{self, Assembler, Directive, Statement, Symbols},
+  error::AssemblyError,
+  field::FieldSpec,
+  instruction::{Operation, Target},
+  word::Word,
+  Data,
+};
+
+/// Line of a MIXAL source that assembles but likely does not do what is
+/// meant, found at the text of the line
+///
+/// It shows as `file:line:column: warning: message [code]`, the way
+/// compilers report to editors, without the file for a source given as
+/// text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lint {
+  /// File the line is in, none for the source given as text
+  pub file: Option<PathBuf>,
+  /// Number of the line, from 1
+  pub line: usize,
+  /// Number of the character of the line the text starts at, from 1
+  pub column: usize,
+  /// Text the lint is found at
+  pub token: String,
+  pub kind: LintKind,
+}
+
+/// What is likely wrong with a line of a MIXAL source
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LintKind {
+  /// The symbol is defined but no address field uses it
+  UnusedSymbol(String),
+  /// The instruction follows a jump that does not return or HLT, and
+  /// nothing jumps to it
+  Unreachable,
+  /// The instruction stores past the address of the instruction at the
+  /// location
+  WriteToCode(i64),
+  /// The operation takes its field from its opcode and ignores the one
+  /// given
+  IgnoredField(i64),
+  /// The field of an operation on a part of a word is not (L:R)
+  InvalidField(i64),
+  /// The field (0:5) the load takes by default puts the whole word at the
+  /// location into an index register, which holds two bytes only
+  DefaultField(i64),
+  /// The index is not one of the index registers rI1-rI6
+  InvalidIndex(i64),
+}
+
+impl Assembler {
+  /// Checks the source for lines that assemble but likely do not do what is
+  /// meant, in the order of the lines
+  ///
+  /// Symbols that the included files define and the local symbols are not
+  /// reported when unused. A jump to a line that starts with STJ is taken
+  /// as a call of a subroutine that returns past the jump.
+  pub fn lint(&self, source: &str) -> Result<Vec<Lint>, AssemblyError> {
+    self.lint_source(None, source)
+  }
+
+  /// Checks the source read from the file, which the lints name
+  pub fn lint_named(
+    &self,
+    file: impl AsRef<Path>,
+    source: &str,
+  ) -> Result<Vec<Lint>, AssemblyError> {
+    self.lint_source(Some(file.as_ref()), source)
+  }
+
+  fn lint_source(&self, file: Option<&Path>, source: &str) -> Result<Vec<Lint>, AssemblyError> {
+    let lines = self.read(file, source)?;
+    let pass = self.passes(&lines, 0, Symbols::default())?;
+    let mut parts = Vec::new();
+
+    for statement in &pass.statements {
+      parts.push(match statement.directive() {
+        Some(_) => None,
+        None => {
+          let (address, index, field) = self.address_field(statement, &pass.symbols)?;
+
+          Some(Parts {
+            operation: statement.operation()?,
+            address,
+            index,
+            field,
+          })
+        }
+      });
+    }
+
+    let instructions = pass
+      .statements
+      .iter()
+      .zip(&parts)
+      .filter_map(|(statement, parts)| Some((statement.location, parts.as_ref()?.operation)))
+      .collect::<HashMap<_, _>>();
+    let words = pass
+      .words
+      .iter()
+      .map(|&(location, word, _)| (location as i64, word))
+      .collect::<HashMap<_, _>>();
+    let used = pass
+      .statements
+      .iter()
+      .filter(|statement| statement.directive() != Some(Directive::Alf))
+      .flat_map(|statement| {
+        statement
+          .operand
+          .split(|char: char| !char.is_ascii_alphanumeric())
+      })
+      .collect::<HashSet<_>>();
+    let targets = parts
+      .iter()
+      .flatten()
+      .filter(|parts| parts.index == 0 && is_jump(parts.operation))
+      .map(|parts| parts.address)
+      .chain(pass.start.map(|start| start as i64))
+      .collect::<HashSet<_>>();
+    let mut lints = Vec::new();
+    let mut previous: Option<&Parts> = None;
+
+    for (statement, parts) in pass.statements.iter().zip(&parts) {
+      let unused = statement.label.filter(|&label| {
+        statement.file == file
+          && assembler::is_symbol(label)
+          && assembler::local(label).is_none()
+          && !used.contains(label)
+      });
+
+      if let Some(label) = unused {
+        lints.push(Lint::new(
+          statement,
+          1,
+          label,
+          LintKind::UnusedSymbol(label.to_string()),
+        ));
+      }
+
+      let Some(parts) = parts else {
+        if statement.directive() != Some(Directive::Equ) {
+          previous = None;
+        }
+
+        continue;
+      };
+
+      let stops = previous.is_some_and(|previous| match previous.operation {
+        Operation::Hlt | Operation::Jsj => true,
+        Operation::Jmp => {
+          let target = instructions.get(&previous.address);
+
+          previous.index == 0 && target.is_some_and(|&target| target != Operation::Stj)
+        }
+        _ => false,
+      });
+
+      if stops && statement.label.is_none() && !targets.contains(&statement.location) {
+        lints.push(Lint::new(
+          statement,
+          statement.operation_column,
+          statement.operation,
+          LintKind::Unreachable,
+        ));
+      }
+
+      for kind in parts.lints(&instructions, &words) {
+        lints.push(Lint::new(
+          statement,
+          statement.operand_column,
+          statement.operand,
+          kind,
+        ));
+      }
+
+      previous = Some(parts);
+    }
+
+    Ok(lints)
+  }
+}
+
+impl Lint {
+  fn new(statement: &Statement, column: usize, token: &str, kind: LintKind) -> Self {
+    Self {
+      file: statement.file.map(Path::to_path_buf),
+      line: statement.line,
+      column,
+      token: token.to_string(),
+      kind,
+    }
+  }
+}
+
+impl LintKind {
+  /// Short name of the lint, for editors and for turning it off
+  pub fn code(&self) -> &'static str {
+    match self {
+      Self::UnusedSymbol(_) => "unused-symbol",
+      Self::Unreachable => "unreachable",
+      Self::WriteToCode(_) => "write-to-code",
+      Self::IgnoredField(_) => "ignored-field",
+      Self::InvalidField(_) => "invalid-field",
+      Self::DefaultField(_) => "default-field",
+      Self::InvalidIndex(_) => "invalid-index",
+    }
+  }
+}
+
+/// Parts of an instruction as its address field gives them
+struct Parts {
+  operation: Operation,
+  address: i64,
+  index: i64,
+  /// Field of the address field, none if it takes the one of the operation
+  field: Option<i64>,
+}
+
+impl Parts {
+  /// Finds what is wrong with the parts, given the instructions and the
+  /// words of the source by their locations
+  fn lints(
+    &self,
+    instructions: &HashMap<i64, Operation>,
+    words: &HashMap<i64, Word>,
+  ) -> Vec<LintKind> {
+    let mut lints = Vec::new();
+    let field = self.field.map(|field| FieldSpec::try_from(field as u32));
+
+    if !(0..=6).contains(&self.index) {
+      lints.push(LintKind::InvalidIndex(self.index));
+    }
+
+    match (self.field, self.operation.field()) {
+      (Some(given), Some(own)) if given != own as i64 => {
+        lints.push(LintKind::IgnoredField(given));
+      }
+      (Some(given), None) if self.operation.has_field_spec() && matches!(field, Some(Err(_))) => {
+        lints.push(LintKind::InvalidField(given));
+      }
+      _ => {}
+    }
+
+    if self.index != 0 {
+      return lints;
+    }
+
+    let stores = matches!(self.operation, Operation::St(_) | Operation::Stz);
+    let right = match field {
+      Some(Ok(field)) => field.right,
+      Some(Err(_)) => 0,
+      None => FieldSpec::WHOLE.right,
+    };
+
+    if stores && right > 2 && instructions.contains_key(&self.address) {
+      lints.push(LintKind::WriteToCode(self.address));
+    }
+
+    let loads_index = matches!(
+      self.operation,
+      Operation::Ld(target) | Operation::Ldn(target) if !matches!(target, Target::A | Target::X)
+    );
+    let wide = words
+      .get(&self.address)
+      .is_some_and(|word| (1..=3).any(|index| word.get_byte(index) != 0));
+
+    if loads_index && self.field.is_none() && wide {
+      lints.push(LintKind::DefaultField(self.address));
+    }
+
+    lints
+  }
+}
+
+/// Checks if the operation jumps to its address, on a condition or not
+fn is_jump(operation: Operation) -> bool {
+  matches!(
+    operation,
+    Operation::Jmp
+      | Operation::Jsj
+      | Operation::Jov
+      | Operation::Jnov
+      | Operation::Jl
+      | Operation::Je
+      | Operation::Jg
+      | Operation::Jge
+      | Operation::Jne
+      | Operation::Jle
+      | Operation::Jump(..)
+      | Operation::Jbus
+      | Operation::Jred
+  )
+}
+
+impl fmt::Display for Lint {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(file) = &self.file {
+      write!(f, "{}:", file.display())?;
+    }
+
+    write!(
+      f,
+      "{}:{}: warning: {} [{}]",
+      self.line,
+      self.column,
+      self.kind,
+      self.kind.code()
+    )
+  }
+}
+
+impl fmt::Display for LintKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnusedSymbol(name) => write!(f, "Symbol {} is never used", name),
+      Self::Unreachable => write!(f, "Instruction cannot be reached"),
+      Self::WriteToCode(location) => {
+        write!(f, "Stores into the instruction at {}", location)
+      }
+      Self::IgnoredField(field) => write!(f, "Field {} is ignored by the operation", field),
+      Self::InvalidField(field) => write!(f, "Field {} is not an (L:R) specification", field),
+      Self::DefaultField(location) => write!(
+        f,
+        "Loads the whole word at {} into an index register",
+        location
+      ),
+      Self::InvalidIndex(index) => write!(f, "Index {} is not one of rI1-rI6", index),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  /// Lines and kinds of the lints of the source
+  fn lints_of(source: &str) -> Vec<(usize, LintKind)> {
+    Assembler::new()
+      .lint(source)
+      .unwrap()
+      .into_iter()
+      .map(|lint| (lint.line, lint.kind))
+      .collect()
+  }
+
+  #[test]
+  fn test_clean() {
+    let source = "
+SIZE   EQU  3
+START  ENT1 SIZE
+LOOP   JMP  DOUBLE
+       DEC1 1
+       J1P  LOOP
+       HLT
+* Doubles rA
+DOUBLE STJ  EXIT
+       STA  TEMP
+       ADD  TEMP
+EXIT   JMP  *
+TEMP   CON  0
+       END  START
+";
+
+    assert_eq!(lints_of(source), []);
+  }
+
+  #[rstest]
+  #[case(" NOP\nUNUSED CON 0", 2, LintKind::UnusedSymbol("UNUSED".to_string()))]
+  #[case(" JMP 2F\n NOP\n2H HLT", 2, LintKind::Unreachable)]
+  #[case(" HLT\n NOP", 2, LintKind::Unreachable)]
+  #[case(" JSJ *+2\n NOP\n NOP", 2, LintKind::Unreachable)]
+  #[case(" STA 1F\n1H NOP", 1, LintKind::WriteToCode(1))]
+  #[case(" STZ 1F(4:4)\n1H NOP", 1, LintKind::WriteToCode(1))]
+  #[case(" JMP 100(5)", 1, LintKind::IgnoredField(5))]
+  #[case(" LDA 100(14)", 1, LintKind::InvalidField(14))]
+  #[case(" LD1 1F\n1H CON 1000000", 1, LintKind::DefaultField(1))]
+  #[case(" LDA 100,7", 1, LintKind::InvalidIndex(7))]
+  fn test_lint(#[case] source: &str, #[case] line: usize, #[case] kind: LintKind) {
+    assert_eq!(lints_of(source), [(line, kind)]);
+  }
+
+  #[rstest]
+  #[case(" JMP 2F\n ORIG 100\n NOP\n2H HLT")]
+  #[case(" JMP 2F\n2H HLT")]
+  #[case(" JMP 2F\n JMP 1F\n1H HLT\n2H JMP *-2")]
+  #[case(" JMP *+2\n CON 0\n HLT\n CON 0")]
+  #[case(" ST1 1F(0:2)\n STJ 1F\n1H JMP *")]
+  #[case(" LD1 1F\n LD2 1F(4:5)\n1H CON 1000")]
+  #[case(" LDA 100(0:3)\n JMP 100(0)")]
+  fn test_lint_none(#[case] source: &str) {
+    assert_eq!(lints_of(source), []);
+  }
+
+  #[test]
+  fn test_display() {
+    let lints = Assembler::new()
+      .lint_named("program.mixal", "       LDA  100,7\nX      CON  0\n")
+      .unwrap();
+    let lines = lints.iter().map(Lint::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+      lines,
+      [
+        "program.mixal:1:13: warning: Index 7 is not one of rI1-rI6 [invalid-index]",
+        "program.mixal:2:1: warning: Symbol X is never used [unused-symbol]"
+      ]
+    );
+    assert_eq!(
+      Assembler::new().lint(" LDA 100,7").unwrap()[0].to_string(),
+      "1:6: warning: Index 7 is not one of rI1-rI6 [invalid-index]"
+    );
+  }
+
+  #[test]
+  fn test_error() {
+    let error = Assembler::new().lint(" JMP NOWHERE").unwrap_err();
+
+    assert_eq!(error.line, 1);
+  }
+}
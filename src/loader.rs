@@ -0,0 +1,294 @@
+use std::io;
+
+use crate::{
+  assembler::{Assembler, Assembly},
+  byte::ByteSize,
+  charcode,
+  deck::Deck,
+  word::Word,
+  Signed,
+};
+
+/// Loading routine of two cards, for the GO button to read the first card
+/// into locations 0-15 and run it
+///
+/// The first card reads the second into locations 16-31, then the routine
+/// reads the data cards into locations 32-47 and places their words. The
+/// words of the routine keep to the bytes 0-55, so that the cards can be
+/// punched.
+const LOADER: &str = "
+BUFFER EQU  32
+       ORIG 0
+       IN   16(16)
+       JBUS *(16)
+READ   IN   BUFFER(16)
+       JBUS *(16)
+       LD1  BUFFER+1(1:1)
+       DEC1 30
+       ENTA 0
+       LDX  BUFFER+1(2:5)
+       NUM
+       STA  BUFFER
+       LD2  BUFFER
+       J1Z  0,2
+       ENT3 0
+WORD   LDA  BUFFER+2,3
+       LDX  BUFFER+3,3
+       NUM
+       STA  0,2
+       LD4  BUFFER+3,3(5:5)
+       DEC4 30
+       J4NN *+3
+       LDAN 0,2
+       STA  0,2
+       INC2 1
+       INC3 2
+       DEC1 1
+       J1P  WORD
+       JMP  READ
+       END  0
+";
+
+/// Number of words a data card holds
+const CARD_WORDS: usize = 7;
+
+/// Punches the assembly as a deck that loads itself, as in exercise 26 of
+/// section 1.3.1: the two cards of the loading routine, then the data cards
+/// and a transfer card
+///
+/// A data card has in columns 6-10 the number of its words, 1 to 7, and the
+/// location of the first of them. Its words follow as ten digits each, a
+/// negative word with its last digit punched as Δ or J through R. A
+/// transfer card has the number 0 and the location the program starts at.
+/// Columns 1-5 name the card and are not read.
+///
+/// The routine takes locations 0-47, an assembly with words there is refused
+/// and so is one without a start.
+impl Assembly {
+  /// Punches the deck for the machine with the byte size the assembly is
+  /// for
+  pub fn to_deck(&self, byte_size: ByteSize) -> io::Result<Deck> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let start = self.start.ok_or_else(|| invalid("Assembly has no start"))?;
+
+    if self
+      .words
+      .iter()
+      .any(|&(location, _)| location < Loader::END)
+    {
+      return Err(invalid("Assembly has a word where the loading routine is"));
+    }
+
+    let mut cards = Loader::cards(byte_size);
+    let mut lines = Vec::new();
+    let mut run: Vec<(usize, Word)> = Vec::new();
+
+    for &(location, word) in &self.words {
+      let contiguous = run.last().is_some_and(|&(last, _)| last + 1 == location);
+
+      if !run.is_empty() && (!contiguous || run.len() == CARD_WORDS) {
+        lines.push(data_card(&run, byte_size));
+        run.clear();
+      }
+
+      run.push((location, word));
+    }
+
+    if !run.is_empty() {
+      lines.push(data_card(&run, byte_size));
+    }
+
+    lines.push(format!("TRANS0{:04}", start));
+
+    for line in lines {
+      cards.push(Deck::card(cards.len() + 1, &line)?);
+    }
+
+    Ok(Deck { cards })
+  }
+}
+
+/// Loading routine of the deck
+struct Loader;
+
+impl Loader {
+  /// First location after the routine and its buffer
+  const END: usize = 48;
+
+  /// Two cards of the routine, assembled for the byte size
+  fn cards(byte_size: ByteSize) -> Vec<Vec<Word>> {
+    let assembly = Assembler {
+      byte_size,
+      ..Assembler::new()
+    }
+    .assemble(LOADER)
+    .expect("Loading routine assembles");
+    let mut memory = [Word::default(); 2 * Deck::CARD_SIZE];
+
+    for (location, word) in assembly.words {
+      memory[location] = word;
+    }
+
+    memory
+      .chunks(Deck::CARD_SIZE)
+      .map(<[Word]>::to_vec)
+      .collect()
+  }
+}
+
+/// Line of the data card for the words at their locations
+fn data_card(words: &[(usize, Word)], byte_size: ByteSize) -> String {
+  let mut line = format!("{:5}{}{:04}", "", words.len(), words[0].0);
+
+  for (_, word) in words {
+    let mut digits = charcode::number_to_digits::<10>(byte_size.magnitude(word));
+
+    if !word.read_sign() {
+      digits[9] -= charcode::DIGIT_ZERO - 10;
+    }
+
+    line.extend(digits.map(|code| charcode::CHARS[code as usize]));
+  }
+
+  line
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    card::CardReader,
+    computer::{Computer, HaltReason},
+    sourcemap::SourceMap,
+    symbols::SymbolTable,
+  };
+
+  const SOURCE: &str = "
+       ORIG 100
+START  LDA  X
+       ADD  Y
+       STA  SUM
+       HLT
+X      CON  -1000
+Y      CON  12345
+       ORIG 2000
+SUM    CON  0
+TABLE  CON  1
+       CON  2
+       CON  3
+       CON  4
+       CON  5
+       CON  6
+       CON  -0
+       CON  8
+       END  START
+";
+
+  fn computer(byte_size: ByteSize) -> Computer {
+    if byte_size == ByteSize::DECIMAL {
+      Computer::decimal()
+    } else {
+      Computer::new()
+    }
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY)]
+  #[case(ByteSize::DECIMAL)]
+  fn test_go(#[case] byte_size: ByteSize) {
+    let assembler = Assembler {
+      byte_size,
+      ..Assembler::new()
+    };
+    let assembly = assembler.assemble(SOURCE).unwrap();
+    let deck = assembly.to_deck(byte_size).unwrap();
+    let mut text = Vec::new();
+
+    deck.to_writer(&mut text).unwrap();
+
+    let mut computer = computer(byte_size);
+    let reader = CardReader::from_reader(&text[..]).unwrap();
+
+    computer.attach_device(16, Box::new(reader)).unwrap();
+
+    assert_eq!(computer.go(), Ok(HaltReason::Halted));
+
+    for &(location, word) in &assembly.words {
+      if location != 2000 {
+        assert_eq!(computer.memory[location], word, "location {}", location);
+      }
+    }
+
+    assert_eq!(computer.memory[2000], byte_size.word(11345, true));
+
+    let reader = computer.detach_device(16).unwrap();
+
+    assert_eq!(reader.downcast_ref::<CardReader>().unwrap().remaining(), 0);
+  }
+
+  #[test]
+  fn test_cards() {
+    let assembly = assemble_at(&[(3000, -12), (3001, 7)], 3000);
+    let mut text = Vec::new();
+
+    assembly
+      .to_deck(ByteSize::BINARY)
+      .unwrap()
+      .to_writer(&mut text)
+      .unwrap();
+
+    let text = String::from_utf8(text).unwrap();
+    let lines = text.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[2], "     23000000000001K0000000007");
+    assert_eq!(lines[3], "TRANS03000");
+  }
+
+  #[test]
+  fn test_cards_split() {
+    let words = (0..9)
+      .map(|index| (100 + index, index as i64))
+      .chain([(200, 1)])
+      .collect::<Vec<_>>();
+    let deck = assemble_at(&words, 100).to_deck(ByteSize::BINARY).unwrap();
+    let lines = deck
+      .cards
+      .iter()
+      .map(|card| Deck::line(card).unwrap())
+      .collect::<Vec<_>>();
+
+    assert_eq!(deck.cards.len(), 2 + 4);
+    assert_eq!(&lines[2][..10], "     70100");
+    assert_eq!(&lines[3][..10], "     20107");
+    assert_eq!(&lines[4][..10], "     10200");
+  }
+
+  #[rstest]
+  #[case(assemble_at(&[(47, 1)], 47))]
+  #[case(Assembly { start: None, ..assemble_at(&[(100, 1)], 100) })]
+  fn test_to_deck_invalid(#[case] assembly: Assembly) {
+    let error = assembly.to_deck(ByteSize::BINARY).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+  }
+
+  fn assemble_at(words: &[(usize, i64)], start: usize) -> Assembly {
+    Assembly {
+      words: words
+        .iter()
+        .map(|&(location, value)| {
+          (
+            location,
+            ByteSize::BINARY.word(value.unsigned_abs(), value >= 0),
+          )
+        })
+        .collect(),
+      start: Some(start),
+      symbols: SymbolTable::default(),
+      source_map: SourceMap::default(),
+    }
+  }
+}
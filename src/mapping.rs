@@ -0,0 +1,176 @@
+use std::{fs::File, io, path::PathBuf};
+
+use crate::{
+  card::{CardPunch, CardReader},
+  device::{self, Device, DeviceKind, MemoryUnit},
+  disk::FileDisk,
+  printer::LinePrinter,
+  tape::FileTape,
+  typewriter::Typewriter,
+};
+
+/// What a unit of the machine is backed by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backing {
+  /// Unit of the kind keeping its words in memory
+  Memory(DeviceKind),
+  /// Tape kept in the file
+  Tape(PathBuf),
+  /// Tape kept in the file as the `mixvm` of GNU MDK keeps it
+  MdkTape(PathBuf),
+  /// Disk of the capacity in blocks kept in the file
+  Disk(PathBuf, u64),
+  /// Card reader taking its deck from the file
+  CardReader(PathBuf),
+  /// Card punch writing its cards to the file
+  CardPunch(PathBuf),
+  /// Line printer printing to the file
+  LinePrinter(PathBuf),
+  /// Typewriter of the terminal the emulator runs in
+  Typewriter,
+}
+
+impl Backing {
+  /// Opens the device backing the unit, the files of tapes and disks are
+  /// created if they do not exist and so are the files of a punch and a
+  /// printer
+  pub fn open(&self) -> io::Result<Box<dyn Device>> {
+    Ok(match self {
+      Self::Memory(kind) => Box::new(MemoryUnit::new(*kind)),
+      Self::Tape(path) => Box::new(FileTape::open(path)?),
+      Self::MdkTape(path) => Box::new(FileTape::open_mdk(path)?),
+      Self::Disk(path, capacity) => Box::new(FileDisk::open(path, *capacity)?),
+      Self::CardReader(path) => Box::new(CardReader::open(path)?),
+      Self::CardPunch(path) => Box::new(CardPunch::new(File::create(path)?)),
+      Self::LinePrinter(path) => Box::new(LinePrinter::new(File::create(path)?)),
+      Self::Typewriter => Box::new(Typewriter::stdio()),
+    })
+  }
+}
+
+/// Which unit number is backed by what, for tools and textbooks that number
+/// the units their own way, such as two card readers or a printer on unit 5
+///
+/// The default mapping is the canonical one of MIX with every unit in memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitMap {
+  units: [Option<Backing>; device::UNITS],
+}
+
+impl UnitMap {
+  /// Creates the mapping with no unit attached
+  pub fn empty() -> Self {
+    Self {
+      units: std::array::from_fn(|_| None),
+    }
+  }
+
+  /// Backs the unit by the backing, the unit must exist
+  pub fn with(mut self, unit: u32, backing: Backing) -> Self {
+    assert!((unit as usize) < device::UNITS, "Unit does not exist");
+
+    self.units[unit as usize] = Some(backing);
+    self
+  }
+
+  /// Leaves the unit detached, the unit must exist
+  pub fn without(mut self, unit: u32) -> Self {
+    assert!((unit as usize) < device::UNITS, "Unit does not exist");
+
+    self.units[unit as usize] = None;
+    self
+  }
+
+  /// Returns what the unit is backed by, none if it is detached
+  pub fn backing(&self, unit: u32) -> Option<&Backing> {
+    self.units.get(unit as usize)?.as_ref()
+  }
+
+  /// Opens the devices of the units, by unit number
+  pub fn open(&self) -> io::Result<[Option<Box<dyn Device>>; device::UNITS]> {
+    let mut devices = std::array::from_fn(|_| None);
+
+    for (device, backing) in devices.iter_mut().zip(&self.units) {
+      if let Some(backing) = backing {
+        *device = Some(backing.open()?);
+      }
+    }
+
+    Ok(devices)
+  }
+}
+
+impl Default for UnitMap {
+  fn default() -> Self {
+    Self {
+      units: std::array::from_fn(|unit| DeviceKind::of(unit as u32).map(Backing::Memory)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use tempfile::NamedTempFile;
+
+  use super::*;
+  use crate::{
+    charcode,
+    computer::{Computer, HaltReason},
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  #[test]
+  fn test_default() {
+    let map = UnitMap::default();
+
+    assert_eq!(map.backing(0), Some(&Backing::Memory(DeviceKind::Tape)));
+    assert_eq!(
+      map.backing(18),
+      Some(&Backing::Memory(DeviceKind::LinePrinter))
+    );
+    assert_eq!(map.backing(21), None);
+  }
+
+  #[test]
+  fn test_two_card_readers() {
+    let mut computer = Computer::new()
+      .with_units(
+        UnitMap::empty()
+          .with(16, Backing::Memory(DeviceKind::CardReader))
+          .with(17, Backing::Memory(DeviceKind::CardReader)),
+      )
+      .unwrap();
+
+    assert_eq!(computer.device(17).unwrap().block_size(), 16);
+    assert!(computer.device(18).is_err());
+
+    let reader: &mut MemoryUnit = computer.device_mut(17).unwrap().downcast_mut().unwrap();
+
+    assert_eq!(reader.kind, DeviceKind::CardReader);
+  }
+
+  #[test]
+  fn test_printer_on_unit_5() {
+    let file = NamedTempFile::new().unwrap();
+    let mut computer = Computer::new()
+      .with_units(UnitMap::default().with(5, Backing::LinePrinter(file.path().into())))
+      .unwrap();
+    let mut program = Program::new();
+
+    computer.memory[1000] = charcode::str_to_words("UNIT5").unwrap()[0];
+
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(std::fs::read_to_string(file.path()).unwrap(), "UNIT5\n");
+  }
+
+  #[test]
+  fn test_missing_deck() {
+    let map = UnitMap::default().with(16, Backing::CardReader("/nonexistent/deck".into()));
+
+    assert!(map.open().is_err());
+  }
+}
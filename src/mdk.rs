@@ -0,0 +1,290 @@
+use std::{
+  fs::File,
+  io::{self, BufReader, BufWriter, Read, Write},
+  path::Path,
+};
+
+use crate::{
+  assembler::Assembly, computer::MEMORY, device::FileFormat, sourcemap::SourceMap,
+  symbols::SymbolTable,
+};
+
+/// Marks a code file of `mixasm` without debugging data
+const SIGNATURE: u32 = 0xDEAD_BEEF;
+
+/// Marks a code file of `mixasm` with debugging data
+const DEBUG_SIGNATURE: u32 = 0xBEEF_DEAD;
+
+/// Version of MDK the code files are written for
+const VERSION: (u32, u32) = (1, 2);
+
+/// Number of bytes the header takes before the path of the source, as the C
+/// structure of `mixasm` lies on a 64-bit host: the signature, the major and
+/// the minor version as four bytes each, the start address as two bytes and
+/// the length of the path as eight bytes after two bytes of padding
+const HEADER_BYTES: usize = 24;
+
+/// Marks a number of the code that sets the location of the next word
+const ADDRESS_TAG: u32 = 1 << 31;
+
+/// Code files of GNU MDK, the `.mix` files that `mixasm` writes and `mixvm`
+/// runs
+///
+/// A code file starts with a header and the path of the source. Then come
+/// numbers of four bytes, little endian: a number with bit 31 set sets the
+/// location of the words that follow and any other number is the next word,
+/// in the layout of [`FileFormat::Mdk`]. The files `mixasm -g` writes with
+/// debugging data are not read.
+///
+/// The code written for MDK is for a binary machine, as `mixvm` is.
+impl Assembly {
+  /// Reads the code file of MDK
+  pub fn open_mdk(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::from_mdk(BufReader::new(File::open(path)?))
+  }
+
+  /// Reads the code of MDK, the words go to their locations and the program
+  /// starts where the header says
+  pub fn from_mdk(mut reader: impl Read) -> io::Result<Self> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut header = [0; HEADER_BYTES];
+
+    reader.read_exact(&mut header)?;
+
+    match u32::from_le_bytes(header[0..4].try_into().unwrap()) {
+      SIGNATURE => {}
+      DEBUG_SIGNATURE => return Err(invalid("Code file has debugging data")),
+      _ => return Err(invalid("File is not a code file of MDK")),
+    }
+
+    let start = u16::from_le_bytes(header[12..14].try_into().unwrap()) as usize;
+    let path_length = u64::from_le_bytes(header[16..24].try_into().unwrap());
+
+    io::copy(&mut (&mut reader).take(path_length), &mut io::sink())?;
+
+    let mut code = Vec::new();
+
+    reader.read_to_end(&mut code)?;
+
+    if code.len() % 4 != 0 {
+      return Err(invalid("Code file ends within a word"));
+    }
+
+    let mut words = Vec::new();
+    let mut location = 0;
+
+    for chunk in code.chunks(4) {
+      let number = u32::from_le_bytes(chunk.try_into().unwrap());
+
+      if number & ADDRESS_TAG != 0 {
+        location = (number & !ADDRESS_TAG) as usize;
+        continue;
+      }
+
+      if location >= MEMORY {
+        return Err(invalid("Code file has a word out of memory"));
+      }
+
+      words.push((location, FileFormat::Mdk.decode(chunk)));
+      location += 1;
+    }
+
+    if start >= MEMORY {
+      return Err(invalid("Code file starts out of memory"));
+    }
+
+    Ok(Self {
+      words,
+      start: Some(start),
+      symbols: SymbolTable::default(),
+      source_map: SourceMap::default(),
+    })
+  }
+
+  /// Writes the code file of MDK
+  pub fn save_mdk(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    self.to_mdk(&mut writer)?;
+    writer.flush()
+  }
+
+  /// Writes the code of MDK, with the path of the source if the source map
+  /// has it and starting at 0 if the assembly gives no start
+  ///
+  /// A word with a byte that does not fit into six bits is refused.
+  pub fn to_mdk(&self, mut writer: impl Write) -> io::Result<()> {
+    let path = self
+      .source_map
+      .lines
+      .values()
+      .find_map(|line| line.file.as_ref())
+      .map(|file| file.to_string_lossy().into_owned())
+      .unwrap_or_default();
+    let mut bytes = Vec::new();
+
+    bytes.extend(SIGNATURE.to_le_bytes());
+    bytes.extend(VERSION.0.to_le_bytes());
+    bytes.extend(VERSION.1.to_le_bytes());
+    bytes.extend((self.start.unwrap_or(0) as u16).to_le_bytes());
+    bytes.extend([0, 0]);
+    bytes.extend((path.len() as u64).to_le_bytes());
+    bytes.extend(path.as_bytes());
+
+    let mut next = None;
+
+    for &(location, word) in &self.words {
+      if next != Some(location) {
+        bytes.extend((location as u32 | ADDRESS_TAG).to_le_bytes());
+      }
+
+      FileFormat::Mdk.encode(&word, &mut bytes)?;
+      next = Some(location + 1);
+    }
+
+    writer.write_all(&bytes)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    assembler::{assemble, Assembler},
+    byte::ByteSize,
+    computer::{Computer, HaltReason},
+    instruction::{Instruction, Operation, Target},
+    word::Word,
+  };
+
+  /// Code file of `mixasm` for the words at their locations
+  fn code_file(start: u16, segments: &[(u32, &[Word])]) -> Vec<u8> {
+    let path = b"hello.mixal";
+    let mut bytes = Vec::new();
+
+    bytes.extend(SIGNATURE.to_le_bytes());
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(2u32.to_le_bytes());
+    bytes.extend(start.to_le_bytes());
+    bytes.extend([0, 0]);
+    bytes.extend((path.len() as u64).to_le_bytes());
+    bytes.extend(path);
+
+    for (origin, words) in segments {
+      bytes.extend((origin | ADDRESS_TAG).to_le_bytes());
+
+      for word in *words {
+        FileFormat::Mdk.encode(word, &mut bytes).unwrap();
+      }
+    }
+
+    bytes
+  }
+
+  fn encode(instruction: Instruction) -> Word {
+    instruction.encode(ByteSize::BINARY)
+  }
+
+  #[test]
+  fn test_from_mdk() {
+    let code = code_file(
+      3000,
+      &[
+        (
+          3000,
+          &[
+            encode(Instruction::new(true, 100, 0, 5, Operation::Ld(Target::A))),
+            encode(Instruction::new(true, 0, 0, 2, Operation::Hlt)),
+          ],
+        ),
+        (100, &[Word::new(42, Some(false))]),
+      ],
+    );
+    let assembly = Assembly::from_mdk(&code[..]).unwrap();
+
+    assert_eq!(assembly.start, Some(3000));
+    assert_eq!(assembly.words.len(), 3);
+    assert_eq!(assembly.words[2], (100, Word::new(42, Some(false))));
+
+    let mut computer = Computer::new();
+
+    assembly.load(&mut computer);
+
+    assert_eq!(computer.run(3000), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(42, Some(false)));
+  }
+
+  #[test]
+  fn test_to_mdk() {
+    let source = "
+       ORIG 3000
+START  LDA  X
+       HLT
+       ORIG 100
+X      CON  -42
+       END  START
+";
+    let assembly = Assembler::new()
+      .assemble_named("hello.mixal", source)
+      .unwrap();
+    let mut bytes = Vec::new();
+
+    assembly.to_mdk(&mut bytes).unwrap();
+
+    assert_eq!(
+      bytes,
+      code_file(
+        3000,
+        &[
+          (
+            3000,
+            &[
+              encode(Instruction::new(true, 100, 0, 5, Operation::Ld(Target::A))),
+              encode(Instruction::new(true, 0, 0, 2, Operation::Hlt)),
+            ],
+          ),
+          (100, &[Word::new(42, Some(false))]),
+        ],
+      )
+    );
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let assembly = assemble(" ORIG 10\nX CON 1(1:1),-2\n JMP X\n END X").unwrap();
+
+    assembly.save_mdk(file.path()).unwrap();
+
+    let loaded = Assembly::open_mdk(file.path()).unwrap();
+
+    assert_eq!(loaded.words, assembly.words);
+    assert_eq!(loaded.start, Some(10));
+  }
+
+  #[test]
+  fn test_to_mdk_decimal() {
+    let assembly = Assembler::decimal().assemble(" CON 99(5:5)").unwrap();
+
+    assert_eq!(
+      assembly.to_mdk(io::sink()).unwrap_err().kind(),
+      io::ErrorKind::InvalidData
+    );
+  }
+
+  #[rstest]
+  #[case(code_file(0, &[])[..20].to_vec(), io::ErrorKind::UnexpectedEof)]
+  #[case([&[0; 4][..], &code_file(0, &[])[4..]].concat(), io::ErrorKind::InvalidData)]
+  #[case(
+    [&DEBUG_SIGNATURE.to_le_bytes()[..], &code_file(0, &[])[4..]].concat(),
+    io::ErrorKind::InvalidData
+  )]
+  #[case(code_file(4000, &[]), io::ErrorKind::InvalidData)]
+  #[case(code_file(0, &[(3999, &[Word::default(); 2])]), io::ErrorKind::InvalidData)]
+  #[case([&code_file(0, &[])[..], &[0, 0]].concat(), io::ErrorKind::InvalidData)]
+  fn test_from_mdk_invalid(#[case] bytes: Vec<u8>, #[case] kind: io::ErrorKind) {
+    assert_eq!(Assembly::from_mdk(&bytes[..]).unwrap_err().kind(), kind);
+  }
+}
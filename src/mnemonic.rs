@@ -0,0 +1,291 @@
+use crate::instruction::{Condition, Operation, Target};
+
+/// Builds a program from instructions written the way MIXAL writes them,
+/// apart by semicolons
+///
+/// An instruction is the name of its operation, then the address, the index
+/// after a comma and the field in parentheses, each optional. The field is
+/// `(L:R)` or a number. The parts are numbers, and a mistake in them stops
+/// the build: an operation without the name, an index that is not 0-6 or a
+/// field that is not the one the operation takes.
+///
+/// ```
+/// use mixi::{instruction::Instruction, mix, mnemonic::*};
+///
+/// let program = mix! { LDA 2000; STA 3000,(1:5); LD1 -4,2(0:2); HLT };
+///
+/// assert_eq!(program.instructions[1], Instruction::new(true, 3000, 0, 13, STA));
+/// assert_eq!(program.instructions[2], Instruction::new(false, 4, 2, 2, LD1));
+/// ```
+///
+/// ```compile_fail
+/// mixi::mix! { LDA 2000,7 };
+/// ```
+#[macro_export]
+macro_rules! mix {
+  (@program $program:ident) => {};
+  (
+    @program $program:ident $operation:ident $($address:literal)? $(, $($index:literal)?)?
+    $(($($field:tt)+))? $(; $($rest:tt)*)?
+  ) => {
+    $program.add({
+      const INSTRUCTION: $crate::instruction::Instruction =
+        $crate::instruction::Instruction::from_parts(
+          $crate::mnemonic::$operation,
+          $crate::mix!(@part $($address)?),
+          $crate::mix!(@part $($($index)?)?),
+          $crate::mix!(@field $($($field)+)?),
+        );
+
+      INSTRUCTION
+    });
+    $crate::mix!(@program $program $($($rest)*)?);
+  };
+  (@part) => {
+    0
+  };
+  (@part $value:literal) => {
+    $value
+  };
+  (@field) => {
+    None
+  };
+  (@field $left:literal : $right:literal) => {
+    Some(8 * $left + $right)
+  };
+  (@field $field:literal) => {
+    Some($field)
+  };
+  ($($instructions:tt)*) => {{
+    let mut program = $crate::program::Program::new();
+
+    $crate::mix!(@program program $($instructions)*);
+    program
+  }};
+}
+
+// Operations by their names in MIXAL, for `mix!`
+pub const NOP: Operation = Operation::Nop;
+pub const ADD: Operation = Operation::Add;
+pub const FADD: Operation = Operation::Fadd;
+pub const SUB: Operation = Operation::Sub;
+pub const FSUB: Operation = Operation::Fsub;
+pub const MUL: Operation = Operation::Mul;
+pub const FMUL: Operation = Operation::Fmul;
+pub const DIV: Operation = Operation::Div;
+pub const FDIV: Operation = Operation::Fdiv;
+pub const NUM: Operation = Operation::Num;
+pub const CHAR: Operation = Operation::Char;
+pub const HLT: Operation = Operation::Hlt;
+pub const AND: Operation = Operation::And;
+pub const OR: Operation = Operation::Or;
+pub const XOR: Operation = Operation::Xor;
+pub const FLOT: Operation = Operation::Flot;
+pub const FIX: Operation = Operation::Fix;
+pub const INT: Operation = Operation::Int;
+pub const SLA: Operation = Operation::Sla;
+pub const SRA: Operation = Operation::Sra;
+pub const SLAX: Operation = Operation::Slax;
+pub const SRAX: Operation = Operation::Srax;
+pub const SLC: Operation = Operation::Slc;
+pub const SRC: Operation = Operation::Src;
+pub const SLB: Operation = Operation::Slb;
+pub const SRB: Operation = Operation::Srb;
+pub const MOVE: Operation = Operation::Move;
+pub const LDA: Operation = Operation::Ld(Target::A);
+pub const LD1: Operation = Operation::Ld(Target::I1);
+pub const LD2: Operation = Operation::Ld(Target::I2);
+pub const LD3: Operation = Operation::Ld(Target::I3);
+pub const LD4: Operation = Operation::Ld(Target::I4);
+pub const LD5: Operation = Operation::Ld(Target::I5);
+pub const LD6: Operation = Operation::Ld(Target::I6);
+pub const LDX: Operation = Operation::Ld(Target::X);
+pub const LDAN: Operation = Operation::Ldn(Target::A);
+pub const LD1N: Operation = Operation::Ldn(Target::I1);
+pub const LD2N: Operation = Operation::Ldn(Target::I2);
+pub const LD3N: Operation = Operation::Ldn(Target::I3);
+pub const LD4N: Operation = Operation::Ldn(Target::I4);
+pub const LD5N: Operation = Operation::Ldn(Target::I5);
+pub const LD6N: Operation = Operation::Ldn(Target::I6);
+pub const LDXN: Operation = Operation::Ldn(Target::X);
+pub const STA: Operation = Operation::St(Target::A);
+pub const ST1: Operation = Operation::St(Target::I1);
+pub const ST2: Operation = Operation::St(Target::I2);
+pub const ST3: Operation = Operation::St(Target::I3);
+pub const ST4: Operation = Operation::St(Target::I4);
+pub const ST5: Operation = Operation::St(Target::I5);
+pub const ST6: Operation = Operation::St(Target::I6);
+pub const STX: Operation = Operation::St(Target::X);
+pub const STJ: Operation = Operation::Stj;
+pub const STZ: Operation = Operation::Stz;
+pub const JBUS: Operation = Operation::Jbus;
+pub const IOC: Operation = Operation::Ioc;
+pub const IN: Operation = Operation::In;
+pub const OUT: Operation = Operation::Out;
+pub const JRED: Operation = Operation::Jred;
+pub const JMP: Operation = Operation::Jmp;
+pub const JSJ: Operation = Operation::Jsj;
+pub const JOV: Operation = Operation::Jov;
+pub const JNOV: Operation = Operation::Jnov;
+pub const JL: Operation = Operation::Jl;
+pub const JE: Operation = Operation::Je;
+pub const JG: Operation = Operation::Jg;
+pub const JGE: Operation = Operation::Jge;
+pub const JNE: Operation = Operation::Jne;
+pub const JLE: Operation = Operation::Jle;
+pub const JAN: Operation = Operation::Jump(Target::A, Condition::Negative);
+pub const JAZ: Operation = Operation::Jump(Target::A, Condition::Zero);
+pub const JAP: Operation = Operation::Jump(Target::A, Condition::Positive);
+pub const JANN: Operation = Operation::Jump(Target::A, Condition::NonNegative);
+pub const JANZ: Operation = Operation::Jump(Target::A, Condition::NonZero);
+pub const JANP: Operation = Operation::Jump(Target::A, Condition::NonPositive);
+pub const JAE: Operation = Operation::Jump(Target::A, Condition::Even);
+pub const JAO: Operation = Operation::Jump(Target::A, Condition::Odd);
+pub const J1N: Operation = Operation::Jump(Target::I1, Condition::Negative);
+pub const J1Z: Operation = Operation::Jump(Target::I1, Condition::Zero);
+pub const J1P: Operation = Operation::Jump(Target::I1, Condition::Positive);
+pub const J1NN: Operation = Operation::Jump(Target::I1, Condition::NonNegative);
+pub const J1NZ: Operation = Operation::Jump(Target::I1, Condition::NonZero);
+pub const J1NP: Operation = Operation::Jump(Target::I1, Condition::NonPositive);
+pub const J2N: Operation = Operation::Jump(Target::I2, Condition::Negative);
+pub const J2Z: Operation = Operation::Jump(Target::I2, Condition::Zero);
+pub const J2P: Operation = Operation::Jump(Target::I2, Condition::Positive);
+pub const J2NN: Operation = Operation::Jump(Target::I2, Condition::NonNegative);
+pub const J2NZ: Operation = Operation::Jump(Target::I2, Condition::NonZero);
+pub const J2NP: Operation = Operation::Jump(Target::I2, Condition::NonPositive);
+pub const J3N: Operation = Operation::Jump(Target::I3, Condition::Negative);
+pub const J3Z: Operation = Operation::Jump(Target::I3, Condition::Zero);
+pub const J3P: Operation = Operation::Jump(Target::I3, Condition::Positive);
+pub const J3NN: Operation = Operation::Jump(Target::I3, Condition::NonNegative);
+pub const J3NZ: Operation = Operation::Jump(Target::I3, Condition::NonZero);
+pub const J3NP: Operation = Operation::Jump(Target::I3, Condition::NonPositive);
+pub const J4N: Operation = Operation::Jump(Target::I4, Condition::Negative);
+pub const J4Z: Operation = Operation::Jump(Target::I4, Condition::Zero);
+pub const J4P: Operation = Operation::Jump(Target::I4, Condition::Positive);
+pub const J4NN: Operation = Operation::Jump(Target::I4, Condition::NonNegative);
+pub const J4NZ: Operation = Operation::Jump(Target::I4, Condition::NonZero);
+pub const J4NP: Operation = Operation::Jump(Target::I4, Condition::NonPositive);
+pub const J5N: Operation = Operation::Jump(Target::I5, Condition::Negative);
+pub const J5Z: Operation = Operation::Jump(Target::I5, Condition::Zero);
+pub const J5P: Operation = Operation::Jump(Target::I5, Condition::Positive);
+pub const J5NN: Operation = Operation::Jump(Target::I5, Condition::NonNegative);
+pub const J5NZ: Operation = Operation::Jump(Target::I5, Condition::NonZero);
+pub const J5NP: Operation = Operation::Jump(Target::I5, Condition::NonPositive);
+pub const J6N: Operation = Operation::Jump(Target::I6, Condition::Negative);
+pub const J6Z: Operation = Operation::Jump(Target::I6, Condition::Zero);
+pub const J6P: Operation = Operation::Jump(Target::I6, Condition::Positive);
+pub const J6NN: Operation = Operation::Jump(Target::I6, Condition::NonNegative);
+pub const J6NZ: Operation = Operation::Jump(Target::I6, Condition::NonZero);
+pub const J6NP: Operation = Operation::Jump(Target::I6, Condition::NonPositive);
+pub const JXN: Operation = Operation::Jump(Target::X, Condition::Negative);
+pub const JXZ: Operation = Operation::Jump(Target::X, Condition::Zero);
+pub const JXP: Operation = Operation::Jump(Target::X, Condition::Positive);
+pub const JXNN: Operation = Operation::Jump(Target::X, Condition::NonNegative);
+pub const JXNZ: Operation = Operation::Jump(Target::X, Condition::NonZero);
+pub const JXNP: Operation = Operation::Jump(Target::X, Condition::NonPositive);
+pub const JXE: Operation = Operation::Jump(Target::X, Condition::Even);
+pub const JXO: Operation = Operation::Jump(Target::X, Condition::Odd);
+pub const INCA: Operation = Operation::Inc(Target::A);
+pub const DECA: Operation = Operation::Dec(Target::A);
+pub const ENTA: Operation = Operation::Ent(Target::A);
+pub const ENNA: Operation = Operation::Enn(Target::A);
+pub const INC1: Operation = Operation::Inc(Target::I1);
+pub const DEC1: Operation = Operation::Dec(Target::I1);
+pub const ENT1: Operation = Operation::Ent(Target::I1);
+pub const ENN1: Operation = Operation::Enn(Target::I1);
+pub const INC2: Operation = Operation::Inc(Target::I2);
+pub const DEC2: Operation = Operation::Dec(Target::I2);
+pub const ENT2: Operation = Operation::Ent(Target::I2);
+pub const ENN2: Operation = Operation::Enn(Target::I2);
+pub const INC3: Operation = Operation::Inc(Target::I3);
+pub const DEC3: Operation = Operation::Dec(Target::I3);
+pub const ENT3: Operation = Operation::Ent(Target::I3);
+pub const ENN3: Operation = Operation::Enn(Target::I3);
+pub const INC4: Operation = Operation::Inc(Target::I4);
+pub const DEC4: Operation = Operation::Dec(Target::I4);
+pub const ENT4: Operation = Operation::Ent(Target::I4);
+pub const ENN4: Operation = Operation::Enn(Target::I4);
+pub const INC5: Operation = Operation::Inc(Target::I5);
+pub const DEC5: Operation = Operation::Dec(Target::I5);
+pub const ENT5: Operation = Operation::Ent(Target::I5);
+pub const ENN5: Operation = Operation::Enn(Target::I5);
+pub const INC6: Operation = Operation::Inc(Target::I6);
+pub const DEC6: Operation = Operation::Dec(Target::I6);
+pub const ENT6: Operation = Operation::Ent(Target::I6);
+pub const ENN6: Operation = Operation::Enn(Target::I6);
+pub const INCX: Operation = Operation::Inc(Target::X);
+pub const DECX: Operation = Operation::Dec(Target::X);
+pub const ENTX: Operation = Operation::Ent(Target::X);
+pub const ENNX: Operation = Operation::Enn(Target::X);
+pub const CMPA: Operation = Operation::Cmp(Target::A);
+pub const FCMP: Operation = Operation::Fcmp;
+pub const CMP1: Operation = Operation::Cmp(Target::I1);
+pub const CMP2: Operation = Operation::Cmp(Target::I2);
+pub const CMP3: Operation = Operation::Cmp(Target::I3);
+pub const CMP4: Operation = Operation::Cmp(Target::I4);
+pub const CMP5: Operation = Operation::Cmp(Target::I5);
+pub const CMP6: Operation = Operation::Cmp(Target::I6);
+pub const CMPX: Operation = Operation::Cmp(Target::X);
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    computer::{Computer, HaltReason},
+    instruction::Instruction,
+    program::Program,
+    word::Word,
+  };
+
+  #[rstest]
+  #[case(mix! { NOP }, Instruction::new(true, 0, 0, 0, NOP))]
+  #[case(mix! { LDA 2000 }, Instruction::new(true, 2000, 0, 5, LDA))]
+  #[case(mix! { LDA -2000 }, Instruction::new(false, 2000, 0, 5, LDA))]
+  #[case(mix! { LDX 2000,3 }, Instruction::new(true, 2000, 3, 5, LDX))]
+  #[case(mix! { STA 3000,(1:5) }, Instruction::new(true, 3000, 0, 13, STA))]
+  #[case(mix! { ST2 3000,4(0:2) }, Instruction::new(true, 3000, 4, 2, ST2))]
+  #[case(mix! { STJ 3000 }, Instruction::new(true, 3000, 0, 2, STJ))]
+  #[case(mix! { MOVE 1000(3) }, Instruction::new(true, 1000, 0, 3, MOVE))]
+  #[case(mix! { OUT 1000(18) }, Instruction::new(true, 1000, 0, 18, OUT))]
+  #[case(mix! { ENTA 5 }, Instruction::new(true, 5, 0, 2, ENTA))]
+  #[case(mix! { J3NP 100 }, Instruction::new(true, 100, 0, 5, J3NP))]
+  #[case(mix! { FADD 100 }, Instruction::new(true, 100, 0, 6, FADD))]
+  fn test_mix(#[case] program: Program, #[case] expected: Instruction) {
+    assert_eq!(program.instructions, [expected]);
+  }
+
+  #[test]
+  fn test_execute() {
+    let mut computer = Computer::new();
+    let program = mix! {
+      ENT1 3;
+      INCA 2;
+      DEC1 1;
+      J1P 1;
+      STA 100,(4:5);
+      HLT;
+    };
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::new(6, Some(true)));
+    assert_eq!(computer.memory[100], Word::new(6, Some(true)));
+  }
+
+  #[rstest]
+  #[case(LDA, 4096, 0, None)]
+  #[case(LDA, 0, 7, None)]
+  #[case(LDA, 0, 0, Some(14))]
+  #[case(ENTA, 0, 0, Some(5))]
+  #[case(OUT, 0, 0, Some(64))]
+  #[should_panic]
+  fn test_from_parts_invalid(
+    #[case] operation: Operation,
+    #[case] address: i64,
+    #[case] index: i64,
+    #[case] field: Option<i64>,
+  ) {
+    Instruction::from_parts(operation, address, index, field);
+  }
+}
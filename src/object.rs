@@ -0,0 +1,588 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  fs::File,
+  io::{self, BufRead, BufReader, BufWriter, Write},
+  path::Path,
+};
+
+use crate::{
+  assembler::Assembly, byte::ByteSize, computer::MEMORY, error::LinkError, sourcemap::SourceMap,
+  symbols::SymbolTable, word::Word, Signed,
+};
+
+/// Relocatable module of a MIXAL source, which [`link`] puts together with
+/// others into one program
+///
+/// The words that move with the module have their locations from 0, the
+/// linker places the modules one after another. A word keeps the values the
+/// linker adds to it: the location of its module or the value of a symbol
+/// another module defines.
+///
+/// An object is kept as text, a line for the byte size, the start and each
+/// symbol and word:
+///
+/// ```text
+/// BYTES 64
+/// START R 0
+/// SYMBOL MAXIMUM R 0
+/// WORD R 0 + 0 2 0 5 8 A*
+/// WORD R 1 + 0 0 0 2 39 A=PRINT
+/// ```
+///
+/// `R` marks a value that moves with the module and `A` one that does not.
+/// A word is written as its sign and bytes, then the values added to it:
+/// `A` to its address and `W` to the whole word, of the module `*` or of the
+/// symbol `=NAME`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Object {
+  /// Byte size of the machine the module is assembled for
+  pub byte_size: ByteSize,
+  pub words: Vec<ObjectWord>,
+  /// Symbols the module defines for the others, by name
+  pub symbols: BTreeMap<String, Value>,
+  /// Location the program starts at, given by END
+  pub start: Option<Value>,
+}
+
+/// Word of a relocatable module
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectWord {
+  /// Location of the word, from the start of the module if it moves with it
+  pub location: usize,
+  /// Whether the location moves with the module
+  pub relocatable: bool,
+  pub word: Word,
+  /// Values the linker adds to the word
+  pub relocations: Vec<Relocation>,
+}
+
+/// Value the linker adds to a word
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relocation {
+  pub part: Part,
+  /// Symbol whose value is added, none for the location of the module
+  pub symbol: Option<String>,
+}
+
+/// Part of a word the linker adds to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+  /// Address of an instruction, the sign and bytes 1-2
+  Address,
+  /// Whole word, such as that of CON
+  Word,
+}
+
+/// Value of a symbol or of the start of a module
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Value {
+  pub value: i64,
+  /// Whether the value is a location that moves with the module
+  pub relocatable: bool,
+}
+
+impl Object {
+  /// Reads the object from the file
+  pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::from_reader(BufReader::new(File::open(path)?))
+  }
+
+  /// Writes the object to the file
+  pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    self.to_writer(&mut writer)?;
+    writer.flush()
+  }
+
+  /// Reads the object from the text, a line that does not read is refused
+  pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+    let mut byte_size = None;
+    let mut words = Vec::new();
+    let mut symbols = BTreeMap::new();
+    let mut start = None;
+
+    for (index, line) in reader.lines().enumerate() {
+      let line = line?;
+      let invalid = || {
+        io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!("Line {} is not part of an object", index + 1),
+        )
+      };
+      let fields = line.split_whitespace().collect::<Vec<_>>();
+
+      match fields.as_slice() {
+        ["BYTES", size] => {
+          let size = size.parse().ok().and_then(ByteSize::new);
+
+          byte_size = Some(size.ok_or_else(invalid)?);
+        }
+        ["START", kind, value] => start = Some(value_of(kind, value).ok_or_else(invalid)?),
+        ["SYMBOL", name, kind, value] => {
+          symbols.insert(name.to_string(), value_of(kind, value).ok_or_else(invalid)?);
+        }
+        ["WORD", kind, location, sign, rest @ ..] if rest.len() >= 5 => {
+          let word = word_of(sign, &rest[..5]).ok_or_else(invalid)?;
+          let location = location.parse().map_err(|_| invalid())?;
+          let relocatable = relocatable(kind).ok_or_else(invalid)?;
+          let relocations = rest[5..]
+            .iter()
+            .map(|text| relocation_of(text))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?;
+
+          words.push(ObjectWord {
+            location,
+            relocatable,
+            word,
+            relocations,
+          });
+        }
+        _ => return Err(invalid()),
+      }
+    }
+
+    Ok(Self {
+      byte_size: byte_size
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Object has no byte size"))?,
+      words,
+      symbols,
+      start,
+    })
+  }
+
+  /// Writes the object as text
+  pub fn to_writer(&self, mut writer: impl Write) -> io::Result<()> {
+    let kind = |relocatable: bool| if relocatable { 'R' } else { 'A' };
+
+    writeln!(writer, "BYTES {}", self.byte_size.get())?;
+
+    if let Some(start) = self.start {
+      writeln!(writer, "START {} {}", kind(start.relocatable), start.value)?;
+    }
+
+    for (name, value) in &self.symbols {
+      writeln!(
+        writer,
+        "SYMBOL {} {} {}",
+        name,
+        kind(value.relocatable),
+        value.value
+      )?;
+    }
+
+    for word in &self.words {
+      write!(
+        writer,
+        "WORD {} {} {}",
+        kind(word.relocatable),
+        word.location,
+        if word.word.read_sign() { '+' } else { '-' }
+      )?;
+
+      for byte in word.word.bytes() {
+        write!(writer, " {}", byte)?;
+      }
+
+      for relocation in &word.relocations {
+        let part = match relocation.part {
+          Part::Address => 'A',
+          Part::Word => 'W',
+        };
+
+        match &relocation.symbol {
+          Some(symbol) => write!(writer, " {}={}", part, symbol)?,
+          None => write!(writer, " {}*", part)?,
+        }
+      }
+
+      writeln!(writer)?;
+    }
+
+    Ok(())
+  }
+
+  /// Number of locations the words that move with the module take
+  pub fn size(&self) -> usize {
+    self
+      .words
+      .iter()
+      .filter(|word| word.relocatable)
+      .map(|word| word.location + 1)
+      .max()
+      .unwrap_or(0)
+  }
+}
+
+impl Part {
+  /// Difference of the part of the word from that of the other word, none if
+  /// they differ elsewhere
+  pub(crate) fn difference(self, word: &Word, other: &Word, byte_size: ByteSize) -> Option<i64> {
+    match self {
+      Self::Address if word.to_bytes()[2..] != other.to_bytes()[2..] => None,
+      Self::Address => Some(address(word, byte_size) - address(other, byte_size)),
+      Self::Word => Some(byte_size.value(word) - byte_size.value(other)),
+    }
+  }
+
+  /// Adds the value to the part of the word, a result that does not fit is
+  /// refused
+  fn add(self, word: &mut Word, value: i64, byte_size: ByteSize) -> Result<(), LinkError> {
+    match self {
+      Self::Address => {
+        let address = address(word, byte_size) + value;
+
+        if address.unsigned_abs() >= byte_size.capacity(2) {
+          return Err(LinkError::OutOfRange(address));
+        }
+
+        let [high, low] = byte_size.bytes(address.unsigned_abs());
+
+        word.set_byte(1, high);
+        word.set_byte(2, low);
+        word.write_sign(address >= 0);
+      }
+      Self::Word => {
+        let value = byte_size.value(word) + value;
+
+        if value.unsigned_abs() >= byte_size.capacity(Word::BYTES as u32) {
+          return Err(LinkError::OutOfRange(value));
+        }
+
+        *word = byte_size.word(value.unsigned_abs(), value >= 0);
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Reads the address of an instruction, the sign and bytes 1-2
+fn address(word: &Word, byte_size: ByteSize) -> i64 {
+  let bytes = word.to_bytes();
+  let magnitude = bytes[0] as i64 * byte_size.get() as i64 + bytes[1] as i64;
+
+  if word.read_sign() {
+    magnitude
+  } else {
+    -magnitude
+  }
+}
+
+fn relocatable(kind: &str) -> Option<bool> {
+  match kind {
+    "R" => Some(true),
+    "A" => Some(false),
+    _ => None,
+  }
+}
+
+fn value_of(kind: &str, value: &str) -> Option<Value> {
+  Some(Value {
+    value: value.parse().ok()?,
+    relocatable: relocatable(kind)?,
+  })
+}
+
+fn word_of(sign: &str, bytes: &[&str]) -> Option<Word> {
+  let sign = match sign {
+    "+" => true,
+    "-" => false,
+    _ => return None,
+  };
+  let mut word_bytes = [0; Word::BYTES];
+
+  for (byte, text) in word_bytes.iter_mut().zip(bytes) {
+    *byte = text.parse().ok()?;
+  }
+
+  Some(Word::from_bytes(word_bytes, sign))
+}
+
+fn relocation_of(text: &str) -> Option<Relocation> {
+  let part = match text.get(..1)? {
+    "A" => Part::Address,
+    "W" => Part::Word,
+    _ => return None,
+  };
+  let symbol = match &text[1..] {
+    "*" => None,
+    rest => Some(rest.strip_prefix('=')?.to_string()),
+  };
+
+  Some(Relocation { part, symbol })
+}
+
+/// Links the objects into one program, the words that move with their
+/// modules placed from the origin one module after another
+///
+/// The symbols of every module are known to the others. A module uses its
+/// own labels, so modules can share a label such as EXIT, but a symbol
+/// that a module takes from the others must be defined by exactly one of
+/// them. A label more than one module defines is left out of the symbols of
+/// the program. At most one module may give the start.
+pub fn link(objects: &[Object], origin: usize) -> Result<Assembly, LinkError> {
+  let byte_size = objects
+    .first()
+    .map_or(ByteSize::BINARY, |object| object.byte_size);
+
+  if objects.iter().any(|object| object.byte_size != byte_size) {
+    return Err(LinkError::ByteSizeMismatch);
+  }
+
+  let mut bases = Vec::with_capacity(objects.len());
+  let mut next = origin;
+
+  for object in objects {
+    bases.push(next as i64);
+    next += object.size();
+  }
+
+  let place = |value: Value, base: i64| match value.relocatable {
+    true => value.value + base,
+    false => value.value,
+  };
+  let mut symbols = BTreeMap::new();
+  let mut duplicates = BTreeSet::new();
+  let mut start = None;
+
+  for (object, &base) in objects.iter().zip(&bases) {
+    for (name, &value) in &object.symbols {
+      if symbols.insert(name.clone(), place(value, base)).is_some() {
+        duplicates.insert(name.clone());
+      }
+    }
+
+    if let Some(value) = object.start {
+      if start.is_some() {
+        return Err(LinkError::MultipleStarts);
+      }
+
+      start = Some(place(value, base));
+    }
+  }
+
+  let mut words = Vec::new();
+
+  for (object, &base) in objects.iter().zip(&bases) {
+    for object_word in &object.words {
+      let location = match object_word.relocatable {
+        true => object_word.location as i64 + base,
+        false => object_word.location as i64,
+      };
+
+      if !(0..MEMORY as i64).contains(&location) {
+        return Err(LinkError::InvalidLocation(location));
+      }
+
+      let mut word = object_word.word;
+
+      for relocation in &object_word.relocations {
+        let value = match &relocation.symbol {
+          Some(name) if duplicates.contains(name) => {
+            return Err(LinkError::DuplicateSymbol(name.clone()))
+          }
+          Some(name) => *symbols
+            .get(name)
+            .ok_or_else(|| LinkError::UndefinedSymbol(name.clone()))?,
+          None => base,
+        };
+
+        relocation.part.add(&mut word, value, byte_size)?;
+      }
+
+      words.push((location as usize, word));
+    }
+  }
+
+  symbols.retain(|name, _| !duplicates.contains(name));
+
+  let start = start
+    .map(|start| match usize::try_from(start) {
+      Ok(start) if start < MEMORY => Ok(start),
+      _ => Err(LinkError::InvalidLocation(start)),
+    })
+    .transpose()?;
+
+  Ok(Assembly {
+    words,
+    start,
+    symbols: SymbolTable {
+      symbols,
+      literals: BTreeMap::new(),
+    },
+    source_map: SourceMap::default(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    assembler::Assembler,
+    computer::{Computer, HaltReason},
+    error::AssemblyErrorKind,
+  };
+
+  const MAIN: &str = "
+START  LDA  VALUE
+       JMP  DOUBLE
+       STA  RESULT
+       HLT
+VALUE  CON  21
+RESULT CON  0
+       END  START
+";
+
+  const LIBRARY: &str = "
+* Doubles rA
+DOUBLE STJ  EXIT
+       STA  TEMP
+       ADD  TEMP
+EXIT   JMP  *
+TEMP   CON  0
+";
+
+  fn object(source: &str) -> Object {
+    Assembler::new().assemble_object(source).unwrap()
+  }
+
+  #[test]
+  fn test_link() {
+    let assembly = link(&[object(MAIN), object(LIBRARY)], 100).unwrap();
+
+    assert_eq!(assembly.start, Some(100));
+    assert_eq!(assembly.symbols.get("DOUBLE"), Some(106));
+    assert_eq!(assembly.symbols.get("RESULT"), Some(105));
+
+    let mut computer = Computer::new();
+
+    assert_eq!(
+      computer.execute(&assembly.to_program()),
+      Ok(HaltReason::Halted)
+    );
+    assert_eq!(computer.a, Word::new(42, Some(true)));
+    assert_eq!(computer.memory[105], Word::new(42, Some(true)));
+  }
+
+  #[test]
+  fn test_link_shared_label() {
+    let main = "
+START  LDA  VALUE
+       JMP  DOUBLE
+       JMP  EXIT
+VALUE  CON  21
+EXIT   HLT
+       END  START
+";
+    let assembly = link(&[object(main), object(LIBRARY)], 100).unwrap();
+
+    assert_eq!(assembly.symbols.get("EXIT"), None);
+    assert_eq!(assembly.symbols.get("DOUBLE"), Some(105));
+
+    let mut computer = Computer::new();
+
+    assert_eq!(
+      computer.execute(&assembly.to_program()),
+      Ok(HaltReason::Halted)
+    );
+    assert_eq!(computer.a, Word::new(42, Some(true)));
+    assert_eq!(computer.pc, 105);
+    assert!(link(&[object(LIBRARY), object(LIBRARY)], 0).is_ok());
+  }
+
+  #[test]
+  fn test_assemble_object() {
+    let object = object(
+      "
+       LDA  X
+X      CON  TABLE+2
+       JMP  PRINT+1
+       ORIG 2000
+       CON  X-*
+       END  X
+",
+    );
+    let mut text = Vec::new();
+
+    object.to_writer(&mut text).unwrap();
+
+    assert_eq!(
+      String::from_utf8(text).unwrap(),
+      "BYTES 64
+START R 1
+SYMBOL X R 1
+WORD R 0 + 0 1 0 5 8 A*
+WORD R 1 + 0 0 0 0 2 W=TABLE
+WORD R 2 + 0 1 0 0 39 A=PRINT
+WORD A 2000 - 0 0 0 31 15 W*
+"
+    );
+    assert_eq!(object.size(), 3);
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let object = object(LIBRARY);
+
+    object.save(file.path()).unwrap();
+
+    assert_eq!(Object::open(file.path()).unwrap(), object);
+  }
+
+  #[rstest]
+  #[case(" LDA X*2\nX CON 0", 1, AssemblyErrorKind::NotRelocatable("X*2".into()))]
+  #[case(" LDA 0,INDEX", 1, AssemblyErrorKind::NotRelocatable("0,INDEX".into()))]
+  #[case(" CON 1\n END TABLE", 2, AssemblyErrorKind::NotRelocatable("TABLE".into()))]
+  #[case(" ORIG SIZE\n CON 0", 2, AssemblyErrorKind::NotRelocatable("0".into()))]
+  #[case("X EQU Y\nY CON 0", 2, AssemblyErrorKind::UndefinedSymbol("Y".into()))]
+  fn test_assemble_object_invalid(
+    #[case] source: &str,
+    #[case] line: usize,
+    #[case] kind: AssemblyErrorKind,
+  ) {
+    let error = Assembler::new().assemble_object(source).unwrap_err();
+
+    assert_eq!((error.line, error.kind), (line, kind));
+  }
+
+  #[rstest]
+  #[case(vec![object(MAIN)], 0, LinkError::UndefinedSymbol("DOUBLE".into()))]
+  #[case(
+    vec![object(MAIN), object(LIBRARY), object(LIBRARY)],
+    0,
+    LinkError::DuplicateSymbol("DOUBLE".into())
+  )]
+  #[case(vec![object(" END 0"), object(" END 0")], 0, LinkError::MultipleStarts)]
+  #[case(vec![object(MAIN), object(LIBRARY)], 3990, LinkError::InvalidLocation(4000))]
+  #[case(vec![object(" JMP *+4000")], 100, LinkError::OutOfRange(4100))]
+  #[case(
+    vec![object(" HLT"), Assembler::decimal().assemble_object(" HLT").unwrap()],
+    0,
+    LinkError::ByteSizeMismatch
+  )]
+  fn test_link_invalid(
+    #[case] objects: Vec<Object>,
+    #[case] origin: usize,
+    #[case] expected: LinkError,
+  ) {
+    assert_eq!(link(&objects, origin).unwrap_err(), expected);
+  }
+
+  #[rstest]
+  #[case("WORD R 0 + 0 0 0 0 5\n")]
+  #[case("BYTES 64\nWORD R 0 + 0 0 0 5\n")]
+  #[case("BYTES 64\nWORD X 0 + 0 0 0 0 5\n")]
+  #[case("BYTES 64\nWORD R 0 + 0 0 0 0 5 A\n")]
+  #[case("BYTES 64\nSYMBOL X R\n")]
+  #[case("BYTES 63\n")]
+  fn test_from_reader_invalid(#[case] text: &str) {
+    let error = Object::from_reader(text.as_bytes()).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  }
+}
@@ -0,0 +1,307 @@
+use crate::{
+  byte::ByteSize,
+  value::MixValue,
+  word::{Overflowing, Word},
+  Signed,
+};
+
+/// Represents two words taken together as ten bytes, the way MUL, DIV,
+/// SLAX, SRAX, SLC, SRC, NUM and CHAR see rA and rX
+///
+/// Each word keeps its own sign, the operations on the pair work on the
+/// bytes and leave the signs untouched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WordPair {
+  /// Bytes 1-5 of the pair, rA on the machine
+  pub high: Word,
+  /// Bytes 6-10 of the pair, rX on the machine
+  pub low: Word,
+}
+
+impl WordPair {
+  pub const BYTES: usize = 10;
+
+  pub fn new(high: Word, low: Word) -> Self {
+    Self { high, low }
+  }
+
+  /// Makes a pair of the magnitude in the byte size, only the remainder
+  /// modulo the capacity of ten bytes is kept
+  pub fn from_magnitude(
+    magnitude: u128,
+    byte_size: ByteSize,
+    high_sign: bool,
+    low_sign: bool,
+  ) -> Self {
+    let capacity = byte_size.capacity(5) as u128;
+
+    Self {
+      high: byte_size.word(((magnitude / capacity) % capacity) as u64, high_sign),
+      low: byte_size.word((magnitude % capacity) as u64, low_sign),
+    }
+  }
+
+  /// Reads the ten bytes as a magnitude in the byte size
+  pub fn magnitude(&self, byte_size: ByteSize) -> u128 {
+    byte_size.magnitude(&self.high) as u128 * byte_size.capacity(5) as u128
+      + byte_size.magnitude(&self.low) as u128
+  }
+
+  /// Joins the words into one number of ten bytes with the sign of the
+  /// high word
+  pub fn joined(&self) -> MixValue<{ Self::BYTES }> {
+    let (high, low) = (self.high.to_bytes(), self.low.to_bytes());
+    let bytes = std::array::from_fn(|index| match index {
+      0..=4 => high[index],
+      _ => low[index - 5],
+    });
+
+    MixValue::from_bytes(bytes, self.high.read_sign())
+  }
+
+  /// Splits the number of ten bytes between the words, the signs are left
+  /// untouched
+  pub fn split(&mut self, value: MixValue<{ Self::BYTES }>) {
+    let bytes = value.to_bytes();
+    let half = |offset: usize| std::array::from_fn(|index| bytes[offset + index]);
+
+    self.high = Word::from_bytes(half(0), self.high.read_sign());
+    self.low = Word::from_bytes(half(5), self.low.read_sign());
+  }
+
+  /// Returns the ten bytes, those of the high word first
+  pub fn bytes(&self) -> [u8; Self::BYTES] {
+    self.joined().to_bytes()
+  }
+
+  /// Replaces the ten bytes, the signs are left untouched
+  pub fn write_bytes(&mut self, bytes: [u8; Self::BYTES]) {
+    self.split(MixValue::from_bytes(bytes, true));
+  }
+
+  /// Divides the ten bytes by the value, giving the quotient with the sign
+  /// of the quotient and the remainder with the sign of the high word
+  ///
+  /// The quotient must fit into five bytes, otherwise the division
+  /// overflows and the pair is returned as is.
+  pub fn checked_div(&self, divisor: &Word, byte_size: ByteSize) -> Overflowing<WordPair> {
+    let magnitude = byte_size.magnitude(divisor);
+
+    if magnitude == 0 || byte_size.magnitude(&self.high) >= magnitude {
+      return Overflowing {
+        result: *self,
+        overflow: true,
+      };
+    }
+
+    let sign = self.high.read_sign();
+    let dividend = self.magnitude(byte_size);
+    let magnitude = magnitude as u128;
+
+    Overflowing {
+      result: Self {
+        high: byte_size.word((dividend / magnitude) as u64, sign == divisor.read_sign()),
+        low: byte_size.word((dividend % magnitude) as u64, sign),
+      },
+      overflow: false,
+    }
+  }
+
+  /// Shifts the ten bytes to the left, zeros come in from the right
+  pub fn shift_left_bytes(&mut self, count: usize) {
+    self.map_joined(|value| value.shift_left_bytes(count));
+  }
+
+  /// Shifts the ten bytes to the right, zeros come in from the left
+  pub fn shift_right_bytes(&mut self, count: usize) {
+    self.map_joined(|value| value.shift_right_bytes(count));
+  }
+
+  /// Shifts the ten bytes to the left circularly
+  pub fn rotate_left_bytes(&mut self, count: usize) {
+    self.map_joined(|value| value.rotate_left_bytes(count));
+  }
+
+  /// Shifts the ten bytes to the right circularly
+  pub fn rotate_right_bytes(&mut self, count: usize) {
+    self.map_joined(|value| value.rotate_right_bytes(count));
+  }
+
+  /// Changes the ten bytes taken together, the signs are left untouched
+  fn map_joined(&mut self, f: impl FnOnce(&mut MixValue<{ Self::BYTES }>)) {
+    let mut value = self.joined();
+
+    f(&mut value);
+    self.split(value);
+  }
+
+  /// Shifts the sixty bits of the packed words to the left, as SLB of the
+  /// binary extension does
+  pub fn shift_bits_left(&mut self, count: u32) {
+    let bits = self.bits() << count.min(60);
+
+    self.write_bits(bits);
+  }
+
+  /// Shifts the sixty bits of the packed words to the right, as SRB of the
+  /// binary extension does
+  pub fn shift_bits_right(&mut self, count: u32) {
+    let bits = self.bits() >> count.min(60);
+
+    self.write_bits(bits);
+  }
+
+  /// Packs the words into sixty bits, the high word first
+  fn bits(&self) -> u64 {
+    self.joined().pack()
+  }
+
+  /// Splits the sixty bits between the words, the bits above are dropped
+  fn write_bits(&mut self, bits: u64) {
+    self.split(MixValue::packed(bits, true));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  fn numbered() -> WordPair {
+    WordPair::new(
+      Word::from_bytes([1, 2, 3, 4, 5], false),
+      Word::from_bytes([6, 7, 8, 9, 10], true),
+    )
+  }
+
+  #[test]
+  fn test_bytes() {
+    let mut pair = numbered();
+
+    assert_eq!(pair.bytes(), [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    pair.write_bytes([10, 9, 8, 7, 6, 5, 4, 3, 2, 1]);
+
+    assert_eq!(pair.high, Word::from_bytes([10, 9, 8, 7, 6], false));
+    assert_eq!(pair.low, Word::from_bytes([5, 4, 3, 2, 1], true));
+  }
+
+  #[test]
+  fn test_joined() {
+    let mut pair = numbered();
+    let joined = pair.joined();
+
+    assert_eq!(
+      joined,
+      MixValue::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10], false)
+    );
+
+    pair.split(MixValue::from_bytes([0, 0, 0, 0, 0, 0, 0, 0, 0, 63], true));
+
+    assert_eq!(pair.high, Word::from_bytes([0; 5], false));
+    assert_eq!(pair.low, Word::from_bytes([0, 0, 0, 0, 63], true));
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, 0, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0])]
+  #[case(ByteSize::BINARY, 1 << 30, [0, 0, 0, 0, 1, 0, 0, 0, 0, 0])]
+  #[case(ByteSize::BINARY, (1 << 60) - 1, [63, 63, 63, 63, 63, 63, 63, 63, 63, 63])]
+  #[case(ByteSize::DECIMAL, 1_234_567_890_123, [0, 0, 0, 1, 23, 45, 67, 89, 1, 23])]
+  fn test_magnitude(#[case] byte_size: ByteSize, #[case] magnitude: u128, #[case] bytes: [u8; 10]) {
+    let pair = WordPair::from_magnitude(magnitude, byte_size, false, true);
+
+    assert_eq!(pair.bytes(), bytes);
+    assert_eq!(pair.magnitude(byte_size), magnitude);
+    assert!(!pair.high.read_sign());
+    assert!(pair.low.read_sign());
+  }
+
+  #[test]
+  fn test_magnitude_keeps_remainder() {
+    let pair = WordPair::from_magnitude((1 << 60) + 5, ByteSize::BINARY, true, true);
+
+    assert_eq!(pair.magnitude(ByteSize::BINARY), 5);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(ByteSize::BINARY, WordPair::new(Word::new(1, Some(true)), Word::new(0, Some(true))), Word::new(2, Some(true)), Word::new(1 << 29, Some(true)), Word::new(0, Some(true)), false)]
+  #[case(ByteSize::BINARY, WordPair::new(Word::new(0, Some(false)), Word::new(17, Some(true))), Word::new(3, Some(true)), Word::new(5, Some(false)), Word::new(2, Some(false)), false)]
+  #[case(ByteSize::DECIMAL, WordPair::new(Word::from_bytes([0, 0, 0, 0, 1], true), Word::from_bytes([0; 5], true)), Word::from_bytes([0, 0, 0, 0, 4], false), Word::from_bytes([25, 0, 0, 0, 0], false), Word::from_bytes([0; 5], true), false)]
+  #[case(ByteSize::BINARY, WordPair::new(Word::new(5, Some(true)), Word::new(1, Some(true))), Word::new(5, Some(true)), Word::new(5, Some(true)), Word::new(1, Some(true)), true)]
+  #[case(ByteSize::BINARY, WordPair::new(Word::new(0, Some(true)), Word::new(1, Some(true))), Word::new(0, Some(false)), Word::new(0, Some(true)), Word::new(1, Some(true)), true)]
+  fn test_checked_div(
+    #[case] byte_size: ByteSize,
+    #[case] pair: WordPair,
+    #[case] divisor: Word,
+    #[case] high: Word,
+    #[case] low: Word,
+    #[case] overflow: bool,
+  ) {
+    assert_eq!(
+      pair.checked_div(&divisor, byte_size),
+      Overflowing { result: WordPair::new(high, low), overflow }
+    );
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10])]
+  #[case(1, [2, 3, 4, 5, 6, 7, 8, 9, 10, 0], [0, 1, 2, 3, 4, 5, 6, 7, 8, 9])]
+  #[case(7, [8, 9, 10, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 1, 2, 3])]
+  #[case(10, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0, 0, 0])]
+  #[case(99, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0, 0, 0])]
+  fn test_shift(#[case] count: usize, #[case] left: [u8; 10], #[case] right: [u8; 10]) {
+    let mut pair = numbered();
+
+    pair.shift_left_bytes(count);
+    assert_eq!(pair.bytes(), left);
+
+    let mut pair = numbered();
+
+    pair.shift_right_bytes(count);
+    assert_eq!(pair.bytes(), right);
+    assert!(!pair.high.read_sign());
+    assert!(pair.low.read_sign());
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10])]
+  #[case(3, [4, 5, 6, 7, 8, 9, 10, 1, 2, 3], [8, 9, 10, 1, 2, 3, 4, 5, 6, 7])]
+  #[case(10, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10])]
+  #[case(12, [3, 4, 5, 6, 7, 8, 9, 10, 1, 2], [9, 10, 1, 2, 3, 4, 5, 6, 7, 8])]
+  fn test_rotate(#[case] count: usize, #[case] left: [u8; 10], #[case] right: [u8; 10]) {
+    let mut pair = numbered();
+
+    pair.rotate_left_bytes(count);
+    assert_eq!(pair.bytes(), left);
+
+    let mut pair = numbered();
+
+    pair.rotate_right_bytes(count);
+    assert_eq!(pair.bytes(), right);
+  }
+
+  #[rstest]
+  #[case(0, 1 << 30 | 1, 1 << 30 | 1)]
+  #[case(1, 1 << 31 | 2, 1 << 29)]
+  #[case(30, 1 << 30, 1)]
+  #[case(59, 1 << 59, 0)]
+  #[case(60, 0, 0)]
+  #[case(64, 0, 0)]
+  fn test_shift_bits(#[case] count: u32, #[case] left: u64, #[case] right: u64) {
+    let mut pair = WordPair::new(Word::new(1, Some(true)), Word::new(1, Some(false)));
+
+    pair.shift_bits_left(count);
+    assert_eq!(pair.bits(), left);
+
+    let mut pair = WordPair::new(Word::new(1, Some(true)), Word::new(1, Some(false)));
+
+    pair.shift_bits_right(count);
+    assert_eq!(pair.bits(), right);
+    assert!(pair.high.read_sign());
+    assert!(!pair.low.read_sign());
+  }
+}
@@ -0,0 +1,191 @@
+use std::io::{self, Write};
+
+use crate::{
+  device::{Device, PAGE_LINES},
+  error::DeviceError,
+  word::Word,
+};
+
+/// How the printer lays out its pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+  /// The lines as the program prints them, the pages are separated by form
+  /// feeds
+  #[default]
+  Plain,
+  /// Each page starts with its number and each line with its number on the
+  /// page, to read a report with
+  Listing,
+}
+
+/// Line printer typing its lines as text, such as to the standard output of
+/// the emulator
+///
+/// A line of 120 characters is printed without its trailing blanks, the
+/// bytes that are not a character are printed as `?`. A page holds 60 lines,
+/// the printer goes on to the next page once a page is full and on IOC 0.
+pub struct LinePrinter {
+  output: Box<dyn Write>,
+  style: Style,
+  /// Number of the page being printed, from 1
+  page: usize,
+  /// Number of lines printed on the page
+  line: usize,
+}
+
+impl LinePrinter {
+  /// Number of words in a line
+  pub const BLOCK_SIZE: usize = 24;
+
+  /// Creates the printer printing to the output
+  pub fn new(output: impl Write + 'static) -> Self {
+    Self {
+      output: Box::new(output),
+      style: Style::Plain,
+      page: 1,
+      line: 0,
+    }
+  }
+
+  /// Lays out the pages in the style
+  pub fn with_style(mut self, style: Style) -> Self {
+    self.style = style;
+    self
+  }
+
+  /// Number of the page being printed, from 1
+  pub fn page(&self) -> usize {
+    self.page
+  }
+
+  /// Number of lines printed on the page
+  pub fn line(&self) -> usize {
+    self.line
+  }
+
+  /// Ejects the page, the next line goes to the top of the next one
+  fn next_page(&mut self) -> io::Result<()> {
+    write!(self.output, "\x0c")?;
+    self.page += 1;
+    self.line = 0;
+
+    Ok(())
+  }
+
+  /// Creates the printer printing to the standard output
+  pub fn stdout() -> Self {
+    Self::new(io::stdout())
+  }
+}
+
+impl Device for LinePrinter {
+  fn block_size(&self) -> usize {
+    Self::BLOCK_SIZE
+  }
+
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    let line: String = block
+      .iter()
+      .map(|word| word.display_as_chars().to_string())
+      .collect();
+
+    if self.line == PAGE_LINES {
+      self.next_page()?;
+    }
+
+    self.line += 1;
+
+    match self.style {
+      Style::Plain => writeln!(self.output, "{}", line.trim_end())?,
+      Style::Listing => {
+        if self.line == 1 {
+          writeln!(self.output, "Page {}", self.page)?;
+        }
+
+        writeln!(self.output, "{:>2}  {}", self.line, line.trim_end())?;
+      }
+    }
+
+    self.output.flush()?;
+
+    Ok(())
+  }
+
+  /// Skips to the top of the next page if M is zero
+  fn control(&mut self, operation: i64, _x: u64) -> Result<(), DeviceError> {
+    if operation != 0 {
+      return Err(DeviceError::Unsupported);
+    }
+
+    self.next_page()?;
+    self.output.flush()?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{charcode, device::Paper};
+
+  #[test]
+  fn test_print() {
+    let paper = Paper::default();
+    let mut printer = LinePrinter::new(paper.clone());
+    let mut line = charcode::str_to_words("TOTAL  42").unwrap();
+
+    line.resize(LinePrinter::BLOCK_SIZE, Word::default());
+
+    printer.write(&line).unwrap();
+    printer.control(0, 0).unwrap();
+    printer.write(&[Word::default(); 24]).unwrap();
+
+    assert_eq!(paper.text(), "TOTAL  42\n\x0c\n");
+  }
+
+  fn line(text: &str) -> Vec<Word> {
+    let mut line = charcode::str_to_words(text).unwrap();
+
+    line.resize(LinePrinter::BLOCK_SIZE, Word::default());
+    line
+  }
+
+  #[test]
+  fn test_full_page() {
+    let paper = Paper::default();
+    let mut printer = LinePrinter::new(paper.clone());
+
+    for _ in 0..PAGE_LINES + 1 {
+      printer.write(&line("X")).unwrap();
+    }
+
+    assert_eq!(printer.page(), 2);
+    assert_eq!(printer.line(), 1);
+    assert_eq!(paper.text(), "X\n".repeat(60) + "\x0cX\n");
+  }
+
+  #[test]
+  fn test_listing() {
+    let paper = Paper::default();
+    let mut printer = LinePrinter::new(paper.clone()).with_style(Style::Listing);
+
+    printer.write(&line("FIRST   ")).unwrap();
+    printer.write(&line("SECOND")).unwrap();
+    printer.control(0, 0).unwrap();
+    printer.write(&line("THIRD")).unwrap();
+
+    assert_eq!(
+      paper.text(),
+      "Page 1\n 1  FIRST\n 2  SECOND\n\x0cPage 2\n 1  THIRD\n"
+    );
+  }
+
+  #[test]
+  fn test_read_unsupported() {
+    let mut printer = LinePrinter::new(io::sink());
+
+    assert_eq!(printer.read(), Err(DeviceError::Unsupported));
+    assert_eq!(printer.control(1, 0), Err(DeviceError::Unsupported));
+  }
+}
@@ -1,17 +1,343 @@
-use crate::instruction::Instruction;
+use std::{
+  fs::File,
+  io::{self, BufReader, BufWriter, Read, Write},
+  path::Path,
+};
 
+use crate::{
+  byte::ByteSize,
+  computer::MEMORY,
+  device::FileFormat,
+  instruction::Instruction,
+  sourcemap::{SourceLine, SourceMap},
+  word::Word,
+};
+
+/// Marks the file of a program
+const MAGIC: &[u8; 4] = b"MIXI";
+
+/// Version of the layout of the file
+const VERSION: u8 = 1;
+
+/// Program for the machine: its instructions, the words it keeps elsewhere
+/// in memory and the location it starts at
+///
+/// The instructions go one after another from the location the program is
+/// loaded at, encoded for the byte size of the machine. The segments hold
+/// words already encoded, such as the data that ORIG sets apart, and go to
+/// their own origins.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Program {
   pub instructions: Vec<Instruction>,
+  /// Origins of the segments and their words
+  pub segments: Vec<(usize, Vec<Word>)>,
+  /// Location the program starts at
+  pub entry: usize,
+  /// Lines of the source the words come from, empty unless the program is
+  /// assembled, and not kept in the file of the program
+  pub source_map: SourceMap,
 }
 
 impl Program {
   pub fn new() -> Self {
     Self {
       instructions: Vec::new(),
+      segments: Vec::new(),
+      entry: 0,
+      source_map: SourceMap::default(),
     }
   }
 
+  /// Finds the line of the source the word at the location comes from, such
+  /// as that of the instruction a fault stops the machine at, given by
+  /// [`Computer::location`](crate::computer::Computer::location)
+  pub fn source_line(&self, location: i64) -> Option<&SourceLine> {
+    self.source_map.get(usize::try_from(location).ok()?)
+  }
+
   pub fn add(&mut self, instruction: Instruction) {
     self.instructions.push(instruction);
   }
+
+  /// Adds the words as a segment from the origin
+  pub fn add_segment(&mut self, origin: usize, words: Vec<Word>) {
+    self.segments.push((origin, words));
+  }
+
+  /// Reads the program from the file
+  pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::from_reader(BufReader::new(File::open(path)?))
+  }
+
+  /// Writes the program to the file, the instructions encoded for the byte
+  /// size
+  pub fn save(&self, path: impl AsRef<Path>, byte_size: ByteSize) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    self.to_writer(&mut writer, byte_size)?;
+    writer.flush()
+  }
+
+  /// Reads the image of memory, a first segment at location 0 is decoded as
+  /// the instructions of the program and the rest are its segments
+  ///
+  /// A segment that does not fit into memory is refused, and so is a
+  /// location to start at out of memory.
+  pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+    let mut bytes = Vec::new();
+
+    reader.read_to_end(&mut bytes)?;
+
+    let image = Image::decode(&bytes)?;
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    if image
+      .segments
+      .iter()
+      .any(|(origin, words)| origin + words.len() > MEMORY)
+    {
+      return Err(invalid("Program has a segment out of memory"));
+    }
+
+    if image.entry >= MEMORY {
+      return Err(invalid("Program starts out of memory"));
+    }
+
+    let mut segments = image.segments;
+    let words = match segments.first() {
+      Some((0, _)) => segments.remove(0).1,
+      _ => Vec::new(),
+    };
+    let instructions = words
+      .iter()
+      .map(|word| Instruction::decode(word, image.byte_size))
+      .collect::<Result<_, _>>()
+      .map_err(|error| invalid(&error.to_string()))?;
+
+    Ok(Self {
+      instructions,
+      segments,
+      entry: image.entry,
+      source_map: SourceMap::default(),
+    })
+  }
+
+  /// Writes the program as an image of memory, the instructions encoded for
+  /// the byte size as the first segment at location 0 and then the segments
+  pub fn to_writer(&self, mut writer: impl Write, byte_size: ByteSize) -> io::Result<()> {
+    let words = self
+      .instructions
+      .iter()
+      .map(|instruction| instruction.encode(byte_size))
+      .collect();
+    let image = Image {
+      byte_size,
+      segments: [(0, words)]
+        .into_iter()
+        .chain(self.segments.iter().cloned())
+        .collect(),
+      entry: self.entry,
+    };
+
+    writer.write_all(&image.encode()?)
+  }
+}
+
+impl Default for Program {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Image of memory as a file keeps it
+///
+/// The file starts with `MIXI`, the version of the layout and the byte size.
+/// Then come the number of segments and each segment as its origin, the
+/// number of its words and the words, six bytes each with the sign first as
+/// 1 for minus. The location the program starts at ends the file. The
+/// numbers take two bytes, little endian.
+struct Image {
+  byte_size: ByteSize,
+  /// Origins of the segments and their words
+  segments: Vec<(usize, Vec<Word>)>,
+  entry: usize,
+}
+
+impl Image {
+  fn encode(&self) -> io::Result<Vec<u8>> {
+    let mut bytes = MAGIC.to_vec();
+
+    bytes.push(VERSION);
+    bytes.push(self.byte_size.get() as u8);
+    put_number(&mut bytes, self.segments.len())?;
+
+    for (origin, words) in &self.segments {
+      put_number(&mut bytes, *origin)?;
+      put_number(&mut bytes, words.len())?;
+
+      for word in words {
+        FileFormat::Mixi.encode(word, &mut bytes)?;
+      }
+    }
+
+    put_number(&mut bytes, self.entry)?;
+
+    Ok(bytes)
+  }
+
+  fn decode(mut bytes: &[u8]) -> io::Result<Self> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    if take(&mut bytes, MAGIC.len())? != MAGIC {
+      return Err(invalid("File is not a program"));
+    }
+
+    if take(&mut bytes, 1)?[0] != VERSION {
+      return Err(invalid("Program is of another version"));
+    }
+
+    let byte_size = ByteSize::new(take(&mut bytes, 1)?[0] as u32)
+      .ok_or_else(|| invalid("Byte size is not 64 through 100"))?;
+    let count = take_number(&mut bytes)?;
+    let mut segments = Vec::with_capacity(count);
+
+    for _ in 0..count {
+      let origin = take_number(&mut bytes)?;
+      let length = take_number(&mut bytes)?;
+      let mut words = Vec::with_capacity(length);
+
+      for _ in 0..length {
+        words.push(FileFormat::Mixi.decode(take(&mut bytes, FileFormat::Mixi.word_bytes())?));
+      }
+
+      segments.push((origin, words));
+    }
+
+    let entry = take_number(&mut bytes)?;
+
+    Ok(Self {
+      byte_size,
+      segments,
+      entry,
+    })
+  }
+}
+
+/// Appends the number as two bytes, a number that does not fit is refused
+fn put_number(bytes: &mut Vec<u8>, number: usize) -> io::Result<()> {
+  let number = u16::try_from(number)
+    .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Number does not fit the file"))?;
+
+  bytes.extend(number.to_le_bytes());
+
+  Ok(())
+}
+
+/// Takes the bytes off the front of the data
+fn take<'a>(bytes: &mut &'a [u8], count: usize) -> io::Result<&'a [u8]> {
+  if bytes.len() < count {
+    return Err(io::ErrorKind::UnexpectedEof.into());
+  }
+
+  let (taken, rest) = bytes.split_at(count);
+
+  *bytes = rest;
+
+  Ok(taken)
+}
+
+/// Takes a number of two bytes off the front of the data
+fn take_number(bytes: &mut &[u8]) -> io::Result<usize> {
+  let number = take(bytes, 2)?;
+
+  Ok(u16::from_le_bytes([number[0], number[1]]) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::instruction::{Operation, Target};
+
+  fn program() -> Program {
+    let mut program = Program::new();
+
+    program.add(Instruction::new(
+      false,
+      2000,
+      1,
+      3,
+      Operation::Ld(Target::A),
+    ));
+    program.add(Instruction::new(true, 0, 0, 2, Operation::Hlt));
+
+    program
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY)]
+  #[case(ByteSize::DECIMAL)]
+  fn test_round_trip_segments(#[case] byte_size: ByteSize) {
+    let mut program = program();
+    let mut bytes = Vec::new();
+
+    program.add_segment(2000, vec![Word::new(5, Some(false)), Word::default()]);
+    program.add_segment(0, vec![Word::new(7, Some(true))]);
+    program.entry = 1;
+    program.to_writer(&mut bytes, byte_size).unwrap();
+
+    assert_eq!(Program::from_reader(&bytes[..]).unwrap(), program);
+  }
+
+  #[test]
+  fn test_from_reader_without_instructions() {
+    let bytes = b"MIXI\x01\x40\x01\x00\x64\x00\x01\x00\x00\x00\x00\x00\x00\x07\x64\x00";
+    let program = Program::from_reader(&bytes[..]).unwrap();
+
+    assert!(program.instructions.is_empty());
+    assert_eq!(
+      program.segments,
+      vec![(100, vec![Word::new(7, Some(true))])]
+    );
+    assert_eq!(program.entry, 100);
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY)]
+  #[case(ByteSize::DECIMAL)]
+  fn test_round_trip(#[case] byte_size: ByteSize) {
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    program().save(file.path(), byte_size).unwrap();
+
+    assert_eq!(
+      std::fs::metadata(file.path()).unwrap().len(),
+      4 + 1 + 1 + 2 + 2 + 2 + 2 * 6 + 2
+    );
+    assert_eq!(Program::load(file.path()).unwrap(), program());
+  }
+
+  #[test]
+  fn test_layout() {
+    let mut bytes = Vec::new();
+
+    program().to_writer(&mut bytes, ByteSize::BINARY).unwrap();
+
+    assert_eq!(&bytes[..8], b"MIXI\x01\x40\x01\x00");
+    assert_eq!(&bytes[8..12], [0, 0, 2, 0]);
+    assert_eq!(&bytes[12..18], [1, 31, 16, 1, 3, 8]);
+  }
+
+  #[rstest]
+  #[case(b"MIXI\x01\x40\x01\x00\x00".to_vec(), io::ErrorKind::UnexpectedEof)]
+  #[case(b"MIXA\x01\x40\x00\x00\x00\x00".to_vec(), io::ErrorKind::InvalidData)]
+  #[case(b"MIXI\x02\x40\x00\x00\x00\x00".to_vec(), io::ErrorKind::InvalidData)]
+  #[case(b"MIXI\x01\x3f\x00\x00\x00\x00".to_vec(), io::ErrorKind::InvalidData)]
+  #[case(b"MIXI\x01\x40\x01\x00\xa1\x0f\x00\x00\x00\x00".to_vec(), io::ErrorKind::InvalidData)]
+  #[case([&b"MIXI\x01\x40\x01\x00\x9f\x0f\x02\x00"[..], &[0; 14]].concat(), io::ErrorKind::InvalidData)]
+  #[case(b"MIXI\x01\x40\x00\x00\xa0\x0f".to_vec(), io::ErrorKind::InvalidData)]
+  fn test_from_reader_invalid(#[case] bytes: Vec<u8>, #[case] kind: io::ErrorKind) {
+    assert_eq!(Program::from_reader(&bytes[..]).unwrap_err().kind(), kind);
+  }
 }
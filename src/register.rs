@@ -1,104 +1,141 @@
 use std::fmt;
 
-use crate::{Data, Signed};
-
-/// Represents a register with a 12-bit value and a sign bit
-pub struct Register {
-  data: u16,
-}
+use crate::{
+  byte::ByteSize,
+  error::{FieldError, OutOfRange},
+  field::FieldSpec,
+  value::MixValue,
+  word::{Overflowing, Word},
+  Data, FieldRead, FieldWrite, Signed,
+};
+
+/// Represents a register of two bytes and a sign
+pub type Register = MixValue<2>;
 
 impl Register {
-  const BYTES: usize = 2;
-
+  #[cfg(test)]
   #[rustfmt::skip]
   const SIGN_MASK:  u16 = 0b0001_0000_0000_0000;
 
   #[rustfmt::skip]
   const DATA_MASK:  u16 = 0b0000_1111_1111_1111;
 
-  #[rustfmt::skip]
-  const VALUE_MASK: u16 = 0b0001_1111_1111_1111;
-
   pub fn new(number: u16, sign: Option<bool>) -> Self {
-    let mut data = number & Self::DATA_MASK;
+    Self::packed(number as u64, sign == Some(true))
+  }
 
-    if let Some(sign) = sign {
-      if sign {
-        data |= Self::SIGN_MASK;
-      }
+  /// Creates the register as `new` does, but refuses a number with bits
+  /// above the two bytes rather than dropping them
+  pub fn try_new(number: u16, sign: Option<bool>) -> Result<Self, OutOfRange> {
+    if number & !Self::DATA_MASK != 0 {
+      return Err(OutOfRange(number as i64));
     }
 
-    Self { data }
+    Ok(Self::new(number, sign))
   }
 }
 
-impl Default for Register {
-  fn default() -> Self {
-    Self::new(0, None)
+/// Counting on the two bytes of the register, the way INCi and DECi do it
+impl Register {
+  /// Adds the number to the register, only the remainder modulo the
+  /// capacity of two bytes is kept and a zero result keeps the sign of the
+  /// register
+  ///
+  /// MIX leaves the register undefined when the result does not fit, the
+  /// overflow lets the caller decide what to do about it.
+  pub fn checked_inc(&self, value: i64, byte_size: ByteSize) -> Overflowing<Register> {
+    let capacity = byte_size.capacity(Self::BYTES as u32);
+    let sum = byte_size.value(&Word::from(*self)) + value;
+    let sign = if sum == 0 { self.read_sign() } else { sum > 0 };
+
+    Overflowing {
+      result: Register::from_bytes(byte_size.bytes(sum.unsigned_abs() % capacity), sign),
+      overflow: sum.unsigned_abs() >= capacity,
+    }
   }
-}
 
-impl Data<u16> for Register {
-  fn read(&self) -> u16 {
-    self.data & Self::VALUE_MASK
+  /// Subtracts the number from the register, the same as adding it with
+  /// the opposite sign
+  pub fn checked_dec(&self, value: i64, byte_size: ByteSize) -> Overflowing<Register> {
+    self.checked_inc(-value, byte_size)
   }
+}
 
-  fn read_data(&self) -> u16 {
-    self.data & Self::DATA_MASK
+/// The register is taken as a word whose bytes 1-3 are zero
+impl From<Register> for Word {
+  fn from(value: Register) -> Self {
+    Word::from_bytes(
+      [0, 0, 0, value.get_byte(1), value.get_byte(2)],
+      value.read_sign(),
+    )
   }
+}
 
-  fn read_with_modifier(&self, modifier: u16) -> u16 {
-    let mut result: u16 = 0;
-    let (left, right) = Self::split_modifier(modifier as u32);
-
-    assert!(right <= Self::BYTES as u32);
+/// Only the sign and bytes 4-5 of the word fit in the register
+impl From<Word> for Register {
+  fn from(value: Word) -> Self {
+    Register::from_bytes([value.get_byte(4), value.get_byte(5)], value.read_sign())
+  }
+}
 
-    for index in left..=right {
-      result <<= 6;
-      result |= self.get_byte(index as usize) as u16;
-    }
+impl Data<u16> for Register {
+  fn read(&self) -> u16 {
+    self.pack_signed() as u16
+  }
 
-    result
+  fn read_data(&self) -> u16 {
+    self.pack() as u16
   }
 
   fn write(&mut self, number: u16, sign: bool) {
-    self.data = (number & Self::DATA_MASK) | if sign { Self::SIGN_MASK } else { 0 };
+    self.unpack(number as u64);
+    self.write_sign(sign);
   }
 
   fn write_data(&mut self, number: u16) {
-    self.data = (number & Self::DATA_MASK) | (self.data & Self::SIGN_MASK);
+    self.unpack(number as u64);
   }
 
+  /// Returns the byte by its index, byte 0 is the sign with 1 for plus
   fn get_byte(&self, index: usize) -> u8 {
-    assert!(index <= Self::BYTES);
-
-    ((self.data >> ((Self::BYTES - index) * 6)) & 0b111111) as u8
+    self.byte(index)
   }
 }
 
-impl Signed for Register {
-  fn read_sign(&self) -> bool {
-    (self.data & Self::SIGN_MASK) != 0
+impl FieldRead<u16> for Register {
+  fn read_with_modifier(&self, field: FieldSpec) -> Result<u16, FieldError> {
+    Ok(self.pack_field(Self::check_field(field)?) as u16)
   }
 
-  fn write_sign(&mut self, sign: bool) {
-    if sign {
-      self.data |= Self::SIGN_MASK;
-    } else {
-      self.data &= !Self::SIGN_MASK;
-    }
+  fn read_field(&self, field: FieldSpec) -> Result<(u16, bool), FieldError> {
+    let (magnitude, sign) = self.pack_signed_field(Self::check_field(field)?);
+
+    Ok((magnitude as u16, sign))
+  }
+}
+
+impl FieldWrite<u16> for Register {
+  fn write_with_modifier(&mut self, number: u16, field: FieldSpec) -> Result<(), FieldError> {
+    self.unpack_field(number as u64, Self::check_field(field)?);
+
+    Ok(())
   }
 }
 
+/// Shows the sign and the two bytes, as the words are shown, or with the
+/// alternate flag the sign and the bytes packed into one number
 impl fmt::Display for Register {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    if self.read_sign() {
-      write!(f, "+")?
-    } else {
-      write!(f, "-")?
+    if !f.alternate() {
+      return self.write_bytes(f);
     }
 
-    write!(f, "{}", self.read_data())
+    write!(
+      f,
+      "{}{}",
+      if self.read_sign() { "+" } else { "-" },
+      self.read_data()
+    )
   }
 }
 
@@ -108,7 +145,7 @@ mod tests {
   use rstest_reuse::{self, *};
 
   use super::*;
-  use crate::tests::split_modifier_cases;
+  use crate::computer::Compare;
 
   #[template]
   #[rstest]
@@ -164,33 +201,234 @@ mod tests {
 
   #[template]
   #[rstest]
-  #[case(0b0000_0000_0000_0000, 0b0000_0000_0000_0000, false, 0)]
-  #[case(0b0001_0000_0000_0000, 0b0000_0000_0000_0001, true, 0)]
-  #[case(0b0000_1111_1100_0000, 0b0000_0000_0011_1111, false, 1)]
-  #[case(0b0000_1111_1111_1111, 0b0000_0000_0011_1111, false, 1)]
-  #[case(0b0000_1111_1111_1111, 0b0000_0000_0111_1111, true, 1)]
-  #[case(0b0001_1111_1111_1111, 0b0000_0000_0111_1111, true, 1)]
-  #[case(0b0000_1111_1111_1111, 0b0000_1111_1111_1111, false, 2)]
-  #[case(0b0001_1111_1111_1111, 0b0000_1111_1111_1111, false, 2)]
-  #[case(0b0000_1111_1111_1111, 0b0001_1111_1111_1111, true, 2)]
-  #[case(0b0001_1111_1111_1111, 0b0001_1111_1111_1111, true, 2)]
-  #[case(0b0000_1111_1111_1111, 0b0000_1111_1111_1111, false, 12)]
-  #[case(0b0001_1111_1111_1111, 0b0000_1111_1111_1111, true, 12)]
-  #[case(0b0000_0000_0011_1111, 0b0000_0000_0011_1111, false, 22)]
-  #[case(0b0001_0000_0011_1111, 0b0000_0000_0011_1111, false, 22)]
-  #[case(0b0001_1111_0011_0000, 0b0000_0000_0011_0000, true, 22)]
-  #[case(0b0000_1111_0011_0001, 0b0000_0000_0011_0001, false, 22)]
+  #[case(
+    0b0000_0000_0000_0000,
+    0b0000_0000_0000_0000,
+    false,
+    FieldSpec::new(0, 0)
+  )]
+  #[case(
+    0b0001_0000_0000_0000,
+    0b0000_0000_0000_0001,
+    true,
+    FieldSpec::new(0, 0)
+  )]
+  #[case(
+    0b0000_1111_1100_0000,
+    0b0000_0000_0011_1111,
+    false,
+    FieldSpec::new(0, 1)
+  )]
+  #[case(
+    0b0000_1111_1111_1111,
+    0b0000_0000_0011_1111,
+    false,
+    FieldSpec::new(0, 1)
+  )]
+  #[case(
+    0b0000_1111_1111_1111,
+    0b0000_0000_0111_1111,
+    true,
+    FieldSpec::new(0, 1)
+  )]
+  #[case(
+    0b0001_1111_1111_1111,
+    0b0000_0000_0111_1111,
+    true,
+    FieldSpec::new(0, 1)
+  )]
+  #[case(
+    0b0000_1111_1111_1111,
+    0b0000_1111_1111_1111,
+    false,
+    FieldSpec::new(0, 2)
+  )]
+  #[case(
+    0b0001_1111_1111_1111,
+    0b0000_1111_1111_1111,
+    false,
+    FieldSpec::new(0, 2)
+  )]
+  #[case(
+    0b0000_1111_1111_1111,
+    0b0001_1111_1111_1111,
+    true,
+    FieldSpec::new(0, 2)
+  )]
+  #[case(
+    0b0001_1111_1111_1111,
+    0b0001_1111_1111_1111,
+    true,
+    FieldSpec::new(0, 2)
+  )]
+  #[case(
+    0b0000_1111_1111_1111,
+    0b0000_1111_1111_1111,
+    false,
+    FieldSpec::new(1, 2)
+  )]
+  #[case(
+    0b0001_1111_1111_1111,
+    0b0000_1111_1111_1111,
+    true,
+    FieldSpec::new(1, 2)
+  )]
+  #[case(
+    0b0000_0000_0011_1111,
+    0b0000_0000_0011_1111,
+    false,
+    FieldSpec::new(2, 2)
+  )]
+  #[case(
+    0b0001_0000_0011_1111,
+    0b0000_0000_0011_1111,
+    false,
+    FieldSpec::new(2, 2)
+  )]
+  #[case(
+    0b0001_1111_0011_0000,
+    0b0000_0000_0011_0000,
+    true,
+    FieldSpec::new(2, 2)
+  )]
+  #[case(
+    0b0000_1111_0011_0001,
+    0b0000_0000_0011_0001,
+    false,
+    FieldSpec::new(2, 2)
+  )]
   fn read_with_modifier_cases(
     #[case] number: u16,
     #[case] expected: u16,
     #[case] sign: bool,
-    #[case] modifier: u16,
+    #[case] field: FieldSpec,
   ) {
   }
 
+  #[rustfmt::skip]
+  #[template]
+  #[rstest]
+  #[case(0b0001_0000_0000_0000, 0b0000_0000_0000_0001, FieldSpec::new(0, 0), 0b0001_0000_0000_0000)]
+  #[case(0b0000_0000_0000_0000, 0b0000_0000_0000_0001, FieldSpec::new(0, 0), 0b0001_0000_0000_0000)]
+  #[case(0b0001_1111_1111_1111, 0b0000_0000_0000_0000, FieldSpec::new(0, 0), 0b0000_1111_1111_1111)]
+  #[case(0b0001_0000_0000_0000, 0b0000_0000_0011_1111, FieldSpec::new(0, 1), 0b0000_1111_1100_0000)]
+  #[case(0b0000_1111_1111_1111, 0b0000_0000_0000_0000, FieldSpec::new(0, 1), 0b0000_0000_0011_1111)]
+  #[case(0b0001_0000_0000_0000, 0b0000_0000_0011_1111, FieldSpec::new(0, 2), 0b0000_0000_0011_1111)]
+  #[case(0b0001_1111_1111_1111, 0b0000_0000_0000_0001, FieldSpec::new(0, 2), 0b0000_0000_0000_0001)]
+  #[case(0b0000_0000_0000_0000, 0b0001_0000_0100_0010, FieldSpec::new(0, 2), 0b0001_0000_0100_0010)]
+  #[case(0b0001_1111_1111_1111, 0b0000_0000_0000_0000, FieldSpec::new(1, 2), 0b0001_0000_0000_0000)]
+  #[case(0b0000_0000_0000_0000, 0b0000_0000_1100_0100, FieldSpec::new(1, 2), 0b0000_0000_1100_0100)]
+  #[case(0b0001_0000_0000_0000, 0b0000_0000_0000_0011, FieldSpec::new(2, 2), 0b0001_0000_0000_0011)]
+  fn write_with_modifier_cases(
+    #[case] number: u16,
+    #[case] value: u16,
+    #[case] field: FieldSpec,
+    #[case] expected: u16,
+  ) {
+  }
+
+  #[rstest]
+  #[case(
+    Register::new(5, Some(true)),
+    Register::new(5, Some(true)),
+    Compare::Equal
+  )]
+  #[case(
+    Register::new(0, Some(true)),
+    Register::new(0, Some(false)),
+    Compare::Equal
+  )]
+  #[case(
+    Register::new(64, Some(true)),
+    Register::new(63, Some(true)),
+    Compare::Greater
+  )]
+  #[case(
+    Register::new(64, Some(false)),
+    Register::new(63, Some(false)),
+    Compare::Less
+  )]
+  #[case(
+    Register::new(1, Some(false)),
+    Register::new(0, Some(false)),
+    Compare::Less
+  )]
+  #[case(
+    Register::new(0, Some(true)),
+    Register::new(1, Some(false)),
+    Compare::Greater
+  )]
+  fn test_mix_cmp(#[case] register: Register, #[case] other: Register, #[case] expected: Compare) {
+    assert_eq!(register.mix_cmp(&other), expected);
+  }
+
+  #[rstest]
+  #[case(Register::from_bytes([1, 2], true), "1042", "1000001000010", "+01 02")]
+  #[case(Register::from_bytes([62, 31], false), "f9f", "111110011111", "-76 37")]
+  fn test_hex_binary_octal(
+    #[case] register: Register,
+    #[case] hex: &str,
+    #[case] binary: &str,
+    #[case] octal: &str,
+  ) {
+    assert_eq!(format!("{:x}", register), hex);
+    assert_eq!(format!("{:b}", register), binary);
+    assert_eq!(format!("{:o}", register), octal);
+  }
+
+  #[rstest]
+  #[case(4095, Some(true), Ok(Register::from_bytes([63, 63], true)))]
+  #[case(4096, Some(true), Err(OutOfRange(4096)))]
+  #[case(0b0001_0000_0000_0001, Some(false), Err(OutOfRange(4097)))]
+  fn test_try_new(
+    #[case] number: u16,
+    #[case] sign: Option<bool>,
+    #[case] expected: Result<Register, OutOfRange>,
+  ) {
+    assert_eq!(Register::try_new(number, sign), expected);
+  }
+
+  #[rstest]
+  #[case(100, Ok(Register::from_bytes([1, 36], true)))]
+  #[case(-4095, Ok(Register::from_bytes([63, 63], false)))]
+  #[case(4096, Err(OutOfRange(4096)))]
+  #[case(-4096, Err(OutOfRange(-4096)))]
+  fn test_try_from_i64(#[case] value: i64, #[case] expected: Result<Register, OutOfRange>) {
+    assert_eq!(Register::try_from(value), expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(ByteSize::BINARY, Register::new(5, Some(true)), 10, Register::new(15, Some(true)), false)]
+  #[case(ByteSize::BINARY, Register::new(5, Some(true)), -10, Register::new(5, Some(false)), false)]
+  #[case(ByteSize::BINARY, Register::new(5, Some(false)), 5, Register::new(0, Some(false)), false)]
+  #[case(ByteSize::BINARY, Register::new(4095, Some(true)), 1, Register::new(0, Some(true)), true)]
+  #[case(ByteSize::BINARY, Register::new(4000, Some(false)), -100, Register::new(4, Some(false)), true)]
+  #[case(ByteSize::DECIMAL, Register::from_bytes([99, 99], true), 1, Register::from_bytes([0, 0], true), true)]
+  #[case(ByteSize::DECIMAL, Register::from_bytes([39, 99], true), 1, Register::from_bytes([40, 0], true), false)]
+  fn test_checked_inc(
+    #[case] byte_size: ByteSize,
+    #[case] register: Register,
+    #[case] value: i64,
+    #[case] result: Register,
+    #[case] overflow: bool,
+  ) {
+    assert_eq!(register.checked_inc(value, byte_size), Overflowing { result, overflow });
+    assert_eq!(register.checked_dec(-value, byte_size), Overflowing { result, overflow });
+  }
+
+  #[rstest]
+  #[case(Register::from_bytes([1, 2], true), "+01 02", "+66")]
+  #[case(Register::from_bytes([62, 31], false), "-62 31", "-3999")]
+  #[case(Register::default(), "+00 00", "+0")]
+  fn test_display(#[case] register: Register, #[case] expected: &str, #[case] alternate: &str) {
+    assert_eq!(register.to_string(), expected);
+    assert_eq!(format!("{:#}", register), alternate);
+  }
+
   #[test]
   fn test_default() {
-    assert!(!Register::default().read_sign());
+    assert!(Register::default().read_sign());
     assert_eq!(Register::default().read_data(), 0);
   }
 
@@ -205,11 +443,68 @@ mod tests {
   }
 
   #[apply(read_with_modifier_cases)]
-  fn test_read_with_modifier(number: u16, expected: u16, sign: bool, modifier: u16) {
+  fn test_read_with_modifier(number: u16, expected: u16, sign: bool, field: FieldSpec) {
+    assert_eq!(
+      Register::new(number, Some(sign)).read_with_modifier(field),
+      Ok(expected)
+    );
+  }
+
+  #[rstest]
+  #[case(0b000001_000010, false, FieldSpec::new(0, 0), 0, false)]
+  #[case(0b000001_000010, true, FieldSpec::new(0, 0), 0, true)]
+  #[case(0b000001_000010, false, FieldSpec::new(0, 1), 0b000001, false)]
+  #[case(0b000001_000010, false, FieldSpec::new(0, 2), 0b000001_000010, false)]
+  #[case(0b000001_000010, true, FieldSpec::new(0, 2), 0b000001_000010, true)]
+  #[case(0b000001_000010, false, FieldSpec::new(2, 2), 0b000010, true)]
+  fn test_read_field(
+    #[case] number: u16,
+    #[case] sign: bool,
+    #[case] field: FieldSpec,
+    #[case] expected: u16,
+    #[case] expected_sign: bool,
+  ) {
+    assert_eq!(
+      Register::new(number, Some(sign)).read_field(field),
+      Ok((expected, expected_sign))
+    );
+  }
+
+  #[apply(write_with_modifier_cases)]
+  fn test_write_with_modifier(number: u16, value: u16, field: FieldSpec, expected: u16) {
+    let mut register = Register::new(number, Some(number & Register::SIGN_MASK != 0));
+    register.write_with_modifier(value, field).unwrap();
+
+    assert_eq!(register.read(), expected);
+  }
+
+  #[apply(read_with_modifier_cases)]
+  fn test_write_with_modifier_round_trip(number: u16, expected: u16, sign: bool, field: FieldSpec) {
+    let mut register = Register::new(!number, Some(!sign));
+    register.write_with_modifier(expected, field).unwrap();
+
+    assert_eq!(register.read_with_modifier(field), Ok(expected));
+  }
+
+  #[rstest]
+  #[case(FieldSpec::new(0, 3))]
+  #[case(FieldSpec::new(3, 5))]
+  fn test_field_out_of_range(#[case] field: FieldSpec) {
+    let mut register = Register::new(0b000001_000010, Some(true));
+
+    assert_eq!(
+      register.read_with_modifier(field),
+      Err(FieldError::OutOfRange(field))
+    );
     assert_eq!(
-      Register::new(number, Some(sign)).read_with_modifier(modifier),
-      expected
+      register.read_field(field),
+      Err(FieldError::OutOfRange(field))
     );
+    assert_eq!(
+      register.write_with_modifier(0, field),
+      Err(FieldError::OutOfRange(field))
+    );
+    assert_eq!(register, Register::new(0b000001_000010, Some(true)));
   }
 
   #[apply(data_with_sign_cases)]
@@ -222,7 +517,7 @@ mod tests {
 
   #[apply(data_without_sign_cases)]
   fn test_write_data(number: u16, expected: u16) {
-    let mut register = Register::default();
+    let mut register = Register::new(0, Some(false));
     register.write_data(number);
 
     assert_eq!(register.read(), expected);
@@ -247,8 +542,22 @@ mod tests {
     assert_eq!(Register::new(number, Some(sign)).get_byte(index), expected);
   }
 
-  #[apply(split_modifier_cases)]
-  fn test_split_modifier(modifier: u32, expected: (u32, u32)) {
-    assert_eq!(Register::split_modifier(modifier), expected);
+  #[apply(data_with_sign_cases)]
+  fn test_word_from_register(number: u16, expected: u16, sign: bool) {
+    let word = Word::from(Register::new(number, Some(sign)));
+
+    assert_eq!(word.read_sign(), sign);
+    assert_eq!(word.read_data(), (expected & Register::DATA_MASK) as u32);
+  }
+
+  #[rstest]
+  #[case(0b000000_000000_000000_000001_000010, true, 0b0000_0000_0100_0010)]
+  #[case(0b000000_000000_000000_111111_111111, false, 0b0000_1111_1111_1111)]
+  #[case(0b111111_111111_111111_000000_000011, true, 0b0000_0000_0000_0011)]
+  fn test_register_from_word(#[case] number: u32, #[case] sign: bool, #[case] expected: u16) {
+    let register = Register::from(Word::new(number, Some(sign)));
+
+    assert_eq!(register.read_sign(), sign);
+    assert_eq!(register.read_data(), expected);
   }
 }
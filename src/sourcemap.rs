@@ -0,0 +1,69 @@
+use std::{
+  collections::BTreeMap,
+  fmt,
+  path::{Path, PathBuf},
+};
+
+/// Line of a MIXAL source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLine {
+  /// File the source is read from, none if it is given as text
+  pub file: Option<PathBuf>,
+  /// Number of the line, from 1
+  pub line: usize,
+}
+
+/// Lines of the source that the words of an assembled program come from, so
+/// that a fault of the machine points at the line of its instruction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+  /// Lines of the source, by location
+  pub lines: BTreeMap<usize, SourceLine>,
+}
+
+impl SourceMap {
+  /// Finds the line the word at the location comes from
+  pub fn get(&self, location: usize) -> Option<&SourceLine> {
+    self.lines.get(&location)
+  }
+
+  /// Records that the word at the location comes from the line of the file
+  pub(crate) fn insert(&mut self, location: usize, file: Option<&Path>, line: usize) {
+    self.lines.insert(
+      location,
+      SourceLine {
+        file: file.map(Path::to_path_buf),
+        line,
+      },
+    );
+  }
+}
+
+/// Shows the line as `file:line`, or as `line N` for a source given as text
+impl fmt::Display for SourceLine {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.file {
+      Some(file) => write!(f, "{}:{}", file.display(), self.line),
+      None => write!(f, "line {}", self.line),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(Some(Path::new("primes.mixal")), "primes.mixal:12")]
+  #[case(None, "line 12")]
+  fn test_display(#[case] file: Option<&Path>, #[case] expected: &str) {
+    let mut map = SourceMap::default();
+
+    map.insert(3000, file, 12);
+
+    assert_eq!(map.get(3000).unwrap().to_string(), expected);
+    assert_eq!(map.get(3001), None);
+  }
+}
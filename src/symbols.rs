@@ -0,0 +1,160 @@
+use std::{
+  collections::BTreeMap,
+  fs::File,
+  io::{self, BufRead, BufReader, BufWriter, Write},
+  path::Path,
+};
+
+/// Symbols of an assembled source, so that a listing shows names in place of
+/// the addresses
+///
+/// The local symbols `dH` are left out, they may stand for many locations.
+///
+/// A table is kept as text, a symbol to a line with its name and its value
+/// apart by a blank. A literal constant goes by its text with the equal
+/// signs, `=1=`, and its location.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+  /// Values of the symbols, by name
+  pub symbols: BTreeMap<String, i64>,
+  /// Locations of the literal constants, by the text between their equal
+  /// signs
+  pub literals: BTreeMap<String, usize>,
+}
+
+impl SymbolTable {
+  /// Reads the table from the file
+  pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::from_reader(BufReader::new(File::open(path)?))
+  }
+
+  /// Writes the table to the file
+  pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    self.to_writer(&mut writer)?;
+    writer.flush()
+  }
+
+  /// Reads the table from the text, a line that is not a name and a value
+  /// is refused
+  pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+    let mut table = Self::default();
+
+    for (index, line) in reader.lines().enumerate() {
+      let line = line?;
+      let invalid = || {
+        io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!("Line {} is not a symbol and its value", index + 1),
+        )
+      };
+      let (name, value) = line.trim().split_once(' ').ok_or_else(invalid)?;
+      let literal = name
+        .strip_prefix('=')
+        .and_then(|name| name.strip_suffix('='));
+
+      match literal {
+        Some(literal) => {
+          let location = value.trim().parse().map_err(|_| invalid())?;
+
+          table.literals.insert(literal.to_string(), location);
+        }
+        None => {
+          let value = value.trim().parse().map_err(|_| invalid())?;
+
+          table.symbols.insert(name.to_string(), value);
+        }
+      }
+    }
+
+    Ok(table)
+  }
+
+  /// Writes the table as text, the symbols by name and then the literal
+  /// constants
+  pub fn to_writer(&self, mut writer: impl Write) -> io::Result<()> {
+    for (name, value) in &self.symbols {
+      writeln!(writer, "{} {}", name, value)?;
+    }
+
+    for (literal, location) in &self.literals {
+      writeln!(writer, "={}= {}", literal, location)?;
+    }
+
+    Ok(())
+  }
+
+  /// Finds the value of the symbol
+  pub fn get(&self, name: &str) -> Option<i64> {
+    self.symbols.get(name).copied()
+  }
+
+  /// Finds a name for the location: the first symbol by name with the
+  /// location as its value, or else the literal constant kept there
+  pub fn name_of(&self, location: usize) -> Option<String> {
+    let symbol = self
+      .symbols
+      .iter()
+      .find(|&(_, &value)| value == location as i64)
+      .map(|(name, _)| name.clone());
+
+    symbol.or_else(|| {
+      self
+        .literals
+        .iter()
+        .find(|&(_, &at)| at == location)
+        .map(|(literal, _)| format!("={}=", literal))
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  fn table() -> SymbolTable {
+    let mut table = SymbolTable::default();
+
+    table.symbols.insert("START".to_string(), 3000);
+    table.symbols.insert("TERM".to_string(), 19);
+    table.symbols.insert("LOW".to_string(), -1);
+    table.literals.insert("1".to_string(), 3010);
+
+    table
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    table().save(file.path()).unwrap();
+
+    assert_eq!(
+      std::fs::read_to_string(file.path()).unwrap(),
+      "LOW -1\nSTART 3000\nTERM 19\n=1= 3010\n"
+    );
+    assert_eq!(SymbolTable::open(file.path()).unwrap(), table());
+  }
+
+  #[rstest]
+  #[case(3000, Some("START"))]
+  #[case(19, Some("TERM"))]
+  #[case(3010, Some("=1="))]
+  #[case(3001, None)]
+  fn test_name_of(#[case] location: usize, #[case] expected: Option<&str>) {
+    assert_eq!(table().name_of(location).as_deref(), expected);
+  }
+
+  #[rstest]
+  #[case("START")]
+  #[case("START 3O00")]
+  #[case("=1= -5")]
+  fn test_from_reader_invalid(#[case] text: &str) {
+    let error = SymbolTable::from_reader(text.as_bytes()).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  }
+}
@@ -0,0 +1,233 @@
+use std::{io, path::Path};
+
+use crate::{
+  device::{BlockFile, Device, FileFormat},
+  error::DeviceError,
+  word::Word,
+};
+
+/// Magnetic tape unit kept in a file of the host, so that the data is there
+/// for the next run
+pub struct FileTape {
+  file: BlockFile,
+  /// Number of the block under the head
+  position: u64,
+}
+
+impl FileTape {
+  /// Number of words in a block of a tape
+  pub const BLOCK_SIZE: usize = 100;
+
+  /// Opens the tape in the file at its first block, the file is created if
+  /// it does not exist and its data is kept if it does
+  pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::open_with(path, FileFormat::Mixi)
+  }
+
+  /// Opens the tape in the file as the `mixvm` of GNU MDK keeps it, so that
+  /// the data can be moved between the two
+  pub fn open_mdk(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::open_with(path, FileFormat::Mdk)
+  }
+
+  fn open_with(path: impl AsRef<Path>, format: FileFormat) -> io::Result<Self> {
+    Ok(Self {
+      file: BlockFile::open(path, Self::BLOCK_SIZE, format)?,
+      position: 0,
+    })
+  }
+
+  /// Number of the block under the head
+  pub fn position(&self) -> u64 {
+    self.position
+  }
+}
+
+impl Device for FileTape {
+  fn block_size(&self) -> usize {
+    Self::BLOCK_SIZE
+  }
+
+  /// Reads the block under the head and moves on to the next one, the words
+  /// past the end of the file are read as zeros
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    let block = self.file.read(self.position)?;
+
+    self.position += 1;
+
+    Ok(block)
+  }
+
+  /// Writes the block under the head and moves on to the next one, a block
+  /// past the end of the file leaves zeros in between
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    self.file.write(self.position, block)?;
+    self.position += 1;
+
+    Ok(())
+  }
+
+  /// Rewinds the tape if M is zero, otherwise skips M blocks forward or
+  /// backward, stopping at the first block
+  fn control(&mut self, operation: i64, _x: u64) -> Result<(), DeviceError> {
+    self.position = match operation {
+      0 => 0,
+      _ => self.position.saturating_add_signed(operation),
+    };
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+  use tempfile::NamedTempFile;
+
+  use super::*;
+  use crate::{
+    computer::{Computer, HaltReason},
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  fn block(number: u32, sign: bool) -> Vec<Word> {
+    vec![Word::new(number, Some(sign)); FileTape::BLOCK_SIZE]
+  }
+
+  #[test]
+  fn test_write_read() {
+    let file = NamedTempFile::new().unwrap();
+    let mut tape = FileTape::open(file.path()).unwrap();
+
+    tape.write(&block(1, true)).unwrap();
+    tape.write(&block(2, false)).unwrap();
+    tape.control(0, 0).unwrap();
+
+    assert_eq!(tape.read(), Ok(block(1, true)));
+    assert_eq!(tape.read(), Ok(block(2, false)));
+    assert_eq!(tape.read(), Ok(block(0, true)));
+    assert_eq!(tape.position(), 3);
+  }
+
+  #[test]
+  fn test_persists_between_runs() {
+    let file = NamedTempFile::new().unwrap();
+
+    FileTape::open(file.path())
+      .unwrap()
+      .write(&block(7, false))
+      .unwrap();
+
+    let mut tape = FileTape::open(file.path()).unwrap();
+
+    assert_eq!(tape.position(), 0);
+    assert_eq!(tape.read(), Ok(block(7, false)));
+  }
+
+  #[test]
+  fn test_write_past_end() {
+    let file = NamedTempFile::new().unwrap();
+    let mut tape = FileTape::open(file.path()).unwrap();
+
+    tape.control(2, 0).unwrap();
+    tape.write(&block(3, true)).unwrap();
+    tape.control(-2, 0).unwrap();
+
+    assert_eq!(tape.read(), Ok(block(0, true)));
+    assert_eq!(tape.read(), Ok(block(3, true)));
+    assert_eq!(file.as_file().metadata().unwrap().len(), 3 * 600);
+  }
+
+  #[test]
+  fn test_write_over_block() {
+    let file = NamedTempFile::new().unwrap();
+    let mut tape = FileTape::open(file.path()).unwrap();
+
+    for number in 1..=3 {
+      tape.write(&block(number, true)).unwrap();
+    }
+
+    tape.control(-2, 0).unwrap();
+    tape.write(&block(9, false)).unwrap();
+    tape.control(0, 0).unwrap();
+
+    assert_eq!(tape.read(), Ok(block(1, true)));
+    assert_eq!(tape.read(), Ok(block(9, false)));
+    assert_eq!(tape.read(), Ok(block(3, true)));
+  }
+
+  #[test]
+  fn test_mdk_layout() {
+    let file = NamedTempFile::new().unwrap();
+    let mut tape = FileTape::open_mdk(file.path()).unwrap();
+    let mut data = block(0, true);
+
+    data[0] = Word::new(1, Some(true));
+    data[1] = Word::from_bytes([1, 2, 3, 4, 5], false);
+    tape.write(&data).unwrap();
+
+    let bytes = std::fs::read(file.path()).unwrap();
+    let minus = 1 << 30 | 1 << 24 | 2 << 18 | 3 << 12 | 4 << 6 | 5_u32;
+
+    assert_eq!(bytes.len(), 400);
+    assert_eq!(bytes[..4], [1, 0, 0, 0]);
+    assert_eq!(bytes[4..8], minus.to_le_bytes());
+
+    tape.control(0, 0).unwrap();
+
+    assert_eq!(tape.read(), Ok(data));
+  }
+
+  #[test]
+  fn test_mdk_byte_too_large() {
+    let file = NamedTempFile::new().unwrap();
+    let mut tape = FileTape::open_mdk(file.path()).unwrap();
+    let mut data = block(0, true);
+
+    data[99] = Word::from_bytes([99, 0, 0, 0, 0], true);
+
+    assert_eq!(
+      tape.write(&data),
+      Err(DeviceError::Io(io::ErrorKind::InvalidData))
+    );
+    assert_eq!(tape.position(), 0);
+  }
+
+  #[rstest]
+  #[case(5, 0, 0)]
+  #[case(0, 3, 3)]
+  #[case(2, 2, 4)]
+  #[case(5, -1, 4)]
+  #[case(5, -5, 0)]
+  #[case(5, -10, 0)]
+  fn test_control(#[case] position: u64, #[case] operation: i64, #[case] expected: u64) {
+    let file = NamedTempFile::new().unwrap();
+    let mut tape = FileTape::open(file.path()).unwrap();
+
+    tape.position = position;
+    tape.control(operation, 0).unwrap();
+
+    assert_eq!(tape.position(), expected);
+  }
+
+  #[test]
+  fn test_on_machine() {
+    let file = NamedTempFile::new().unwrap();
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(3, Box::new(FileTape::open(file.path()).unwrap()))
+      .unwrap();
+    computer.memory[1000..1100].copy_from_slice(&block(5, false));
+
+    program.add(Instruction::new(true, 1000, 0, 3, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 3, Operation::Ioc));
+    program.add(Instruction::new(true, 2000, 0, 3, Operation::In));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.memory[2000..2100], block(5, false));
+  }
+}
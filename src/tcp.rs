@@ -0,0 +1,134 @@
+use std::{
+  io::{self, BufRead, BufReader, Write},
+  net::{TcpStream, ToSocketAddrs},
+};
+
+use crate::{charcode, device::Device, error::DeviceError, word::Word};
+
+/// Unit exchanging its blocks over a TCP connection, one line of text to a
+/// block, so that a program serves or talks to a peer across the network
+///
+/// A block goes as the characters of its words, five to a word, without its
+/// trailing blanks and with the bytes that are not a character as `?`. A
+/// line that comes in is filled with blanks, one with more characters than
+/// the block holds or with a character outside the MIX character code
+/// faults, and so does IN once the peer closes the connection.
+pub struct TcpDevice {
+  reader: BufReader<TcpStream>,
+  writer: TcpStream,
+  block_size: usize,
+}
+
+impl TcpDevice {
+  /// Connects to the address, the blocks hold the number of words
+  pub fn connect(address: impl ToSocketAddrs, block_size: usize) -> io::Result<Self> {
+    Self::from_stream(TcpStream::connect(address)?, block_size)
+  }
+
+  /// Exchanges the blocks over the connection, such as one accepted from a
+  /// listener
+  pub fn from_stream(stream: TcpStream, block_size: usize) -> io::Result<Self> {
+    Ok(Self {
+      reader: BufReader::new(stream.try_clone()?),
+      writer: stream,
+      block_size,
+    })
+  }
+
+  /// Number of characters in a block
+  fn columns(&self) -> usize {
+    self.block_size * Word::BYTES
+  }
+}
+
+impl Device for TcpDevice {
+  fn block_size(&self) -> usize {
+    self.block_size
+  }
+
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    let mut line = String::new();
+
+    if self.reader.read_line(&mut line)? == 0 {
+      return Err(DeviceError::Io(io::ErrorKind::UnexpectedEof));
+    }
+
+    let line = line.trim_end_matches(['\n', '\r']);
+
+    charcode::line_to_words(line, self.columns()).ok_or(DeviceError::Io(io::ErrorKind::InvalidData))
+  }
+
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    let line: String = block
+      .iter()
+      .map(|word| word.display_as_chars().to_string())
+      .collect();
+
+    writeln!(self.writer, "{}", line.trim_end())?;
+    self.writer.flush()?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{net::TcpListener, thread};
+
+  use super::*;
+  use crate::{
+    computer::Computer,
+    error::MixError,
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  #[test]
+  fn test_echo_service() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // The peer sends a request and keeps the reply
+    let peer = thread::spawn(move || {
+      let mut stream = TcpStream::connect(address).unwrap();
+      let mut reply = String::new();
+
+      writeln!(stream, "PING 42").unwrap();
+      BufReader::new(stream).read_line(&mut reply).unwrap();
+      reply
+    });
+
+    let (stream, _) = listener.accept().unwrap();
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(5, Box::new(TcpDevice::from_stream(stream, 2).unwrap()))
+      .unwrap();
+
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::In));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Out));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::In));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(
+      computer.execute(&program),
+      Err(MixError::DeviceFailure(5, io::ErrorKind::UnexpectedEof))
+    );
+    assert_eq!(peer.join().unwrap(), "PING 42\n");
+  }
+
+  #[test]
+  fn test_line_too_long() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut device = TcpDevice::connect(listener.local_addr().unwrap(), 1).unwrap();
+    let (mut stream, _) = listener.accept().unwrap();
+
+    writeln!(stream, "SIX CH").unwrap();
+
+    assert_eq!(
+      device.read(),
+      Err(DeviceError::Io(io::ErrorKind::InvalidData))
+    );
+  }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+
+use crate::device::UNITS;
+
+/// Time of the machine split between computing and waiting for the units,
+/// to weigh buffering strategies against one another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+  /// Units of time spent on the work of the program
+  pub compute: u64,
+  /// Units of time spent waiting for each unit, by unit: IN, OUT and IOC
+  /// waiting for a busy unit and JBUS finding it busy
+  pub wait: [u64; UNITS],
+}
+
+impl Timing {
+  /// Units of time spent waiting for all the units
+  pub fn waited(&self) -> u64 {
+    self.wait.iter().sum()
+  }
+
+  /// Units of time spent in all, which is the time of the machine
+  pub fn total(&self) -> u64 {
+    self.compute + self.waited()
+  }
+}
+
+impl Default for Timing {
+  fn default() -> Self {
+    Self {
+      compute: 0,
+      wait: [0; UNITS],
+    }
+  }
+}
+
+/// Shows the share of the time computing and waiting for each unit that
+/// was waited for, one to a line
+impl fmt::Display for Timing {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let total = self.total().max(1) as f64;
+    let share = |time: u64| 100.0 * time as f64 / total;
+
+    write!(
+      f,
+      "Compute: {}u ({:.1}%)",
+      self.compute,
+      share(self.compute)
+    )?;
+
+    for (unit, &time) in self.wait.iter().enumerate() {
+      if time > 0 {
+        write!(f, "\nUnit {}: {}u ({:.1}%)", unit, time, share(time))?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    computer::{Computer, HaltReason},
+    device::MemoryUnit,
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  #[test]
+  fn test_display() {
+    let mut timing = Timing {
+      compute: 30,
+      ..Timing::default()
+    };
+
+    timing.wait[18] = 10;
+
+    assert_eq!(
+      timing.to_string(),
+      "Compute: 30u (75.0%)\nUnit 18: 10u (25.0%)"
+    );
+  }
+
+  #[test]
+  fn test_jbus_spin_and_wait() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    for unit in [16, 18] {
+      let device: &mut MemoryUnit = computer.device_mut(unit).unwrap().downcast_mut().unwrap();
+
+      device.latency = 20;
+    }
+
+    // Spins on JBUS for the card and then waits for the printer at OUT
+    program.add(Instruction::new(true, 1000, 0, 16, Operation::In));
+    program.add(Instruction::new(true, 1, 0, 16, Operation::Jbus));
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.timing.wait[16], 19);
+    assert_eq!(computer.timing.wait[18], 19);
+    assert_eq!(computer.timing.compute, 1 + 1 + 1 + 1 + 10);
+    assert_eq!(computer.timing.total(), computer.time);
+  }
+}
@@ -0,0 +1,137 @@
+use std::{fmt, ops::Range};
+
+use crate::word::Word;
+
+/// Number of characters of the data an entry shows
+const SUMMARY: usize = 20;
+
+/// Operation of a unit as the transcript records it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+  /// IN stored the block at the addresses
+  In(Range<usize>),
+  /// OUT wrote the block from the addresses
+  Out(Range<usize>),
+  /// IOC with the operation M
+  Control(i64),
+}
+
+/// Operation of a unit recorded once the unit is done with it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+  /// Time at which the program issued the operation
+  pub started: u64,
+  /// Time at which the unit was done
+  pub done: u64,
+  pub unit: u32,
+  pub action: Action,
+  /// Block that was read or written, empty for IOC
+  pub data: Vec<Word>,
+}
+
+/// Shows the entry on one line with the first characters of the data, the
+/// bytes that are not a character are shown as `?`
+impl fmt::Display for Entry {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:>8} {:>8} U{:02} ", self.started, self.done, self.unit)?;
+
+    let range = match &self.action {
+      Action::In(range) => {
+        write!(f, "IN  ")?;
+        range
+      }
+      Action::Out(range) => {
+        write!(f, "OUT ")?;
+        range
+      }
+      Action::Control(operation) => return write!(f, "IOC {}", operation),
+    };
+
+    let text: String = self
+      .data
+      .iter()
+      .map(|word| word.display_as_chars().to_string())
+      .collect();
+    let text = text.trim_end();
+
+    write!(f, "{:04}-{:04} \"", range.start, range.end - 1)?;
+
+    match text.char_indices().nth(SUMMARY) {
+      Some((end, _)) => write!(f, "{}...\"", &text[..end]),
+      None => write!(f, "{}\"", text),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    card::CardReader,
+    charcode,
+    computer::{Computer, HaltReason},
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  #[rstest]
+  #[case(Action::Control(-1), "", "       5       15 U03 IOC -1")]
+  #[case(Action::In(1000..1004), "HELLO", "       5       15 U03 IN  1000-1003 \"HELLO\"")]
+  #[case(
+    Action::Out(0..24),
+    "THE QUICK BROWN FOX JUMPS",
+    "       5       15 U03 OUT 0000-0023 \"THE QUICK BROWN FOX ...\""
+  )]
+  fn test_display(#[case] action: Action, #[case] text: &str, #[case] expected: &str) {
+    let entry = Entry {
+      started: 5,
+      done: 15,
+      unit: 3,
+      action,
+      data: charcode::str_to_words(text).unwrap(),
+    };
+
+    assert_eq!(entry.to_string(), expected);
+  }
+
+  #[test]
+  fn test_on_machine() {
+    let mut computer = Computer::new().with_transcript();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(
+        16,
+        Box::new(CardReader::from_reader("CARD".as_bytes()).unwrap()),
+      )
+      .unwrap();
+
+    program.add(Instruction::new(true, 1000, 0, 16, Operation::In));
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 18, Operation::Ioc));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    let transcript = computer.transcript.unwrap();
+    let actions: Vec<_> = transcript
+      .iter()
+      .map(|entry| (entry.started, entry.unit, entry.action.clone()))
+      .collect();
+
+    assert_eq!(
+      actions,
+      [
+        (1, 16, Action::In(1000..1016)),
+        (2, 18, Action::Out(1000..1024)),
+        (3, 18, Action::Control(0)),
+      ]
+    );
+    assert_eq!(
+      charcode::words_to_string(&transcript[1].data[..1]).unwrap(),
+      "CARD "
+    );
+  }
+}
@@ -0,0 +1,333 @@
+use std::ops::Range;
+
+use crate::{
+  computer::Computer,
+  device::{Device, UNITS},
+  error::MixError,
+  interrupt,
+  transcript::{Action, Entry},
+  word::Word,
+};
+
+/// What is left to do of an operation once the unit is done with it
+pub(crate) enum Completion {
+  /// IN stores the block it read into memory
+  Store(Range<usize>, Vec<Word>),
+  /// OUT writes the block as the memory holds it by then
+  Write(Range<usize>),
+  /// IOC with the operation M has nothing left to do
+  Control(i64),
+}
+
+/// Operation of a unit that goes on in the background of the program
+pub(crate) struct Transfer {
+  /// Time at which the operation was issued
+  pub started: u64,
+  /// Time at which the unit is done
+  pub done: u64,
+  pub completion: Completion,
+}
+
+/// Operations of the units take time, the memory changes or is read only once
+/// a unit is done, so a program that touches a buffer before JBUS or JRED
+/// tells it is ready sees the old words
+impl Computer {
+  /// Starts the operation on the unit, it is done after the latency of the
+  /// unit and at once if there is none
+  pub(crate) fn start(&mut self, unit: u32, completion: Completion) -> Result<(), MixError> {
+    let done = self.time + self.device(unit)?.latency();
+
+    self.transfers[unit as usize] = Some(Transfer {
+      started: self.time,
+      done,
+      completion,
+    });
+
+    if done <= self.time {
+      self.complete(unit)?;
+    }
+
+    Ok(())
+  }
+
+  /// Checks if the unit is busy, either with an operation that takes time
+  /// or on its own account
+  pub(crate) fn is_busy(&self, unit: u32) -> Result<bool, MixError> {
+    if self.device(unit)?.is_busy() {
+      return Ok(true);
+    }
+
+    Ok(
+      self.transfers[unit as usize]
+        .as_ref()
+        .is_some_and(|transfer| self.time < transfer.done),
+    )
+  }
+
+  /// Waits for the unit to be done with its operation, as IN, OUT and IOC
+  /// do on a busy unit, returns the time spent waiting
+  pub(crate) fn wait(&mut self, unit: u32) -> Result<u64, MixError> {
+    self.device(unit)?;
+
+    let waited = match &self.transfers[unit as usize] {
+      Some(transfer) => transfer.done.saturating_sub(self.time),
+      None => return Ok(0),
+    };
+
+    self.time += waited;
+    self.timing.wait[unit as usize] += waited;
+    self.complete_transfers()?;
+
+    Ok(waited)
+  }
+
+  /// Waits for the unit as [`wait`](Self::wait) does before IN, OUT or IOC,
+  /// a unit busy on its own account never gets ready and faults
+  pub(crate) fn wait_ready(&mut self, unit: u32) -> Result<u64, MixError> {
+    let waited = self.wait(unit)?;
+
+    if self.device(unit)?.is_busy() {
+      return Err(MixError::NotReady(unit));
+    }
+
+    Ok(waited)
+  }
+
+  /// Mounts the device on the unit while the machine is paused, as an
+  /// operator mounts another reel or deck, returns the device taken off
+  ///
+  /// The operator waits for the unit to be done with its operation first,
+  /// so the old device finishes it and the time of the machine moves on.
+  pub fn mount(&mut self, unit: u32, device: Box<dyn Device>) -> Result<Box<dyn Device>, MixError> {
+    self.wait(unit)?;
+
+    let mounted = self.devices[unit as usize]
+      .replace(device)
+      .ok_or(MixError::InvalidUnit(unit))?;
+
+    Ok(mounted)
+  }
+
+  /// Completes the operations the units are done with by now, in the order
+  /// they are done
+  pub(crate) fn complete_transfers(&mut self) -> Result<(), MixError> {
+    self.complete_until(self.time)
+  }
+
+  /// Completes all the operations in progress, since the units go on after
+  /// the machine halts
+  pub(crate) fn finish_transfers(&mut self) -> Result<(), MixError> {
+    self.complete_until(u64::MAX)
+  }
+
+  fn complete_until(&mut self, time: u64) -> Result<(), MixError> {
+    let mut done: Vec<(u64, u32)> = (0..UNITS)
+      .filter_map(|unit| {
+        let transfer = self.transfers[unit].as_ref()?;
+
+        (transfer.done <= time).then_some((transfer.done, unit as u32))
+      })
+      .collect();
+
+    done.sort_unstable();
+
+    for (_, unit) in done {
+      self.complete(unit)?;
+    }
+
+    Ok(())
+  }
+
+  /// Carries out what is left of the operation on the unit and signals that
+  /// the unit is done
+  fn complete(&mut self, unit: u32) -> Result<(), MixError> {
+    let Some(transfer) = self.transfers[unit as usize].take() else {
+      return Ok(());
+    };
+
+    let (action, data) = match transfer.completion {
+      Completion::Store(range, block) => {
+        self.memory[range.start..range.start + block.len()].copy_from_slice(&block);
+        (Action::In(range), block)
+      }
+      Completion::Write(range) => {
+        let device = self.devices[unit as usize]
+          .as_deref_mut()
+          .ok_or(MixError::InvalidUnit(unit))?;
+
+        let block = &self.memory[range.clone()];
+
+        device.write(block).map_err(|error| error.on(unit))?;
+        (Action::Out(range), block.to_vec())
+      }
+      Completion::Control(operation) => (Action::Control(operation), Vec::new()),
+    };
+
+    if let Some(transcript) = &mut self.transcript {
+      transcript.push(Entry {
+        started: transfer.started,
+        done: transfer.done,
+        unit,
+        action,
+        data,
+      });
+    }
+
+    self.interrupt(interrupt::device_interrupt(unit));
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    computer::HaltReason,
+    device::{DeviceKind, MemoryUnit},
+    instruction::{Instruction, Operation, Target},
+    program::Program,
+  };
+
+  fn hlt() -> Instruction {
+    Instruction::new(true, 0, 0, 0, Operation::Hlt)
+  }
+
+  fn slow_unit(computer: &mut Computer, unit: u32) -> &mut MemoryUnit {
+    let device: &mut MemoryUnit = computer.device_mut(unit).unwrap().downcast_mut().unwrap();
+
+    device.latency = 50;
+    device
+  }
+
+  #[test]
+  fn test_in_stores_when_done() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    slow_unit(&mut computer, 16).data = vec![Word::new(7, Some(true)); 16];
+
+    // Loads the buffer before the card is in, then again after waiting
+    program.add(Instruction::new(true, 1000, 0, 16, Operation::In));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::A)));
+    program.add(Instruction::new(true, 2, 0, 16, Operation::Jbus));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Ld(Target::X)));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(computer.a, Word::default());
+    assert_eq!(computer.x, Word::new(7, Some(true)));
+  }
+
+  #[test]
+  fn test_out_writes_when_done() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    slow_unit(&mut computer, 18);
+    computer.memory[1000] = Word::new(7, Some(true));
+
+    // Clears the buffer while the printer is still busy with it
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Stz));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    let printer: &MemoryUnit = computer.device(18).unwrap().downcast_ref().unwrap();
+
+    assert_eq!(printer.data, vec![Word::default(); 24]);
+  }
+
+  #[test]
+  fn test_out_after_jbus() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    slow_unit(&mut computer, 18);
+    computer.memory[1000] = Word::new(7, Some(true));
+
+    program.add(Instruction::new(true, 1000, 0, 18, Operation::Out));
+    program.add(Instruction::new(true, 1, 0, 18, Operation::Jbus));
+    program.add(Instruction::new(true, 1000, 0, 5, Operation::Stz));
+    program.add(hlt());
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+
+    let printer: &MemoryUnit = computer.device(18).unwrap().downcast_ref().unwrap();
+
+    assert_eq!(printer.data[0], Word::new(7, Some(true)));
+  }
+
+  #[test]
+  fn test_mount_next_reel() {
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+    let mut reel = MemoryUnit::new(DeviceKind::Tape);
+
+    slow_unit(&mut computer, 0).data = vec![Word::new(1, Some(true)); 100];
+    reel.data = vec![Word::new(2, Some(true)); 100];
+
+    program.add(Instruction::new(true, 1000, 0, 0, Operation::In));
+    program.add(Instruction::new(true, 1100, 0, 0, Operation::In));
+    program.add(hlt());
+
+    // Pauses after the first block to change reels while it is still read
+    assert_eq!(
+      computer.execute_with_limit(&program, 1),
+      Ok(HaltReason::LimitExceeded)
+    );
+
+    let first: Box<dyn Device> = computer.mount(0, Box::new(reel)).unwrap();
+
+    assert_eq!(first.downcast_ref::<MemoryUnit>().unwrap().position, 100);
+    assert_eq!(computer.time, 51);
+    assert_eq!(computer.memory[1000], Word::new(1, Some(true)));
+    assert_eq!(computer.run(computer.pc as usize), Ok(HaltReason::Halted));
+    assert_eq!(computer.memory[1100], Word::new(2, Some(true)));
+  }
+
+  #[test]
+  fn test_mount_detached_unit() {
+    let mut computer = Computer::new();
+    let device = MemoryUnit::new(DeviceKind::Tape);
+
+    computer.detach_device(0).unwrap();
+
+    assert!(matches!(
+      computer.mount(0, Box::new(device)),
+      Err(MixError::InvalidUnit(0))
+    ));
+  }
+
+  #[test]
+  fn test_complete_in_order() {
+    let mut computer = Computer::new();
+
+    computer.interrupts = true;
+
+    for (unit, done) in [(3, 30), (1, 10), (2, 20)] {
+      computer.transfers[unit] = Some(Transfer {
+        started: 0,
+        done,
+        completion: Completion::Control(0),
+      });
+    }
+
+    computer.time = 25;
+    computer.complete_transfers().unwrap();
+
+    assert!(computer.transfers[3].is_some());
+    assert_eq!(
+      computer.pending,
+      [
+        interrupt::device_interrupt(1),
+        interrupt::device_interrupt(2)
+      ]
+    );
+
+    computer.finish_transfers().unwrap();
+
+    assert!(computer.transfers.iter().all(Option::is_none));
+  }
+}
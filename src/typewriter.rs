@@ -0,0 +1,177 @@
+use std::{
+  fs::File,
+  io::{self, BufRead, BufReader, Write},
+  path::Path,
+};
+
+use crate::{charcode, device::Device, error::DeviceError, word::Word};
+
+/// Typewriter terminal, IN reads a line typed by the user and OUT types a
+/// line for them
+///
+/// A line holds up to 70 characters of the MIX character code, a shorter one
+/// is filled with blanks and lowercase letters are taken as uppercase. A line
+/// that does not fit or has other characters faults, and so does IN once the
+/// input has ended.
+pub struct Typewriter {
+  input: Box<dyn BufRead>,
+  output: Box<dyn Write>,
+}
+
+impl Typewriter {
+  /// Number of words in a line
+  pub const BLOCK_SIZE: usize = 14;
+
+  /// Number of characters in a line
+  pub const COLUMNS: usize = Self::BLOCK_SIZE * Word::BYTES;
+
+  /// Creates the typewriter reading lines from the input and typing them to
+  /// the output
+  pub fn new(input: impl BufRead + 'static, output: impl Write + 'static) -> Self {
+    Self {
+      input: Box::new(input),
+      output: Box::new(output),
+    }
+  }
+
+  /// Creates the typewriter of the terminal the emulator runs in
+  pub fn stdio() -> Self {
+    Self::new(io::stdin().lock(), io::stdout())
+  }
+
+  /// Creates the typewriter that takes its input from the lines of the file
+  /// instead of the user, so that a run can be repeated
+  pub fn scripted(path: impl AsRef<Path>) -> io::Result<Self> {
+    Ok(Self::new(BufReader::new(File::open(path)?), io::stdout()))
+  }
+}
+
+impl Device for Typewriter {
+  fn block_size(&self) -> usize {
+    Self::BLOCK_SIZE
+  }
+
+  fn read(&mut self) -> Result<Vec<Word>, DeviceError> {
+    let mut line = String::new();
+
+    if self.input.read_line(&mut line)? == 0 {
+      return Err(DeviceError::Io(io::ErrorKind::UnexpectedEof));
+    }
+
+    let line = line.trim_end_matches(['\n', '\r']).to_uppercase();
+
+    charcode::line_to_words(&line, Self::COLUMNS).ok_or(DeviceError::Io(io::ErrorKind::InvalidData))
+  }
+
+  /// Types the line without its trailing blanks, the bytes that are not a
+  /// character are typed as `?`
+  fn write(&mut self, block: &[Word]) -> Result<(), DeviceError> {
+    let line: String = block
+      .iter()
+      .map(|word| word.display_as_chars().to_string())
+      .collect();
+
+    writeln!(self.output, "{}", line.trim_end())?;
+    self.output.flush()?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+  use crate::{
+    computer::{Computer, HaltReason},
+    device::Paper,
+    instruction::{Instruction, Operation},
+    program::Program,
+  };
+
+  #[test]
+  fn test_read() {
+    let mut typewriter = Typewriter::new("Hello, MIX\r\n\n".as_bytes(), io::sink());
+
+    let line = typewriter.read().unwrap();
+
+    assert_eq!(line.len(), 14);
+    assert_eq!(charcode::words_to_string(&line[..2]).unwrap(), "HELLO, MIX");
+    assert!(line[2..].iter().all(|word| *word == Word::default()));
+    assert_eq!(typewriter.read(), Ok(vec![Word::default(); 14]));
+    assert_eq!(
+      typewriter.read(),
+      Err(DeviceError::Io(io::ErrorKind::UnexpectedEof))
+    );
+  }
+
+  #[rstest]
+  #[case("X".repeat(71))]
+  #[case("50%".to_string())]
+  fn test_read_invalid(#[case] line: String) {
+    let mut typewriter = Typewriter::new(io::Cursor::new(line), io::sink());
+
+    assert_eq!(
+      typewriter.read(),
+      Err(DeviceError::Io(io::ErrorKind::InvalidData))
+    );
+  }
+
+  #[test]
+  fn test_write() {
+    let paper = Paper::default();
+    let mut typewriter = Typewriter::new(io::empty(), paper.clone());
+    let mut line = charcode::str_to_words("ANSWER = 42").unwrap();
+
+    line.resize(14, Word::default());
+    typewriter.write(&line).unwrap();
+    typewriter
+      .write(&[Word::from_bytes([63, 1, 0, 0, 0], true)])
+      .unwrap();
+
+    assert_eq!(paper.text(), "ANSWER = 42\n?A\n");
+  }
+
+  #[test]
+  fn test_scripted() {
+    let mut script = tempfile::NamedTempFile::new().unwrap();
+
+    writeln!(script, "FIRST\nSECOND").unwrap();
+
+    let mut typewriter = Typewriter::scripted(script.path()).unwrap();
+
+    assert_eq!(
+      charcode::words_to_string(&typewriter.read().unwrap()[..1]).unwrap(),
+      "FIRST"
+    );
+    assert_eq!(
+      charcode::words_to_string(&typewriter.read().unwrap()[..2]).unwrap(),
+      "SECOND    "
+    );
+  }
+
+  #[test]
+  fn test_echo_on_machine() {
+    let paper = Paper::default();
+    let mut computer = Computer::new();
+    let mut program = Program::new();
+
+    computer
+      .attach_device(
+        19,
+        Box::new(Typewriter::new(
+          "what is your name\n".as_bytes(),
+          paper.clone(),
+        )),
+      )
+      .unwrap();
+
+    program.add(Instruction::new(true, 1000, 0, 19, Operation::In));
+    program.add(Instruction::new(true, 1000, 0, 19, Operation::Out));
+    program.add(Instruction::new(true, 0, 0, 0, Operation::Hlt));
+
+    assert_eq!(computer.execute(&program), Ok(HaltReason::Halted));
+    assert_eq!(paper.text(), "WHAT IS YOUR NAME\n");
+  }
+}
@@ -0,0 +1,470 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+  computer::Compare,
+  error::{FieldError, OutOfRange},
+  field::FieldSpec,
+  Signed,
+};
+
+/// Number of bits a byte takes in the packed form
+pub const BYTE_BITS: usize = 6;
+
+/// Bits of a single byte in the packed form
+const BYTE_MASK: u64 = (1 << BYTE_BITS) - 1;
+
+/// Represents a number of a sign and bytes, each byte holds up to 100 values
+/// so that the number fits any byte size of the machine
+///
+/// Words and registers differ in the number of bytes only, and the ten bytes
+/// of rA and rX taken together are one more such number. The packed form
+/// keeps six bits of each byte, which is the layout of a binary machine,
+/// with the sign above the bytes and 1 for plus, so up to ten bytes fit into
+/// the 64 bits it takes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(into = "Repr", try_from = "Repr")
+)]
+pub struct MixValue<const BYTES: usize> {
+  sign: bool,
+  bytes: [u8; BYTES],
+}
+
+impl<const BYTES: usize> MixValue<BYTES> {
+  pub const BYTES: usize = BYTES;
+
+  /// Bit of the sign in the packed form, which is also the number of
+  /// values the bytes hold together
+  const SIGN_BIT: u64 = 1 << (BYTE_BITS * BYTES);
+
+  /// Bits of the bytes in the packed form
+  const DATA_BITS: u64 = Self::SIGN_BIT - 1;
+
+  /// Creates the value from its bytes, the first one is byte 1
+  pub fn from_bytes(bytes: [u8; BYTES], sign: bool) -> Self {
+    Self { sign, bytes }
+  }
+
+  /// Returns the bytes, the sign is left out
+  pub fn to_bytes(&self) -> [u8; BYTES] {
+    self.bytes
+  }
+
+  /// Iterates over the bytes, the sign is left out
+  pub fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+    self.bytes.iter().copied()
+  }
+
+  /// Returns the byte by its index, byte 0 is the sign with 1 for plus
+  pub(crate) fn byte(&self, index: usize) -> u8 {
+    assert!(index <= BYTES);
+
+    match index {
+      0 => self.sign as u8,
+      _ => self.bytes[index - 1],
+    }
+  }
+
+  /// Replaces the byte by its index, byte 0 is the sign with 1 for plus
+  pub fn set_byte(&mut self, index: usize, value: u8) {
+    assert!(index <= BYTES);
+
+    match index {
+      0 => self.sign = value != 0,
+      _ => self.bytes[index - 1] = value,
+    }
+  }
+
+  /// Checks if all the bytes are zero, whatever the sign
+  pub fn is_zero(&self) -> bool {
+    self.bytes.iter().all(|byte| *byte == 0)
+  }
+
+  /// Compares the numbers the way CMPA and the other comparisons do, +0 and
+  /// -0 are equal and a negative number is less than a positive one
+  pub fn mix_cmp(&self, other: &Self) -> Compare {
+    let ordering = match (self.sign, other.sign) {
+      _ if self.is_zero() && other.is_zero() => Ordering::Equal,
+      (true, true) => self.bytes.cmp(&other.bytes),
+      (false, false) => other.bytes.cmp(&self.bytes),
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+    };
+
+    ordering.into()
+  }
+
+  /// Checks if the numbers are equal the way the machine sees them, unlike
+  /// `==` which tells +0 from -0
+  pub fn mix_eq(&self, other: &Self) -> bool {
+    self.mix_cmp(other) == Compare::Equal
+  }
+
+  /// Shifts the bytes to the left, zeros come in from the right and the
+  /// sign is left untouched
+  pub fn shift_left_bytes(&mut self, count: usize) {
+    let count = count.min(BYTES);
+
+    self.bytes.rotate_left(count);
+    self.bytes[BYTES - count..].fill(0);
+  }
+
+  /// Shifts the bytes to the right, zeros come in from the left and the
+  /// sign is left untouched
+  pub fn shift_right_bytes(&mut self, count: usize) {
+    let count = count.min(BYTES);
+
+    self.bytes.rotate_right(count);
+    self.bytes[..count].fill(0);
+  }
+
+  /// Shifts the bytes to the left circularly
+  pub fn rotate_left_bytes(&mut self, count: usize) {
+    self.bytes.rotate_left(count % BYTES);
+  }
+
+  /// Shifts the bytes to the right circularly
+  pub fn rotate_right_bytes(&mut self, count: usize) {
+    self.bytes.rotate_right(count % BYTES);
+  }
+
+  /// Writes the sign and the bytes in decimal, two digits each
+  pub(crate) fn write_bytes(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", if self.sign { "+" } else { "-" })?;
+
+    for (index, byte) in self.bytes().enumerate() {
+      if index > 0 {
+        write!(f, " ")?;
+      }
+
+      write!(f, "{:02}", byte)?;
+    }
+
+    Ok(())
+  }
+
+  /// Makes the value of the packed number, the bits above the bytes are
+  /// dropped
+  pub(crate) fn packed(number: u64, sign: bool) -> Self {
+    let number = number & Self::DATA_BITS;
+
+    Self {
+      sign,
+      bytes: std::array::from_fn(|index| {
+        ((number >> ((BYTES - 1 - index) * BYTE_BITS)) & BYTE_MASK) as u8
+      }),
+    }
+  }
+
+  /// Packs the bytes into a number, six bits each
+  pub(crate) fn pack(&self) -> u64 {
+    self.bytes.iter().fold(0, |data, byte| {
+      (data << BYTE_BITS) | (*byte as u64 & BYTE_MASK)
+    })
+  }
+
+  /// Packs the sign and the bytes into a number
+  pub(crate) fn pack_signed(&self) -> u64 {
+    self.pack() | if self.sign { Self::SIGN_BIT } else { 0 }
+  }
+
+  /// Replaces the bytes with those of the packed number
+  pub(crate) fn unpack(&mut self, number: u64) {
+    self.bytes = Self::packed(number, self.sign).bytes;
+  }
+
+  /// Checks that the field lies within the bytes
+  pub(crate) fn check_field(field: FieldSpec) -> Result<FieldSpec, FieldError> {
+    if field.right as usize > BYTES {
+      return Err(FieldError::OutOfRange(field));
+    }
+
+    Ok(field)
+  }
+
+  /// Packs the field, the sign comes as the highest bit when the field
+  /// includes it
+  pub(crate) fn pack_field(&self, field: FieldSpec) -> u64 {
+    let FieldSpec { left, right } = field;
+
+    assert!(right as usize <= BYTES);
+
+    (left..=right).fold(0, |result, index| {
+      (result << BYTE_BITS) | (self.byte(index as usize) as u64 & BYTE_MASK)
+    })
+  }
+
+  /// Packs the bytes of the field as a magnitude together with its sign, the
+  /// field is negative only if it includes the sign and the sign is minus
+  pub(crate) fn pack_signed_field(&self, field: FieldSpec) -> (u64, bool) {
+    let sign = !field.includes_sign() || self.sign;
+
+    // The field of the sign alone has no bytes, its magnitude is zero
+    if field.right == 0 {
+      return (0, sign);
+    }
+
+    let bytes = FieldSpec::new(field.left.max(1), field.right);
+
+    (self.pack_field(bytes), sign)
+  }
+
+  /// Replaces the field with the packed number, taken from the right
+  pub(crate) fn unpack_field(&mut self, number: u64, field: FieldSpec) {
+    let mut rest = number;
+    let FieldSpec { left, right } = field;
+
+    assert!(right as usize <= BYTES);
+
+    for index in (left..=right).rev() {
+      match index {
+        0 => self.sign = rest & 1 != 0,
+        _ => self.bytes[index as usize - 1] = (rest & BYTE_MASK) as u8,
+      }
+
+      rest >>= BYTE_BITS;
+    }
+  }
+}
+
+/// The default value is +0, the machine never produces -0 unless an
+/// operation calls for it
+impl<const BYTES: usize> Default for MixValue<BYTES> {
+  fn default() -> Self {
+    Self {
+      sign: true,
+      bytes: [0; BYTES],
+    }
+  }
+}
+
+/// Makes a value of the signed number in binary bytes, the magnitude must
+/// fit into the bytes
+impl<const BYTES: usize> TryFrom<i64> for MixValue<BYTES> {
+  type Error = OutOfRange;
+
+  fn try_from(value: i64) -> Result<Self, Self::Error> {
+    if value.unsigned_abs() > Self::DATA_BITS {
+      return Err(OutOfRange(value));
+    }
+
+    Ok(Self::packed(value.unsigned_abs(), value >= 0))
+  }
+}
+
+impl<const BYTES: usize> Signed for MixValue<BYTES> {
+  fn read_sign(&self) -> bool {
+    self.sign
+  }
+
+  fn write_sign(&mut self, sign: bool) {
+    self.sign = sign;
+  }
+}
+
+/// Shows the packed value, the sign is the highest bit with 1 for plus
+impl<const BYTES: usize> fmt::LowerHex for MixValue<BYTES> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::LowerHex::fmt(&self.pack_signed(), f)
+  }
+}
+
+/// Shows the packed value, the sign is the highest bit with 1 for plus
+impl<const BYTES: usize> fmt::Binary for MixValue<BYTES> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Binary::fmt(&self.pack_signed(), f)
+  }
+}
+
+/// Shows the sign and the bytes in octal, the way MIX listings show words,
+/// a binary byte takes two digits
+impl<const BYTES: usize> fmt::Octal for MixValue<BYTES> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", if self.sign { "+" } else { "-" })?;
+
+    for (index, byte) in self.bytes().enumerate() {
+      if index > 0 {
+        write!(f, " ")?;
+      }
+
+      write!(f, "{:02o}", byte)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Form the value takes in serialized data, the bytes are a sequence as
+/// serde has no impls for arrays of any length
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Repr {
+  sign: bool,
+  bytes: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl<const BYTES: usize> From<MixValue<BYTES>> for Repr {
+  fn from(value: MixValue<BYTES>) -> Self {
+    Self {
+      sign: value.sign,
+      bytes: value.bytes.to_vec(),
+    }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<const BYTES: usize> TryFrom<Repr> for MixValue<BYTES> {
+  type Error = String;
+
+  fn try_from(value: Repr) -> Result<Self, Self::Error> {
+    let count = value.bytes.len();
+    let bytes: [u8; BYTES] = value
+      .bytes
+      .try_into()
+      .map_err(|_| format!("expected {} bytes, found {}", BYTES, count))?;
+
+    // No byte size of the machine holds more values than a decimal one
+    let limit = crate::byte::ByteSize::DECIMAL.get();
+
+    if let Some(byte) = bytes.iter().find(|&&byte| byte as u32 >= limit) {
+      return Err(format!("byte {} is out of range", byte));
+    }
+
+    Ok(Self::from_bytes(bytes, value.sign))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  #[test]
+  fn test_default() {
+    assert_eq!(MixValue::<3>::default(), MixValue::from_bytes([0; 3], true));
+  }
+
+  #[rstest]
+  #[case(MixValue::from_bytes([0, 0], true), MixValue::from_bytes([0, 0], false), true)]
+  #[case(MixValue::from_bytes([1, 2], false), MixValue::from_bytes([1, 2], false), true)]
+  #[case(MixValue::from_bytes([1, 2], true), MixValue::from_bytes([1, 2], false), false)]
+  #[case(MixValue::from_bytes([1, 2], true), MixValue::from_bytes([2, 1], true), false)]
+  fn test_mix_eq(#[case] value: MixValue<2>, #[case] other: MixValue<2>, #[case] expected: bool) {
+    assert_eq!(value.mix_eq(&other), expected);
+    assert_eq!(other.mix_eq(&value), expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0, [1, 2, 3, 4, 5], [1, 2, 3, 4, 5])]
+  #[case(1, [2, 3, 4, 5, 0], [0, 1, 2, 3, 4])]
+  #[case(4, [5, 0, 0, 0, 0], [0, 0, 0, 0, 1])]
+  #[case(5, [0, 0, 0, 0, 0], [0, 0, 0, 0, 0])]
+  #[case(99, [0, 0, 0, 0, 0], [0, 0, 0, 0, 0])]
+  fn test_shift_bytes(#[case] count: usize, #[case] left: [u8; 5], #[case] right: [u8; 5]) {
+    let mut value = MixValue::from_bytes([1, 2, 3, 4, 5], false);
+
+    value.shift_left_bytes(count);
+    assert_eq!(value, MixValue::from_bytes(left, false));
+
+    let mut value = MixValue::from_bytes([1, 2, 3, 4, 5], false);
+
+    value.shift_right_bytes(count);
+    assert_eq!(value, MixValue::from_bytes(right, false));
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0, [1, 2, 3, 4, 5], [1, 2, 3, 4, 5])]
+  #[case(2, [3, 4, 5, 1, 2], [4, 5, 1, 2, 3])]
+  #[case(5, [1, 2, 3, 4, 5], [1, 2, 3, 4, 5])]
+  #[case(7, [3, 4, 5, 1, 2], [4, 5, 1, 2, 3])]
+  fn test_rotate_bytes(#[case] count: usize, #[case] left: [u8; 5], #[case] right: [u8; 5]) {
+    let mut value = MixValue::from_bytes([1, 2, 3, 4, 5], true);
+
+    value.rotate_left_bytes(count);
+    assert_eq!(value, MixValue::from_bytes(left, true));
+
+    let mut value = MixValue::from_bytes([1, 2, 3, 4, 5], true);
+
+    value.rotate_right_bytes(count);
+    assert_eq!(value, MixValue::from_bytes(right, true));
+  }
+
+  #[rstest]
+  #[case(0b1_000001_000010_000011, MixValue::from_bytes([1, 2, 3], true))]
+  #[case(0b0_111111_000000_000001, MixValue::from_bytes([63, 0, 1], false))]
+  fn test_pack(#[case] number: u64, #[case] value: MixValue<3>) {
+    assert_eq!(MixValue::packed(number, number >> 18 != 0), value);
+    assert_eq!(value.pack_signed(), number);
+    assert_eq!(value.pack(), number & ((1 << 18) - 1));
+  }
+
+  #[test]
+  fn test_pack_ten_bytes() {
+    let value = MixValue::from_bytes([63; 10], false);
+
+    assert_eq!(value.pack(), (1 << 60) - 1);
+    assert_eq!(value.pack_signed(), (1 << 60) - 1);
+    assert_eq!(MixValue::<10>::packed(u64::MAX, false), value);
+  }
+
+  #[rstest]
+  #[case(FieldSpec::new(0, 0), 0, false)]
+  #[case(FieldSpec::new(0, 1), 1, false)]
+  #[case(FieldSpec::new(1, 3), 0b000001_000010_000011, true)]
+  #[case(FieldSpec::new(3, 3), 3, true)]
+  fn test_pack_signed_field(#[case] field: FieldSpec, #[case] magnitude: u64, #[case] sign: bool) {
+    let value = MixValue::from_bytes([1, 2, 3], false);
+
+    assert_eq!(value.pack_signed_field(field), (magnitude, sign));
+  }
+
+  #[rstest]
+  #[case(FieldSpec::new(0, 0), 1, MixValue::from_bytes([1, 2, 3], true))]
+  #[case(FieldSpec::new(2, 3), 0b111111_000000, MixValue::from_bytes([1, 63, 0], false))]
+  #[case(FieldSpec::new(0, 1), 0b1_000111, MixValue::from_bytes([7, 2, 3], true))]
+  fn test_unpack_field(
+    #[case] field: FieldSpec,
+    #[case] number: u64,
+    #[case] expected: MixValue<3>,
+  ) {
+    let mut value = MixValue::from_bytes([1, 2, 3], false);
+
+    value.unpack_field(number, field);
+
+    assert_eq!(value, expected);
+  }
+
+  #[rstest]
+  #[case(262_143, Ok(MixValue::from_bytes([63, 63, 63], true)))]
+  #[case(-1, Ok(MixValue::from_bytes([0, 0, 1], false)))]
+  #[case(262_144, Err(OutOfRange(262_144)))]
+  fn test_try_from_i64(#[case] number: i64, #[case] expected: Result<MixValue<3>, OutOfRange>) {
+    assert_eq!(MixValue::try_from(number), expected);
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn test_serde_round_trip() {
+    let value = MixValue::from_bytes([1, 63, 99], false);
+    let text = serde_json::to_string(&value).unwrap();
+
+    assert_eq!(text, r#"{"sign":false,"bytes":[1,63,99]}"#);
+    assert_eq!(serde_json::from_str::<MixValue<3>>(&text).unwrap(), value);
+  }
+
+  #[cfg(feature = "serde")]
+  #[rstest]
+  #[case(r#"{"sign":true,"bytes":[1,100,3]}"#, "byte 100 is out of range")]
+  #[case(r#"{"sign":true,"bytes":[1,2]}"#, "expected 3 bytes, found 2")]
+  fn test_deserialize_invalid(#[case] text: &str, #[case] message: &str) {
+    let error = serde_json::from_str::<MixValue<3>>(text).unwrap_err();
+
+    assert_eq!(error.to_string(), message);
+  }
+}
@@ -1,130 +1,367 @@
 use std::fmt;
 
-use crate::{Data, Signed};
-
-/// Represents a word with a 30-bit value and a sign bit
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Word {
-  data: u32,
-}
+use crate::{
+  byte::ByteSize,
+  charcode,
+  error::{FieldError, OutOfRange},
+  field::FieldSpec,
+  instruction::Instruction,
+  pair::WordPair,
+  value::MixValue,
+  Data, FieldRead, FieldWrite, Signed,
+};
+
+/// Represents a word of five bytes and a sign
+///
+/// The packed form used by `Data` keeps six bits of each byte, which is the
+/// layout of a binary machine.
+pub type Word = MixValue<5>;
 
 impl Word {
-  const BYTES: usize = 5;
-
   #[rustfmt::skip]
   const SIGN_MASK:  u32 = 0b0100_0000_0000_0000_0000_0000_0000_0000;
 
   #[rustfmt::skip]
   const DATA_MASK:  u32 = 0b0011_1111_1111_1111_1111_1111_1111_1111;
 
-  #[rustfmt::skip]
-  const VALUE_MASK: u32 = 0b0111_1111_1111_1111_1111_1111_1111_1111;
-
   pub fn new(number: u32, sign: Option<bool>) -> Self {
-    let mut data = number & Self::DATA_MASK;
+    Self::packed(number as u64, sign == Some(true))
+  }
 
-    if let Some(sign) = sign {
-      if sign {
-        data |= Self::SIGN_MASK;
-      }
+  /// Creates the word as `new` does, but refuses a number with bits above
+  /// the sign rather than dropping them
+  pub fn try_new(number: u32, sign: Option<bool>) -> Result<Self, OutOfRange> {
+    if number & !(Self::SIGN_MASK | Self::DATA_MASK) != 0 {
+      return Err(OutOfRange(number as i64));
     }
 
-    Self { data }
+    Ok(Self::new(number, sign))
   }
 }
 
-impl Default for Word {
-  fn default() -> Self {
-    Self::new(0, None)
-  }
+/// Result of an arithmetic operation together with whether it overflowed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overflowing<T> {
+  pub result: T,
+  pub overflow: bool,
 }
 
-impl From<u32> for Word {
-  fn from(value: u32) -> Self {
-    Self {
-      data: value & Word::VALUE_MASK,
+/// Arithmetic on signed magnitudes, the way the machine does it with the
+/// bytes of the byte size
+impl Word {
+  /// Adds the value, only the remainder modulo the capacity of the word is
+  /// kept on overflow and a zero result keeps the sign of the word
+  pub fn checked_add(&self, other: &Word, byte_size: ByteSize) -> Overflowing<Word> {
+    let capacity = byte_size.capacity(Self::BYTES as u32) as i64;
+    let sum = byte_size.value(self) + byte_size.value(other);
+    let sign = if sum == 0 { self.read_sign() } else { sum > 0 };
+
+    Overflowing {
+      result: byte_size.word(sum.unsigned_abs() % capacity as u64, sign),
+      overflow: sum.abs() >= capacity,
+    }
+  }
+
+  /// Subtracts the value, the same as adding it with the opposite sign
+  pub fn checked_sub(&self, other: &Word, byte_size: ByteSize) -> Overflowing<Word> {
+    let mut negated = *other;
+
+    negated.write_sign(!other.read_sign());
+    self.checked_add(&negated, byte_size)
+  }
+
+  /// Multiplies by the value, the product takes ten bytes so it never
+  /// overflows and both words of it get the sign of the product
+  pub fn checked_mul(&self, other: &Word, byte_size: ByteSize) -> Overflowing<WordPair> {
+    let sign = self.read_sign() == other.read_sign();
+    let product = byte_size.magnitude(self) as u128 * byte_size.magnitude(other) as u128;
+
+    Overflowing {
+      result: WordPair::from_magnitude(product, byte_size, sign, sign),
+      overflow: false,
+    }
+  }
+
+  /// Divides by the value, the quotient comes first in the pair and the
+  /// remainder, with the sign of the word, second
+  pub fn checked_div(&self, other: &Word, byte_size: ByteSize) -> Overflowing<WordPair> {
+    let zero = Word::from_bytes([0; Self::BYTES], self.read_sign());
+
+    WordPair::new(zero, *self).checked_div(other, byte_size)
+  }
+
+  /// Adds the value keeping only the remainder modulo the capacity of the
+  /// word, which is what ADD leaves in rA on overflow
+  pub fn wrapping_add(&self, other: &Word, byte_size: ByteSize) -> Word {
+    self.checked_add(other, byte_size).result
+  }
+
+  /// Subtracts the value keeping only the remainder modulo the capacity of
+  /// the word
+  pub fn wrapping_sub(&self, other: &Word, byte_size: ByteSize) -> Word {
+    self.checked_sub(other, byte_size).result
+  }
+
+  /// Adds the value, a sum that does not fit becomes the largest magnitude
+  /// of the word with the sign of the sum
+  pub fn saturating_add(&self, other: &Word, byte_size: ByteSize) -> Word {
+    let Overflowing { result, overflow } = self.checked_add(other, byte_size);
+
+    if overflow {
+      byte_size.word(
+        byte_size.capacity(Self::BYTES as u32) - 1,
+        result.read_sign(),
+      )
+    } else {
+      result
     }
   }
+
+  /// Subtracts the value, a difference that does not fit becomes the
+  /// largest magnitude of the word with the sign of the difference
+  pub fn saturating_sub(&self, other: &Word, byte_size: ByteSize) -> Word {
+    let mut negated = *other;
+
+    negated.write_sign(!other.read_sign());
+    self.saturating_add(&negated, byte_size)
+  }
 }
 
-impl Data<u32> for Word {
-  fn read(&self) -> u32 {
-    self.data & Self::VALUE_MASK
+/// Nodes of two links, the way the lists of chapter 2 keep them, with the
+/// sign and byte 3 left for a tag
+impl Word {
+  /// Field of the first address of a node
+  pub const LEFT_ADDRESS: FieldSpec = FieldSpec::new(1, 2);
+
+  /// Field of the second address of a node
+  pub const RIGHT_ADDRESS: FieldSpec = FieldSpec::new(4, 5);
+
+  /// Makes a node of the two addresses in the byte size, refuses an address
+  /// that does not fit in two bytes
+  pub fn pack_addresses(left: u32, right: u32, byte_size: ByteSize) -> Result<Self, OutOfRange> {
+    let mut word = Self::default();
+
+    word.write_address(Self::LEFT_ADDRESS, left, byte_size)?;
+    word.write_address(Self::RIGHT_ADDRESS, right, byte_size)?;
+
+    Ok(word)
   }
 
-  fn read_data(&self) -> u32 {
-    self.data & Self::DATA_MASK
+  /// Reads the two addresses of a node in the byte size
+  pub fn unpack_addresses(&self, byte_size: ByteSize) -> (u32, u32) {
+    (
+      self.read_address(Self::LEFT_ADDRESS, byte_size),
+      self.read_address(Self::RIGHT_ADDRESS, byte_size),
+    )
   }
 
-  fn read_with_modifier(&self, modifier: u32) -> u32 {
-    let mut result: u32 = 0;
-    let (left, right) = Self::split_modifier(modifier);
+  /// Replaces the two bytes of the field with the address
+  fn write_address(
+    &mut self,
+    field: FieldSpec,
+    address: u32,
+    byte_size: ByteSize,
+  ) -> Result<(), OutOfRange> {
+    if address as u64 >= byte_size.capacity(2) {
+      return Err(OutOfRange(address as i64));
+    }
 
-    assert!(right <= Self::BYTES as u32);
+    let bytes: [u8; 2] = byte_size.bytes(address as u64);
 
-    for index in left..=right {
-      result <<= 6;
-      result |= self.get_byte(index as usize) as u32;
+    for (index, byte) in (field.left..=field.right).zip(bytes) {
+      self.set_byte(index as usize, byte);
     }
 
-    result
+    Ok(())
+  }
+
+  /// Reads the two bytes of the field as an address
+  fn read_address(&self, field: FieldSpec, byte_size: ByteSize) -> u32 {
+    (field.left..=field.right).fold(0, |address, index| {
+      address * byte_size.get() + self.byte(index as usize) as u32
+    })
+  }
+}
+
+/// Partial fields, the way the store instructions write them
+impl Word {
+  /// Writes the value into the field as STA does: the sign if the field
+  /// includes it and the lowest bytes of the value into the bytes of the
+  /// field, the rest of the word is kept
+  pub fn store(&mut self, value: &Word, field: FieldSpec) {
+    let FieldSpec { left, right } = field;
+
+    if left == 0 {
+      self.write_sign(value.read_sign());
+    }
+
+    for index in left.max(1)..=right {
+      self.set_byte(
+        index as usize,
+        value.get_byte((Self::BYTES as u32 - (right - index)) as usize),
+      );
+    }
+  }
+}
+
+impl From<u32> for Word {
+  fn from(value: u32) -> Self {
+    Self::new(value, Some(value & Self::SIGN_MASK != 0))
+  }
+}
+
+impl Data<u32> for Word {
+  fn read(&self) -> u32 {
+    self.pack_signed() as u32
+  }
+
+  fn read_data(&self) -> u32 {
+    self.pack() as u32
   }
 
   fn write(&mut self, number: u32, sign: bool) {
-    self.data = (number & Self::DATA_MASK) | if sign { Self::SIGN_MASK } else { 0 };
+    self.unpack(number as u64);
+    self.write_sign(sign);
   }
 
   fn write_data(&mut self, number: u32) {
-    self.data = (number & Self::DATA_MASK) | (self.data & Self::SIGN_MASK);
+    self.unpack(number as u64);
   }
 
+  /// Returns the byte by its index, byte 0 is the sign with 1 for plus
   fn get_byte(&self, index: usize) -> u8 {
-    assert!(index <= Self::BYTES);
-
-    ((self.data >> ((Self::BYTES - index) * 6)) & 0b111111) as u8
+    self.byte(index)
   }
 }
 
-impl Signed for Word {
-  fn read_sign(&self) -> bool {
-    (self.data & Self::SIGN_MASK) != 0
+impl FieldRead<u32> for Word {
+  fn read_with_modifier(&self, field: FieldSpec) -> Result<u32, FieldError> {
+    Ok(self.pack_field(Self::check_field(field)?) as u32)
   }
 
-  fn write_sign(&mut self, sign: bool) {
-    if sign {
-      self.data |= Self::SIGN_MASK;
-    } else {
-      self.data &= !Self::SIGN_MASK;
-    }
+  fn read_field(&self, field: FieldSpec) -> Result<(u32, bool), FieldError> {
+    let (magnitude, sign) = self.pack_signed_field(Self::check_field(field)?);
+
+    Ok((magnitude as u32, sign))
   }
 }
 
+impl FieldWrite<u32> for Word {
+  fn write_with_modifier(&mut self, number: u32, field: FieldSpec) -> Result<(), FieldError> {
+    self.unpack_field(number as u64, Self::check_field(field)?);
+
+    Ok(())
+  }
+}
+
+/// Shows the sign and the five bytes, or with the alternate flag the fields
+/// of an instruction: the address (1:2), the index (3:3), the modifier (4:4)
+/// and the operation code (5:5)
 impl fmt::Display for Word {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    if self.read_sign() {
-      write!(f, "+")?
-    } else {
-      write!(f, "-")?
+    if !f.alternate() {
+      return self.write_bytes(f);
     }
 
     write!(
       f,
-      "{:06} {:06} {:06} {:06}",
-      self.read_with_modifier(12),
-      self.read_with_modifier(33),
-      self.read_with_modifier(44),
-      self.read_with_modifier(55),
+      "{}{:06} {:06} {:06} {:06}",
+      if self.read_sign() { "+" } else { "-" },
+      self.pack_field(FieldSpec::new(1, 2)),
+      self.pack_field(FieldSpec::new(3, 3)),
+      self.pack_field(FieldSpec::new(4, 4)),
+      self.pack_field(FieldSpec::new(5, 5)),
     )
   }
 }
 
+impl Word {
+  /// Shows the word as a signed number in the byte size
+  pub fn display_as_value(&self, byte_size: ByteSize) -> Value<'_> {
+    Value {
+      word: self,
+      byte_size,
+    }
+  }
+
+  /// Shows the word as the instruction it encodes in the byte size
+  pub fn display_as_instruction(&self, byte_size: ByteSize) -> Disassembly<'_> {
+    Disassembly {
+      word: self,
+      byte_size,
+    }
+  }
+
+  /// Shows the bytes of the word as five characters
+  pub fn display_as_chars(&self) -> Chars<'_> {
+    Chars(self)
+  }
+
+  /// Reads the bytes as five characters of the MIX character code, such as
+  /// an ALF constant or a word of a printer line, none if a byte is not a
+  /// character
+  pub fn to_chars(&self) -> Option<[char; Self::BYTES]> {
+    let mut chars = [' '; Self::BYTES];
+
+    for (char, byte) in chars.iter_mut().zip(self.bytes()) {
+      *char = charcode::code_to_char(byte)?;
+    }
+
+    Some(chars)
+  }
+}
+
+/// Shows the word as a signed number, minus zero keeps its sign
+pub struct Value<'a> {
+  word: &'a Word,
+  byte_size: ByteSize,
+}
+
+impl fmt::Display for Value<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let sign = if self.word.read_sign() { "+" } else { "-" };
+
+    write!(f, "{}{}", sign, self.byte_size.magnitude(self.word))
+  }
+}
+
+/// Shows the word as an instruction, a word that does not decode to one is
+/// shown byte by byte
+pub struct Disassembly<'a> {
+  word: &'a Word,
+  byte_size: ByteSize,
+}
+
+impl fmt::Display for Disassembly<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match Instruction::decode(self.word, self.byte_size) {
+      Ok(instruction) => write!(f, "{}", instruction),
+      Err(_) => write!(f, "{}", self.word),
+    }
+  }
+}
+
+/// Shows the bytes as characters of the MIX character code, the bytes that
+/// are not a character are shown as `?`
+pub struct Chars<'a>(&'a Word);
+
+impl fmt::Display for Chars<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for byte in self.0.bytes() {
+      write!(f, "{}", charcode::code_to_char(byte).unwrap_or('?'))?;
+    }
+
+    Ok(())
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use rstest::rstest;
   use rstest_reuse::{self, *};
 
   use super::*;
-  use crate::tests::split_modifier_cases;
+  use crate::computer::Compare;
 
   #[template]
   #[rstest]
@@ -186,41 +423,373 @@ mod tests {
   #[rustfmt::skip]
   #[template]
   #[rstest]
-  #[case(0b0000_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0000_0000, false, 0)]
-  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0000_0001, true, 0)]
-  #[case(0b0011_1111_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, 1)]
-  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, 1)]
-  #[case(0b0010_1010_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0110_1010, true, 1)]
-  #[case(0b0110_1010_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0110_1010, true, 1)]
-  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, 2)]
-  #[case(0b0111_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, 2)]
-  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0001_1111_1111_1111, true, 2)]
-  #[case(0b0111_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0001_1111_1111_1111, true, 2)]
-  #[case(0b0011_1111_1111_1111_1111_1111_1111_1111, 0b0011_1111_1111_1111_1111_1111_1111_1111, false, 5)]
-  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0011_1111_1111_1111_1111_1111_1111_1111, false, 5)]
-  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0111_1111_1111_1111_1111_1111_1111_1111, true, 5)]
-  #[case(0b1111_1111_1111_1111_1111_1111_1111_1111, 0b0111_1111_1111_1111_1111_1111_1111_1111, true, 5)]
-  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, 12)]
-  #[case(0b0111_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, true, 12)]
-  #[case(0b0000_0001_1111_1110_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, 22)]
-  #[case(0b0000_0001_1111_1110_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, true, 22)]
-  #[case(0b0000_0000_0000_0011_1111_1111_1100_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, 34)]
-  #[case(0b0000_0000_0000_0111_1111_1111_1110_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, true, 34)]
-  #[case(0b0000_0000_0000_0000_0000_1111_1111_1111, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, 45)]
-  #[case(0b0000_0000_0000_0000_0001_1111_1111_1111, 0b0000_0000_0000_0000_0000_1111_1111_1111, true, 45)]
-  #[case(0b0000_0000_0000_0000_0000_0000_0011_1111, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, 55)]
-  #[case(0b0000_0000_0000_0000_0000_0000_0111_1111, 0b0000_0000_0000_0000_0000_0000_0011_1111, true, 55)]
+  #[case(0b0000_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0000_0000, false, FieldSpec::new(0, 0))]
+  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0000_0001, true, FieldSpec::new(0, 0))]
+  #[case(0b0011_1111_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, FieldSpec::new(0, 1))]
+  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, FieldSpec::new(0, 1))]
+  #[case(0b0010_1010_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0110_1010, true, FieldSpec::new(0, 1))]
+  #[case(0b0110_1010_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0110_1010, true, FieldSpec::new(0, 1))]
+  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, FieldSpec::new(0, 2))]
+  #[case(0b0111_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, FieldSpec::new(0, 2))]
+  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0001_1111_1111_1111, true, FieldSpec::new(0, 2))]
+  #[case(0b0111_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0001_1111_1111_1111, true, FieldSpec::new(0, 2))]
+  #[case(0b0011_1111_1111_1111_1111_1111_1111_1111, 0b0011_1111_1111_1111_1111_1111_1111_1111, false, FieldSpec::new(0, 5))]
+  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0011_1111_1111_1111_1111_1111_1111_1111, false, FieldSpec::new(0, 5))]
+  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0111_1111_1111_1111_1111_1111_1111_1111, true, FieldSpec::new(0, 5))]
+  #[case(0b1111_1111_1111_1111_1111_1111_1111_1111, 0b0111_1111_1111_1111_1111_1111_1111_1111, true, FieldSpec::new(0, 5))]
+  #[case(0b0011_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, FieldSpec::new(1, 2))]
+  #[case(0b0111_1111_1111_1100_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, true, FieldSpec::new(1, 2))]
+  #[case(0b0000_0001_1111_1110_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, FieldSpec::new(2, 2))]
+  #[case(0b0000_0001_1111_1110_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, true, FieldSpec::new(2, 2))]
+  #[case(0b0000_0000_0000_0011_1111_1111_1100_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, FieldSpec::new(3, 4))]
+  #[case(0b0000_0000_0000_0111_1111_1111_1110_0000, 0b0000_0000_0000_0000_0000_1111_1111_1111, true, FieldSpec::new(3, 4))]
+  #[case(0b0000_0000_0000_0000_0000_1111_1111_1111, 0b0000_0000_0000_0000_0000_1111_1111_1111, false, FieldSpec::new(4, 5))]
+  #[case(0b0000_0000_0000_0000_0001_1111_1111_1111, 0b0000_0000_0000_0000_0000_1111_1111_1111, true, FieldSpec::new(4, 5))]
+  #[case(0b0000_0000_0000_0000_0000_0000_0011_1111, 0b0000_0000_0000_0000_0000_0000_0011_1111, false, FieldSpec::new(5, 5))]
+  #[case(0b0000_0000_0000_0000_0000_0000_0111_1111, 0b0000_0000_0000_0000_0000_0000_0011_1111, true, FieldSpec::new(5, 5))]
   fn read_with_modifier_cases(
     #[case] number: u32,
     #[case] expected: u32,
     #[case] sign: bool,
-    #[case] modifier: u32,
+    #[case] field: FieldSpec,
+  ) {
+  }
+
+  #[rustfmt::skip]
+  #[template]
+  #[rstest]
+  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0000_0001, FieldSpec::new(0, 0), 0b0100_0000_0000_0000_0000_0000_0000_0000)]
+  #[case(0b0000_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0000_0001, FieldSpec::new(0, 0), 0b0100_0000_0000_0000_0000_0000_0000_0000)]
+  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0000_0000_0000_0000_0000_0000_0000_0000, FieldSpec::new(0, 0), 0b0011_1111_1111_1111_1111_1111_1111_1111)]
+  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, FieldSpec::new(0, 1), 0b0011_1111_0000_0000_0000_0000_0000_0000)]
+  #[case(0b0011_1111_1111_1111_1111_1111_1111_1111, 0b0000_0000_0000_0000_0000_0000_0000_0000, FieldSpec::new(0, 1), 0b0000_0000_1111_1111_1111_1111_1111_1111)]
+  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, FieldSpec::new(0, 5), 0b0000_0000_0000_0000_0000_0000_0011_1111)]
+  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0000_0000_0000_0000_0000_0000_0000_0001, FieldSpec::new(0, 5), 0b0000_0000_0000_0000_0000_0000_0000_0001)]
+  #[case(0b0000_0000_0000_0000_0000_0000_0000_0000, 0b0100_0001_0000_1000_0011_0001_0000_0101, FieldSpec::new(0, 5), 0b0100_0001_0000_1000_0011_0001_0000_0101)]
+  #[case(0b0000_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0001_0000_0100_0010, FieldSpec::new(0, 2), 0b0100_0001_0000_1000_0000_0000_0000_0000)]
+  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0000_0000_0000_0000_0000_0000_0000_0000, FieldSpec::new(0, 2), 0b0000_0000_0000_0011_1111_1111_1111_1111)]
+  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_1100_0100, FieldSpec::new(1, 2), 0b0100_0011_0001_0000_0000_0000_0000_0000)]
+  #[case(0b0111_1111_1111_1111_1111_1111_1111_1111, 0b0000_0000_0000_0000_0000_0000_0000_0000, FieldSpec::new(1, 5), 0b0100_0000_0000_0000_0000_0000_0000_0000)]
+  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_0011_1111, FieldSpec::new(2, 2), 0b0100_0000_1111_1100_0000_0000_0000_0000)]
+  #[case(0b0000_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0011_1111_0000_0100_0010, FieldSpec::new(3, 4), 0b0000_0000_0000_0000_0001_0000_1000_0000)]
+  #[case(0b0100_0000_0000_0000_0000_0000_0000_0000, 0b0000_0000_0000_0000_0000_0000_1100_0100, FieldSpec::new(4, 5), 0b0100_0000_0000_0000_0000_0000_1100_0100)]
+  fn write_with_modifier_cases(
+    #[case] number: u32,
+    #[case] value: u32,
+    #[case] field: FieldSpec,
+    #[case] expected: u32,
+  ) {
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(ByteSize::BINARY, Word::new(5, Some(true)), Word::new(7, Some(true)), Word::new(12, Some(true)), false)]
+  #[case(ByteSize::BINARY, Word::new(5, Some(true)), Word::new(7, Some(false)), Word::new(2, Some(false)), false)]
+  #[case(ByteSize::BINARY, Word::new(5, Some(false)), Word::new(5, Some(true)), Word::new(0, Some(false)), false)]
+  #[case(ByteSize::BINARY, Word::new((1 << 30) - 1, Some(true)), Word::new(1, Some(true)), Word::new(0, Some(true)), true)]
+  #[case(ByteSize::BINARY, Word::new((1 << 30) - 1, Some(false)), Word::new(5, Some(false)), Word::new(4, Some(false)), true)]
+  #[case(ByteSize::DECIMAL, Word::from_bytes([99, 99, 99, 99, 99], true), Word::from_bytes([0, 0, 0, 0, 2], true), Word::from_bytes([0, 0, 0, 0, 1], true), true)]
+  #[case(ByteSize::DECIMAL, Word::from_bytes([0, 0, 0, 0, 70], true), Word::from_bytes([0, 0, 0, 0, 40], true), Word::from_bytes([0, 0, 0, 1, 10], true), false)]
+  fn test_checked_add(
+    #[case] byte_size: ByteSize,
+    #[case] word: Word,
+    #[case] other: Word,
+    #[case] result: Word,
+    #[case] overflow: bool,
+  ) {
+    assert_eq!(word.checked_add(&other, byte_size), Overflowing { result, overflow });
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(ByteSize::BINARY, Word::new(5, Some(true)), Word::new(7, Some(false)), Word::new(2, Some(false)), Word::new(2, Some(false)))]
+  #[case(ByteSize::BINARY, Word::new((1 << 30) - 1, Some(true)), Word::new(2, Some(true)), Word::new(1, Some(true)), Word::new((1 << 30) - 1, Some(true)))]
+  #[case(ByteSize::BINARY, Word::new((1 << 30) - 1, Some(false)), Word::new(1, Some(false)), Word::new(0, Some(false)), Word::new((1 << 30) - 1, Some(false)))]
+  #[case(ByteSize::DECIMAL, ByteSize::DECIMAL.word(9_999_999_999, true), ByteSize::DECIMAL.word(5, true), ByteSize::DECIMAL.word(4, true), ByteSize::DECIMAL.word(9_999_999_999, true))]
+  fn test_wrapping_saturating_add(
+    #[case] byte_size: ByteSize,
+    #[case] word: Word,
+    #[case] other: Word,
+    #[case] wrapping: Word,
+    #[case] saturating: Word,
+  ) {
+    let mut negated = other;
+
+    negated.write_sign(!other.read_sign());
+
+    assert_eq!(word.wrapping_add(&other, byte_size), wrapping);
+    assert_eq!(word.saturating_add(&other, byte_size), saturating);
+    assert_eq!(word.wrapping_sub(&negated, byte_size), wrapping);
+    assert_eq!(word.saturating_sub(&negated, byte_size), saturating);
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, 0, 0, [0, 0, 0, 0, 0])]
+  #[case(ByteSize::BINARY, 3000, 1, [46, 56, 0, 0, 1])]
+  #[case(ByteSize::BINARY, 4095, 64, [63, 63, 0, 1, 0])]
+  #[case(ByteSize::DECIMAL, 3000, 1, [30, 0, 0, 0, 1])]
+  #[case(ByteSize::DECIMAL, 9999, 100, [99, 99, 0, 1, 0])]
+  fn test_pack_addresses(
+    #[case] byte_size: ByteSize,
+    #[case] left: u32,
+    #[case] right: u32,
+    #[case] bytes: [u8; 5],
+  ) {
+    let word = Word::pack_addresses(left, right, byte_size).unwrap();
+
+    assert_eq!(word, Word::from_bytes(bytes, true));
+    assert_eq!(word.unpack_addresses(byte_size), (left, right));
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, 4096, 0, 4096)]
+  #[case(ByteSize::BINARY, 0, 5000, 5000)]
+  #[case(ByteSize::DECIMAL, 10_000, 0, 10_000)]
+  fn test_pack_addresses_out_of_range(
+    #[case] byte_size: ByteSize,
+    #[case] left: u32,
+    #[case] right: u32,
+    #[case] address: i64,
+  ) {
+    assert_eq!(
+      Word::pack_addresses(left, right, byte_size),
+      Err(OutOfRange(address))
+    );
+  }
+
+  #[test]
+  fn test_unpack_addresses_ignores_tag() {
+    let mut word = Word::pack_addresses(100, 200, ByteSize::BINARY).unwrap();
+
+    word.write_sign(false);
+    word.set_byte(3, 7);
+
+    assert_eq!(word.unpack_addresses(ByteSize::BINARY), (100, 200));
+  }
+
+  #[rstest]
+  #[case(FieldSpec::WHOLE, Word::from_bytes([6, 7, 8, 9, 10], false))]
+  #[case(FieldSpec::new(0, 0), Word::from_bytes([1, 2, 3, 4, 5], false))]
+  #[case(FieldSpec::new(1, 2), Word::from_bytes([9, 10, 3, 4, 5], true))]
+  #[case(FieldSpec::new(0, 1), Word::from_bytes([10, 2, 3, 4, 5], false))]
+  #[case(FieldSpec::new(5, 5), Word::from_bytes([1, 2, 3, 4, 10], true))]
+  fn test_store(#[case] field: FieldSpec, #[case] expected: Word) {
+    let mut word = Word::from_bytes([1, 2, 3, 4, 5], true);
+
+    word.store(&Word::from_bytes([6, 7, 8, 9, 10], false), field);
+
+    assert_eq!(word, expected);
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(5, Some(true)), Word::new(7, Some(true)), Word::new(2, Some(false)), false)]
+  #[case(Word::new(5, Some(false)), Word::new(5, Some(false)), Word::new(0, Some(false)), false)]
+  #[case(Word::new(0, Some(true)), Word::new(0, Some(true)), Word::new(0, Some(true)), false)]
+  #[case(Word::new((1 << 30) - 1, Some(true)), Word::new(2, Some(false)), Word::new(1, Some(true)), true)]
+  fn test_checked_sub(
+    #[case] word: Word,
+    #[case] other: Word,
+    #[case] result: Word,
+    #[case] overflow: bool,
+  ) {
+    assert_eq!(word.checked_sub(&other, ByteSize::BINARY), Overflowing { result, overflow });
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(ByteSize::BINARY, Word::new(112, Some(false)), Word::new(2, Some(true)), Word::new(0, Some(false)), Word::new(224, Some(false)))]
+  #[case(ByteSize::BINARY, Word::new((1 << 30) - 1, Some(true)), Word::new((1 << 30) - 1, Some(true)), Word::new((1 << 30) - 2, Some(true)), Word::new(1, Some(true)))]
+  #[case(ByteSize::BINARY, Word::new(0, Some(false)), Word::new(5, Some(false)), Word::new(0, Some(true)), Word::new(0, Some(true)))]
+  #[case(ByteSize::DECIMAL, Word::from_bytes([0, 0, 5, 0, 0], true), Word::from_bytes([0, 0, 5, 0, 0], false), Word::from_bytes([0, 0, 0, 0, 0], false), Word::from_bytes([25, 0, 0, 0, 0], false))]
+  fn test_checked_mul(
+    #[case] byte_size: ByteSize,
+    #[case] word: Word,
+    #[case] other: Word,
+    #[case] high: Word,
+    #[case] low: Word,
   ) {
+    assert_eq!(
+      word.checked_mul(&other, byte_size),
+      Overflowing { result: WordPair::new(high, low), overflow: false }
+    );
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(17, Some(true)), Word::new(3, Some(true)), Word::new(5, Some(true)), Word::new(2, Some(true)), false)]
+  #[case(Word::new(17, Some(false)), Word::new(3, Some(true)), Word::new(5, Some(false)), Word::new(2, Some(false)), false)]
+  #[case(Word::new(17, Some(true)), Word::new(3, Some(false)), Word::new(5, Some(false)), Word::new(2, Some(true)), false)]
+  #[case(Word::new(17, Some(true)), Word::new(0, Some(true)), Word::new(0, Some(true)), Word::new(17, Some(true)), true)]
+  fn test_checked_div(
+    #[case] word: Word,
+    #[case] other: Word,
+    #[case] quotient: Word,
+    #[case] remainder: Word,
+    #[case] overflow: bool,
+  ) {
+    assert_eq!(
+      word.checked_div(&other, ByteSize::BINARY),
+      Overflowing { result: WordPair::new(quotient, remainder), overflow }
+    );
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(Word::new(5, Some(true)), Word::new(5, Some(true)), Compare::Equal)]
+  #[case(Word::new(0, Some(true)), Word::new(0, Some(false)), Compare::Equal)]
+  #[case(Word::new(0, Some(false)), Word::new(0, Some(true)), Compare::Equal)]
+  #[case(Word::new(5, Some(true)), Word::new(6, Some(true)), Compare::Less)]
+  #[case(Word::new(5, Some(false)), Word::new(6, Some(false)), Compare::Greater)]
+  #[case(Word::new(5, Some(false)), Word::new(0, Some(true)), Compare::Less)]
+  #[case(Word::new(0, Some(false)), Word::new(1, Some(false)), Compare::Greater)]
+  #[case(Word::new(1 << 24, Some(true)), Word::new((1 << 24) - 1, Some(true)), Compare::Greater)]
+  #[case(Word::from_bytes([0, 0, 0, 1, 0], true), Word::from_bytes([0, 0, 0, 0, 99], true), Compare::Greater)]
+  fn test_mix_cmp(#[case] word: Word, #[case] other: Word, #[case] expected: Compare) {
+    assert_eq!(word.mix_cmp(&other), expected);
+  }
+
+  #[test]
+  fn test_bytes() {
+    let word = Word::from_bytes([1, 2, 3, 4, 99], false);
+
+    assert_eq!(word.bytes().collect::<Vec<_>>(), [1, 2, 3, 4, 99]);
+  }
+
+  #[rstest]
+  #[case(0, 1, Word::from_bytes([1, 2, 3, 4, 5], true))]
+  #[case(0, 0, Word::from_bytes([1, 2, 3, 4, 5], false))]
+  #[case(1, 99, Word::from_bytes([99, 2, 3, 4, 5], false))]
+  #[case(5, 0, Word::from_bytes([1, 2, 3, 4, 0], false))]
+  fn test_set_byte(#[case] index: usize, #[case] value: u8, #[case] expected: Word) {
+    let mut word = Word::from_bytes([1, 2, 3, 4, 5], false);
+
+    word.set_byte(index, value);
+
+    assert_eq!(word, expected);
+    assert_eq!(word.get_byte(index), value);
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_set_byte_out_of_range() {
+    Word::default().set_byte(6, 0);
+  }
+
+  #[rstest]
+  #[case(Word::from_bytes([1, 2, 3, 4, 5], true), "+01 02 03 04 05")]
+  #[case(Word::from_bytes([63, 0, 10, 0, 99], false), "-63 00 10 00 99")]
+  #[case(Word::default(), "+00 00 00 00 00")]
+  fn test_display(#[case] word: Word, #[case] expected: &str) {
+    assert_eq!(word.to_string(), expected);
+  }
+
+  #[rstest]
+  #[case(Word::from_bytes([31, 16, 2, 5, 8], true), "+002000 000002 000005 000008")]
+  #[case(Word::from_bytes([0, 1, 0, 0, 5], false), "-000001 000000 000000 000005")]
+  fn test_display_alternate(#[case] word: Word, #[case] expected: &str) {
+    assert_eq!(format!("{:#}", word), expected);
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, Word::from_bytes([0, 0, 0, 1, 0], true), "+64")]
+  #[case(ByteSize::DECIMAL, Word::from_bytes([0, 0, 0, 1, 0], false), "-100")]
+  #[case(ByteSize::BINARY, Word::from_bytes([0; 5], false), "-0")]
+  fn test_display_as_value(
+    #[case] byte_size: ByteSize,
+    #[case] word: Word,
+    #[case] expected: &str,
+  ) {
+    assert_eq!(word.display_as_value(byte_size).to_string(), expected);
+  }
+
+  #[rstest]
+  #[case(ByteSize::BINARY, Word::from_bytes([31, 16, 1, 5, 8], false), "LDA -2000,1")]
+  #[case(ByteSize::DECIMAL, Word::from_bytes([20, 0, 0, 11, 24], true), "STA 2000(1:3)")]
+  #[case(ByteSize::BINARY, Word::from_bytes([0, 0, 9, 5, 8], true), "+00 00 09 05 08")]
+  fn test_display_as_instruction(
+    #[case] byte_size: ByteSize,
+    #[case] word: Word,
+    #[case] expected: &str,
+  ) {
+    assert_eq!(word.display_as_instruction(byte_size).to_string(), expected);
+  }
+
+  #[rstest]
+  #[case(Word::from_bytes([8, 5, 13, 13, 16], true), "HELLO")]
+  #[case(Word::from_bytes([0, 30, 39, 10, 40], true), " 09Δ.")]
+  #[case(Word::from_bytes([1, 56, 63, 2, 0], false), "A??B ")]
+  fn test_display_as_chars(#[case] word: Word, #[case] expected: &str) {
+    assert_eq!(word.display_as_chars().to_string(), expected);
+  }
+
+  #[rstest]
+  #[case(Word::from_bytes([1, 2, 3, 4, 5], true), "41083105", "1000001000010000011000100000101")]
+  #[case(Word::from_bytes([63, 0, 0, 0, 1], false), "3f000001", "111111000000000000000000000001")]
+  #[case(Word::default(), "40000000", "1000000000000000000000000000000")]
+  fn test_hex_binary(#[case] word: Word, #[case] hex: &str, #[case] binary: &str) {
+    assert_eq!(format!("{:x}", word), hex);
+    assert_eq!(format!("{:b}", word), binary);
+  }
+
+  #[test]
+  fn test_hex_flags() {
+    let word = Word::from_bytes([0, 0, 0, 0, 10], false);
+
+    assert_eq!(format!("{:#010x}", word), "0x0000000a");
+  }
+
+  #[rstest]
+  #[case(Word::from_bytes([1, 2, 3, 4, 5], true), "+01 02 03 04 05")]
+  #[case(Word::from_bytes([63, 8, 0, 10, 99], false), "-77 10 00 12 143")]
+  fn test_octal(#[case] word: Word, #[case] expected: &str) {
+    assert_eq!(format!("{:o}", word), expected);
+  }
+
+  #[rstest]
+  #[case(Word::from_bytes([8, 5, 13, 13, 16], true), Some(['H', 'E', 'L', 'L', 'O']))]
+  #[case(Word::from_bytes([0, 0, 30, 31, 55], false), Some([' ', ' ', '0', '1', '\'']))]
+  #[case(Word::from_bytes([1, 2, 3, 4, 56], true), None)]
+  fn test_to_chars(#[case] word: Word, #[case] expected: Option<[char; 5]>) {
+    assert_eq!(word.to_chars(), expected);
+  }
+
+  #[rstest]
+  #[case(
+    0b0100_0000_0000_0000_0000_0000_0000_0101,
+    Some(true),
+    Ok(Word::new(5, Some(true)))
+  )]
+  #[case(
+    0b0011_1111_1111_1111_1111_1111_1111_1111,
+    None,
+    Ok(Word::new(0b0011_1111_1111_1111_1111_1111_1111_1111, None))
+  )]
+  #[case(0b1000_0000_0000_0000_0000_0000_0000_0000, Some(true), Err(OutOfRange(1 << 31)))]
+  #[case(u32::MAX, Some(false), Err(OutOfRange(u32::MAX as i64)))]
+  fn test_try_new(
+    #[case] number: u32,
+    #[case] sign: Option<bool>,
+    #[case] expected: Result<Word, OutOfRange>,
+  ) {
+    assert_eq!(Word::try_new(number, sign), expected);
+  }
+
+  #[rstest]
+  #[case(0, Ok(Word::new(0, Some(true))))]
+  #[case(-5, Ok(Word::new(5, Some(false))))]
+  #[case((1 << 30) - 1, Ok(Word::new((1 << 30) - 1, Some(true))))]
+  #[case(-(1 << 30) + 1, Ok(Word::new((1 << 30) - 1, Some(false))))]
+  #[case(1 << 30, Err(OutOfRange(1 << 30)))]
+  #[case(-(1 << 30), Err(OutOfRange(-(1 << 30))))]
+  #[case(i64::MIN, Err(OutOfRange(i64::MIN)))]
+  fn test_try_from_i64(#[case] value: i64, #[case] expected: Result<Word, OutOfRange>) {
+    assert_eq!(Word::try_from(value), expected);
   }
 
   #[test]
   fn test_default() {
-    assert!(!Word::default().read_sign());
+    assert!(Word::default().read_sign());
     assert_eq!(Word::default().read_data(), 0);
   }
 
@@ -240,13 +809,52 @@ mod tests {
   }
 
   #[apply(read_with_modifier_cases)]
-  fn test_read_with_modifier(number: u32, expected: u32, sign: bool, modifier: u32) {
+  fn test_read_with_modifier(number: u32, expected: u32, sign: bool, field: FieldSpec) {
+    assert_eq!(
+      Word::new(number, Some(sign)).read_with_modifier(field),
+      Ok(expected)
+    );
+  }
+
+  #[rustfmt::skip]
+  #[rstest]
+  #[case(0b000001_000010_000011_000100_000101, false, FieldSpec::new(0, 0), 0, false)]
+  #[case(0b000001_000010_000011_000100_000101, true, FieldSpec::new(0, 0), 0, true)]
+  #[case(0b000001_000010_000011_000100_000101, false, FieldSpec::new(0, 2), 0b000001_000010, false)]
+  #[case(0b000001_000010_000011_000100_000101, true, FieldSpec::new(0, 2), 0b000001_000010, true)]
+  #[case(0b000001_000010_000011_000100_000101, false, FieldSpec::new(1, 2), 0b000001_000010, true)]
+  #[case(0b000001_000010_000011_000100_000101, false, FieldSpec::new(4, 5), 0b000100_000101, true)]
+  #[case(0b000001_000010_000011_000100_000101, false, FieldSpec::WHOLE, 0b000001_000010_000011_000100_000101, false)]
+  #[case(0, false, FieldSpec::WHOLE, 0, false)]
+  fn test_read_field(
+    #[case] number: u32,
+    #[case] sign: bool,
+    #[case] field: FieldSpec,
+    #[case] expected: u32,
+    #[case] expected_sign: bool,
+  ) {
     assert_eq!(
-      Word::new(number, Some(sign)).read_with_modifier(modifier),
-      expected
+      Word::new(number, Some(sign)).read_field(field),
+      Ok((expected, expected_sign))
     );
   }
 
+  #[apply(write_with_modifier_cases)]
+  fn test_write_with_modifier(number: u32, value: u32, field: FieldSpec, expected: u32) {
+    let mut word = Word::from(number);
+    word.write_with_modifier(value, field).unwrap();
+
+    assert_eq!(word.read(), expected);
+  }
+
+  #[apply(read_with_modifier_cases)]
+  fn test_write_with_modifier_round_trip(number: u32, expected: u32, sign: bool, field: FieldSpec) {
+    let mut word = Word::new(!number, Some(!sign));
+    word.write_with_modifier(expected, field).unwrap();
+
+    assert_eq!(word.read_with_modifier(field), Ok(expected));
+  }
+
   #[apply(data_with_sign_cases)]
   fn test_write(number: u32, expected: u32, sign: bool) {
     let mut word = Word::default();
@@ -257,7 +865,7 @@ mod tests {
 
   #[apply(data_without_sign_cases)]
   fn test_write_data(number: u32, expected: u32) {
-    let mut word = Word::default();
+    let mut word = Word::new(0, Some(false));
     word.write_data(number);
 
     assert_eq!(word.read(), expected);
@@ -281,9 +889,4 @@ mod tests {
   fn test_get_byte(number: u32, expected: u8, sign: bool, index: usize) {
     assert_eq!(Word::new(number, Some(sign)).get_byte(index), expected);
   }
-
-  #[apply(split_modifier_cases)]
-  fn test_split_modifier(modifier: u32, expected: (u32, u32)) {
-    assert_eq!(Word::split_modifier(modifier), expected);
-  }
 }